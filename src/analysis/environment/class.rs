@@ -5,6 +5,8 @@ use analysis::environment::field::analyze_field_declaration;
 use analysis::environment::method::analyze_abstract_method_declaration;
 use analysis::environment::method::analyze_constructor_declaration;
 use analysis::environment::method::analyze_method_declaration;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::ASTNodeImport;
 use scanner::Token;
@@ -14,12 +16,13 @@ pub fn analyze_class_declaration(canonical: &ASTNode,
                                  kinds: &mut Vec<ClassOrInterfaceEnvironment>,
                                  imports: &Vec<ASTNodeImport>,
                                  node: &ASTNode)
-                                 -> Result<(), String> {
+                                 -> Result<(), AnalysisError> {
     let mut current = ClassOrInterfaceEnvironment::new(canonical.clone(), ClassOrInterface::CLASS);
 
     for kind in kinds.clone() {
         if kind.name == current.name {
-            return Err("class/interface names must be unique".to_owned());
+            let message = "class/interface names must be unique".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateType, message));
         }
     }
 
@@ -28,8 +31,9 @@ pub fn analyze_class_declaration(canonical: &ASTNode,
         for import in &current.imports {
             if let Some((import_name, import_package)) = import.import.children.split_last() {
                 if import_name == class_name && import_package != class_package {
-                    return Err(format!("single-type-import declaration clashes with class {}",
-                                       class_name));
+                    let message = format!("single-type-import declaration clashes with class {}",
+                                          class_name);
+                    return Err(AnalysisError::new(AnalysisCode::ImportClash, message));
                 }
             }
         }
@@ -70,8 +74,9 @@ pub fn analyze_class_declaration(canonical: &ASTNode,
                     } else if greatgrandkid.token.kind == TokenKind::Comma {
                         continue;
                     } else {
-                        return Err(format!("got invalid ClassOrInterfaceTypeList child {}",
-                                           greatgrandkid.token));
+                        let message = format!("got invalid ClassOrInterfaceTypeList child {}",
+                                              greatgrandkid.token);
+                        return Err(AnalysisError::other(message));
                     }
                 }
             }
@@ -87,8 +92,8 @@ pub fn analyze_class_declaration(canonical: &ASTNode,
                         .extends
                         .push(child.children[1].clone().flatten().clone());
                 } else {
-                    return Err(format!("got invalid ClassExtends child {}",
-                                       child.children[1].token));
+                    return Err(AnalysisError::other(format!("got invalid ClassExtends child {}",
+                                                            child.children[1].token)));
                 }
             }
             Some(ref le) if le == "ClassBody" && child.children.len() == 3 => {
@@ -126,7 +131,8 @@ pub fn analyze_class_declaration(canonical: &ASTNode,
                                                        &decl.children[1])
                         }
                         Some(ref lex) => {
-                            return Err(format!("no ClassBody analyzer for {}", lex));
+                            let message = format!("no ClassBody analyzer for {}", lex);
+                            return Err(AnalysisError::other(message));
                         }
                         _ => Ok(()),
                     };
@@ -1,10 +1,17 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
 use analysis::environment::field::FieldEnvironment;
 use analysis::environment::method::MethodEnvironment;
 use analysis::types::lookup;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::ASTNodeImport;
+use scanner::Span;
 use scanner::Token;
 use scanner::TokenKind;
 
@@ -42,6 +49,46 @@ impl ClassOrInterfaceEnvironment {
         }
     }
 
+    /// Where this class or interface was declared. The canonical name is
+    /// mostly made up of the package, so only its last segment is used.
+    pub fn span(&self) -> Option<Span> {
+        match self.name.children.last() {
+            Some(name) => name.span(),
+            None => self.name.span(),
+        }
+    }
+
+    /// The class this class extends, if any. Only `java.lang.Object` and
+    /// interfaces have no superclass.
+    pub fn superclass(&self,
+                      kinds: &Vec<ClassOrInterfaceEnvironment>)
+                      -> Result<Option<ClassOrInterfaceEnvironment>, AnalysisError> {
+        if self.kind != ClassOrInterface::CLASS {
+            return Ok(None);
+        }
+
+        match self.extends.first() {
+            Some(extended) => lookup::class::in_env(extended, self, kinds).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The classes and interfaces this class or interface directly extends or
+    /// implements.
+    pub fn supertypes(&self,
+                      kinds: &Vec<ClassOrInterfaceEnvironment>)
+                      -> Result<Vec<ClassOrInterfaceEnvironment>, AnalysisError> {
+        let mut supertypes = Vec::new();
+        for parent in self.extends.iter().chain(self.implements.iter()) {
+            match lookup::class::in_env(parent, self, kinds) {
+                Ok(found) => supertypes.push(found),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(supertypes)
+    }
+
     /// This method is used for building subclasses. If `A extends B, C`, we
     /// can build a new subclass by applying this function to `A`.
     ///
@@ -60,7 +107,7 @@ impl ClassOrInterfaceEnvironment {
     pub fn apply(&mut self,
                  child: &ClassOrInterfaceEnvironment,
                  kinds: &Vec<ClassOrInterfaceEnvironment>)
-                 -> Result<(), String> {
+                 -> Result<(), AnalysisError> {
         let modifier_final = ASTNode {
             token: Token::new(TokenKind::Final, None),
             children: Vec::new(),
@@ -165,30 +212,36 @@ impl ClassOrInterfaceEnvironment {
                             Err(e) => return Err(e),
                         };
                     if method_return_type.name != existing_return_type.name {
-                        return Err(format!("cannot override method {} with different return type",
-                                           method.name));
+                        let message = format!("cannot override method {} with different \
+                                               return type",
+                                              method.name);
+                        return Err(AnalysisError::new(AnalysisCode::OverrideReturnType, message));
                     }
 
                     if existing.modifiers.contains(&modifier_final) &&
                        existing.body != method.body {
-                        return Err(format!("cannot override final method {}", existing.name));
+                        let message = format!("cannot override final method {}", existing.name);
+                        return Err(AnalysisError::new(AnalysisCode::OverrideFinal, message));
                     }
 
                     if existing.modifiers.contains(&public) &&
                        (method.modifiers.contains(&protected) ||
                         method.modifiers.contains(&private)) {
-                        return Err(format!("cannot weaken perms of public method {}",
-                                           existing.name));
+                        let message = format!("cannot weaken perms of public method {}",
+                                              existing.name);
+                        return Err(AnalysisError::new(AnalysisCode::WeakerAccess, message));
                     } else if existing.modifiers.contains(&protected) &&
                               method.modifiers.contains(&private) {
-                        return Err(format!("cannot weaken perms of protected method {}",
-                                           existing.name));
+                        let message = format!("cannot weaken perms of protected method {}",
+                                              existing.name);
+                        return Err(AnalysisError::new(AnalysisCode::WeakerAccess, message));
                     }
 
                     if existing.modifiers.contains(&modifier_static) !=
                        method.modifiers.contains(&modifier_static) {
-                        return Err(format!("cannot override method {} with different static",
-                                           existing.name));
+                        let message = format!("cannot override method {} with different static",
+                                              existing.name);
+                        return Err(AnalysisError::new(AnalysisCode::OverrideStatic, message));
                     }
 
                     self.methods.remove(idx);
@@ -224,7 +277,7 @@ impl ClassOrInterfaceEnvironment {
     pub fn inherit(&mut self,
                    parent: &ClassOrInterfaceEnvironment,
                    kinds: &Vec<ClassOrInterfaceEnvironment>)
-                   -> Result<(), String> {
+                   -> Result<(), AnalysisError> {
         let modifier_abstract = ASTNode {
             token: Token::new(TokenKind::Abstract, None),
             children: Vec::new(),
@@ -328,33 +381,41 @@ impl ClassOrInterfaceEnvironment {
                             Err(e) => return Err(e),
                         };
                     if method_return_type.name != existing_return_type.name {
-                        return Err(format!("could not inherit methods {} with conflicting returns",
-                                           method.name));
+                        let message = format!("could not inherit methods {} with \
+                                               conflicting returns",
+                                              method.name);
+                        return Err(AnalysisError::new(AnalysisCode::OverrideReturnType, message));
                     }
 
                     if existing.modifiers.contains(&modifier_abstract) {
                         if !method.modifiers.contains(&modifier_abstract) {
                             if existing.modifiers.contains(&modifier_final) &&
                                existing.body != method.body {
-                                return Err(format!("cannot override final method {}",
-                                                   existing.name));
+                                let message = format!("cannot override final method {}",
+                                                      existing.name);
+                                return Err(AnalysisError::new(AnalysisCode::OverrideFinal,
+                                                              message));
                             }
 
                             if existing.modifiers.contains(&public) &&
                                (method.modifiers.contains(&protected) ||
                                 method.modifiers.contains(&private)) {
-                                return Err(format!("cannot weaken perms of public method {}",
-                                                   existing.name));
+                                let message = format!("cannot weaken perms of public method {}",
+                                                      existing.name);
+                                return Err(AnalysisError::new(AnalysisCode::WeakerAccess, message));
                             } else if existing.modifiers.contains(&protected) &&
                                       method.modifiers.contains(&private) {
-                                return Err(format!("cannot weaken perms of protected method {}",
-                                                   existing.name));
+                                let message = format!("cannot weaken perms of protected method {}",
+                                                      existing.name);
+                                return Err(AnalysisError::new(AnalysisCode::WeakerAccess, message));
                             }
 
                             if existing.modifiers.contains(&modifier_static) !=
                                method.modifiers.contains(&modifier_static) {
-                                return Err(format!("cannot override staticity of method {}",
-                                                   existing.name));
+                                let message = format!("cannot override staticity of method {}",
+                                                      existing.name);
+                                return Err(AnalysisError::new(AnalysisCode::OverrideStatic,
+                                                              message));
                             }
 
                             self.methods.remove(idx);
@@ -369,32 +430,40 @@ impl ClassOrInterfaceEnvironment {
                                 break;
                             }
 
-                            return Err(format!("could not inherit conflicting methods {}",
-                                               method.name));
+                            let message = format!("could not inherit conflicting methods {}",
+                                                  method.name);
+                            return Err(AnalysisError::other(message));
                         } else {
                             // existing -> concrete, method -> abstract
                             // thus we're basically overridding `method` with
                             // `existing`
                             if method.modifiers.contains(&modifier_final) &&
                                existing.body != method.body {
-                                return Err(format!("cannot override final method {}", method.name));
+                                let message = format!("cannot override final method {}",
+                                                      method.name);
+                                return Err(AnalysisError::new(AnalysisCode::OverrideFinal,
+                                                              message));
                             }
 
                             if method.modifiers.contains(&public) &&
                                (existing.modifiers.contains(&protected) ||
                                 existing.modifiers.contains(&private)) {
-                                return Err(format!("cannot weaken perms of public method {}",
-                                                   method.name));
+                                let message = format!("cannot weaken perms of public method {}",
+                                                      method.name);
+                                return Err(AnalysisError::new(AnalysisCode::WeakerAccess, message));
                             } else if method.modifiers.contains(&protected) &&
                                       existing.modifiers.contains(&private) {
-                                return Err(format!("cannot weaken perms of protected method {}",
-                                                   method.name));
+                                let message = format!("cannot weaken perms of protected method {}",
+                                                      method.name);
+                                return Err(AnalysisError::new(AnalysisCode::WeakerAccess, message));
                             }
 
                             if method.modifiers.contains(&modifier_static) !=
                                existing.modifiers.contains(&modifier_static) {
-                                return Err(format!("cannot override staticity of method {}",
-                                                   method.name));
+                                let message = format!("cannot override staticity of method {}",
+                                                      method.name);
+                                return Err(AnalysisError::new(AnalysisCode::OverrideStatic,
+                                                              message));
                             }
 
                             overwrite = false;
@@ -418,3 +487,28 @@ impl fmt::Display for ClassOrInterfaceEnvironment {
         write!(f, "{}", self.name)
     }
 }
+
+impl ToJson for ClassOrInterfaceEnvironment {
+    fn to_json(&self) -> Json {
+        let kind = match self.kind {
+            ClassOrInterface::CLASS => "class",
+            ClassOrInterface::INTERFACE => "interface",
+        };
+        let names = |nodes: &Vec<ASTNode>| {
+            nodes.iter().map(|n| n.source_text()).collect::<Vec<String>>().to_json()
+        };
+
+        let mut object = BTreeMap::new();
+        object.insert("kind".to_owned(), kind.to_json());
+        object.insert("name".to_owned(), self.name.source_text().to_json());
+        object.insert("modifiers".to_owned(), names(&self.modifiers));
+        object.insert("extends".to_owned(), names(&self.extends));
+        object.insert("implements".to_owned(), names(&self.implements));
+        object.insert("imports".to_owned(), self.imports.to_json());
+        object.insert("fields".to_owned(), self.fields.to_json());
+        object.insert("constructors".to_owned(), self.constructors.to_json());
+        object.insert("methods".to_owned(), self.methods.to_json());
+        object.insert("span".to_owned(), self.name.span().to_json());
+        Json::Object(object)
+    }
+}
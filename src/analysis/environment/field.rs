@@ -1,5 +1,13 @@
+use std::collections::BTreeMap;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
 use analysis::environment::ClassOrInterfaceEnvironment;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
+use scanner::Span;
 
 #[derive(Clone,Debug)]
 pub struct FieldEnvironment {
@@ -30,11 +38,30 @@ impl FieldEnvironment {
             value: None,
         }
     }
+
+    /// Where this field was declared.
+    pub fn span(&self) -> Option<Span> {
+        self.name.span()
+    }
+}
+
+// Initializers are left out; they can be found in the AST.
+impl ToJson for FieldEnvironment {
+    fn to_json(&self) -> Json {
+        let modifiers = self.modifiers.iter().map(|m| m.source_text()).collect::<Vec<String>>();
+
+        let mut object = BTreeMap::new();
+        object.insert("name".to_owned(), self.name.source_text().to_json());
+        object.insert("type".to_owned(), self.kind.source_text().to_json());
+        object.insert("modifiers".to_owned(), modifiers.to_json());
+        object.insert("span".to_owned(), self.name.span().to_json());
+        Json::Object(object)
+    }
 }
 
 pub fn analyze_constant_declaration(current: &mut ClassOrInterfaceEnvironment,
                                     declaration: &ASTNode)
-                                    -> Result<(), String> {
+                                    -> Result<(), AnalysisError> {
     let mut new = FieldEnvironment::new(declaration.children[2].clone(),
                                         declaration.children[1].clone());
 
@@ -56,7 +83,8 @@ pub fn analyze_constant_declaration(current: &mut ClassOrInterfaceEnvironment,
 
     for field in current.fields.clone() {
         if field.name == new.name {
-            return Err("field names must be unique".to_owned());
+            let message = "field names must be unique".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateField, message));
         }
     }
 
@@ -66,7 +94,7 @@ pub fn analyze_constant_declaration(current: &mut ClassOrInterfaceEnvironment,
 
 pub fn analyze_field_declaration(fields: &mut Vec<FieldEnvironment>,
                                  declaration: &ASTNode)
-                                 -> Result<(), String> {
+                                 -> Result<(), AnalysisError> {
     let mut new = FieldEnvironment::new(declaration.children[2].clone(),
                                         declaration.children[1].clone());
 
@@ -76,7 +104,8 @@ pub fn analyze_field_declaration(fields: &mut Vec<FieldEnvironment>,
 
     for field in fields.clone() {
         if field.name == new.name {
-            return Err("field names must be unique".to_owned());
+            let message = "field names must be unique".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateField, message));
         }
     }
 
@@ -2,6 +2,8 @@ use analysis::environment::classorinterface::ClassOrInterface;
 use analysis::environment::classorinterface::ClassOrInterfaceEnvironment;
 use analysis::environment::field::analyze_constant_declaration;
 use analysis::environment::method::analyze_abstract_method_declaration;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::ASTNodeImport;
 use scanner::Token;
@@ -11,13 +13,14 @@ pub fn analyze_interface_declaration(canonical: &ASTNode,
                                      kinds: &mut Vec<ClassOrInterfaceEnvironment>,
                                      imports: &Vec<ASTNodeImport>,
                                      node: &ASTNode)
-                                     -> Result<(), String> {
+                                     -> Result<(), AnalysisError> {
     let mut current = ClassOrInterfaceEnvironment::new(canonical.clone(),
                                                        ClassOrInterface::INTERFACE);
 
     for kind in kinds.clone() {
         if kind.name == current.name {
-            return Err("class/interface names must be unique".to_owned());
+            let message = "class/interface names must be unique".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateType, message));
         }
     }
 
@@ -26,8 +29,10 @@ pub fn analyze_interface_declaration(canonical: &ASTNode,
         for import in &current.imports {
             if let Some((import_name, import_package)) = import.import.children.split_last() {
                 if import_name == interface_name && import_package != interface_package {
-                    return Err(format!("single-type-import declaration clashes with interface {}",
-                                       interface_name));
+                    let message = format!("single-type-import declaration clashes with \
+                                           interface {}",
+                                          interface_name);
+                    return Err(AnalysisError::new(AnalysisCode::ImportClash, message));
                 }
             }
         }
@@ -68,8 +73,9 @@ pub fn analyze_interface_declaration(canonical: &ASTNode,
                     } else if greatgrandkid.token.kind == TokenKind::Comma {
                         continue;
                     } else {
-                        return Err(format!("got invalid ClassOrInterfaceTypeList child {}",
-                                           greatgrandkid.token));
+                        let message = format!("got invalid ClassOrInterfaceTypeList child {}",
+                                              greatgrandkid.token);
+                        return Err(AnalysisError::other(message));
                     }
                 }
             }
@@ -94,7 +100,8 @@ pub fn analyze_interface_declaration(canonical: &ASTNode,
                             analyze_constant_declaration(&mut current, &decl)
                         }
                         Some(ref lex) => {
-                            return Err(format!("no InterfaceBody analyzer for {}", lex));
+                            let message = format!("no InterfaceBody analyzer for {}", lex);
+                            return Err(AnalysisError::other(message));
                         }
                         _ => Ok(()),
                     };
@@ -1,9 +1,16 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
 use analysis::environment::classorinterface::ClassOrInterface;
 use analysis::environment::classorinterface::ClassOrInterfaceEnvironment;
 use analysis::environment::variable::VariableEnvironment;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
+use scanner::Span;
 use scanner::Token;
 use scanner::TokenKind;
 
@@ -35,6 +42,11 @@ impl MethodEnvironment {
         }
     }
 
+    /// Where this method or constructor was declared.
+    pub fn span(&self) -> Option<Span> {
+        self.name.span()
+    }
+
     pub fn to_label(&self, class_label: String) -> Result<String, String> {
         let mut label: Vec<String> = Vec::new();
         label.push("__".to_owned());
@@ -79,9 +91,26 @@ impl fmt::Display for MethodEnvironment {
     }
 }
 
+// Bodies are left out; they can be found in the AST.
+impl ToJson for MethodEnvironment {
+    fn to_json(&self) -> Json {
+        let modifiers = self.modifiers.iter().map(|m| m.source_text()).collect::<Vec<String>>();
+
+        let mut object = BTreeMap::new();
+        object.insert("name".to_owned(), self.name.source_text().to_json());
+        object.insert("return_type".to_owned(), self.return_type.source_text().to_json());
+        object.insert("modifiers".to_owned(), modifiers.to_json());
+        object.insert("parameters".to_owned(), self.parameters.to_json());
+        object.insert("parent".to_owned(),
+                      self.parent.as_ref().map(|p| p.source_text()).to_json());
+        object.insert("span".to_owned(), self.name.span().to_json());
+        Json::Object(object)
+    }
+}
+
 pub fn analyze_abstract_method_declaration(current: &mut ClassOrInterfaceEnvironment,
                                            header: &ASTNode)
-                                           -> Result<(), String> {
+                                           -> Result<(), AnalysisError> {
     let declarator = header.children[2].clone();
 
     let mut new = MethodEnvironment::new(declarator.children[0].clone(),
@@ -115,16 +144,15 @@ pub fn analyze_abstract_method_declaration(current: &mut ClassOrInterfaceEnviron
                 continue;
             }
 
-            let mut param = VariableEnvironment::new(param.clone());
-            param.initialized = true;
-            new.parameters.push(param);
+            new.parameters.push(VariableEnvironment::new(param.clone()));
         }
     }
 
     for method in current.methods.clone() {
         if method.name == new.name && method.parameters == new.parameters {
             // TODO: check after inheritance?
-            return Err("methods must have unique signatures".to_owned());
+            let message = "methods must have unique signatures".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateMethod, message));
         }
     }
 
@@ -136,7 +164,7 @@ pub fn analyze_constructor_declaration(current: &mut ClassOrInterfaceEnvironment
                                        modifiers: &ASTNode,
                                        declarator: &ASTNode,
                                        body: &ASTNode)
-                                       -> Result<(), String> {
+                                       -> Result<(), AnalysisError> {
     let mut new = MethodEnvironment::new(declarator.children[0].clone(), current.name.clone());
 
     for child in modifiers.clone().children {
@@ -159,9 +187,7 @@ pub fn analyze_constructor_declaration(current: &mut ClassOrInterfaceEnvironment
                 continue;
             }
 
-            let mut param = VariableEnvironment::new(param.clone());
-            param.initialized = true;
-            new.parameters.push(param);
+            new.parameters.push(VariableEnvironment::new(param.clone()));
         }
     }
 
@@ -169,7 +195,8 @@ pub fn analyze_constructor_declaration(current: &mut ClassOrInterfaceEnvironment
 
     for constructor in &current.constructors {
         if constructor.parameters == new.parameters {
-            return Err("constructors must have unique signatures".to_owned());
+            let message = "constructors must have unique signatures".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateConstructor, message));
         }
     }
 
@@ -180,7 +207,7 @@ pub fn analyze_constructor_declaration(current: &mut ClassOrInterfaceEnvironment
 pub fn analyze_method_declaration(current: &mut ClassOrInterfaceEnvironment,
                                   header: &ASTNode,
                                   body: &ASTNode)
-                                  -> Result<(), String> {
+                                  -> Result<(), AnalysisError> {
     let declarator = header.children[2].clone();
 
     let mut new = MethodEnvironment::new(declarator.children[0].clone(),
@@ -206,9 +233,7 @@ pub fn analyze_method_declaration(current: &mut ClassOrInterfaceEnvironment,
                 continue;
             }
 
-            let mut param = VariableEnvironment::new(param.clone());
-            param.initialized = true;
-            new.parameters.push(param);
+            new.parameters.push(VariableEnvironment::new(param.clone()));
         }
     }
 
@@ -217,7 +242,8 @@ pub fn analyze_method_declaration(current: &mut ClassOrInterfaceEnvironment,
     for method in current.methods.clone() {
         if method.name == new.name && method.parameters == new.parameters {
             // TODO: check after inheritance?
-            return Err("methods must have unique signatures".to_owned());
+            let message = "methods must have unique signatures".to_owned();
+            return Err(AnalysisError::new(AnalysisCode::DuplicateMethod, message));
         }
     }
 
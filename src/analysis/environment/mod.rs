@@ -5,6 +5,20 @@ mod interface;
 mod method;
 mod variable;
 
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
+use analysis::types::annotation::Annotations;
+use error::AnalysisCode;
+use error::AnalysisError;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Lint;
+use error::Phase;
+use error::Warning;
 use scanner::AST;
 use scanner::ASTNode;
 use scanner::Token;
@@ -21,16 +35,24 @@ pub use self::variable::VariableEnvironment;
 #[derive(Clone,Debug)]
 pub struct Environment {
     pub kinds: Vec<ClassOrInterfaceEnvironment>,
+    /// the type of each expression, filled in by type checking
+    pub annotations: Annotations,
 }
 
 impl Environment {
-    pub fn new(trees: &Vec<AST>) -> Result<Environment, String> {
+    pub fn new(trees: &Vec<AST>,
+               warnings: &mut Vec<Warning>)
+               -> Result<Environment, Vec<Diagnostic>> {
         let node_star = ASTNode {
             token: Token::new(TokenKind::Star, None),
             children: Vec::new(),
         };
 
-        let mut env = Environment { kinds: Vec::new() };
+        let mut env = Environment {
+            kinds: Vec::new(),
+            annotations: Annotations::new(),
+        };
+        let mut errors = Vec::new();
 
         for tree in trees {
             let root = match tree.root {
@@ -52,7 +74,12 @@ impl Environment {
             };
             match result {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => {
+                    let name = tree.canonical.children.last().unwrap_or(&tree.canonical);
+                    errors.push(Diagnostic::new(Phase::Environment,
+                                                ErrorMessage::EnvironmentError(e),
+                                                name.span()));
+                }
             }
 
             'import: for import in &tree.imports {
@@ -66,8 +93,23 @@ impl Environment {
                             }
                         }
                         if !found {
-                            return Err(format!("could not find imported package {}",
-                                               import_package));
+                            let message = format!("could not find imported package {}",
+                                                  import_package);
+                            let reason = AnalysisError::new(AnalysisCode::MissingImport, message);
+                            errors.push(Diagnostic::new(Phase::Environment,
+                                                        ErrorMessage::EnvironmentError(reason),
+                                                        import.import.span()));
+                            continue 'import;
+                        }
+
+                        if let Some(ref simple) = import_name.token.lexeme {
+                            if !root.mentions(simple) {
+                                let message = format!("unused import {}",
+                                                      import.import.source_text());
+                                warnings.push(Warning::new(Lint::UnusedImport,
+                                                           message,
+                                                           import.import.span()));
+                            }
                         }
                     }
                 }
@@ -84,10 +126,84 @@ impl Environment {
                     }
                 }
 
-                return Err(format!("could not find imported package {}", import_package));
+                let message = format!("could not find imported package {}", import_package);
+                let reason = AnalysisError::new(AnalysisCode::MissingImport, message);
+                errors.push(Diagnostic::new(Phase::Environment,
+                                            ErrorMessage::EnvironmentError(reason),
+                                            import.import.span()));
             }
         }
 
-        Ok(env)
+        if errors.is_empty() {
+            Ok(env)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn write_method(f: &mut fmt::Formatter,
+                label: &str,
+                method: &MethodEnvironment)
+                -> fmt::Result {
+    try!(write!(f, "  {}", label));
+    for modifier in &method.modifiers {
+        try!(write!(f, " {}", modifier.source_text()));
+    }
+    if label == "method" {
+        try!(write!(f, " {}", method.return_type.source_text()));
+    }
+
+    let parameters = method.parameters
+        .iter()
+        .map(|p| format!("{} {}", p.kind.source_text(), p.name.source_text()))
+        .collect::<Vec<String>>();
+    writeln!(f, " {}({})", method.name.source_text(), parameters.join(", "))
+}
+
+// Writes every class and interface with its supertypes and members, one per
+// line.
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for kind in &self.kinds {
+            let keyword = match kind.kind {
+                ClassOrInterface::CLASS => "class",
+                ClassOrInterface::INTERFACE => "interface",
+            };
+            try!(write!(f, "{}", keyword));
+            for modifier in &kind.modifiers {
+                try!(write!(f, " {}", modifier.source_text()));
+            }
+            try!(writeln!(f, " {}", kind.name.source_text()));
+
+            for parent in &kind.extends {
+                try!(writeln!(f, "  extends {}", parent.source_text()));
+            }
+            for parent in &kind.implements {
+                try!(writeln!(f, "  implements {}", parent.source_text()));
+            }
+            for field in &kind.fields {
+                try!(write!(f, "  field"));
+                for modifier in &field.modifiers {
+                    try!(write!(f, " {}", modifier.source_text()));
+                }
+                try!(writeln!(f, " {} {}", field.kind.source_text(), field.name.source_text()));
+            }
+            for constructor in &kind.constructors {
+                try!(write_method(f, "constructor", constructor));
+            }
+            for method in &kind.methods {
+                try!(write_method(f, "method", method));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToJson for Environment {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("kinds".to_owned(), self.kinds.to_json());
+        Json::Object(object)
     }
 }
@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
 use scanner::ASTNode;
+use scanner::Span;
 use scanner::Token;
 use scanner::TokenKind;
 
@@ -9,7 +14,6 @@ pub struct VariableEnvironment {
     pub kind: ASTNode,
     pub name: ASTNode,
     pub dim: bool,
-    pub initialized: bool,
 }
 
 impl VariableEnvironment {
@@ -49,9 +53,13 @@ impl VariableEnvironment {
                 }
             },
             dim: dim,
-            initialized: false,
         }
     }
+
+    /// Where this variable or parameter was declared.
+    pub fn span(&self) -> Option<Span> {
+        self.name.span()
+    }
 }
 
 impl PartialEq for VariableEnvironment {
@@ -69,3 +77,13 @@ impl fmt::Display for VariableEnvironment {
         Ok(())
     }
 }
+
+impl ToJson for VariableEnvironment {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("name".to_owned(), self.name.source_text().to_json());
+        object.insert("type".to_owned(), self.kind.source_text().to_json());
+        object.insert("span".to_owned(), self.name.span().to_json());
+        Json::Object(object)
+    }
+}
@@ -3,102 +3,96 @@
 mod environment;
 mod types;
 
-use std;
-
+use error::Diagnostic;
+use error::Warning;
 use scanner::AST;
 
+pub use self::environment::ClassOrInterface;
 pub use self::environment::ClassOrInterfaceEnvironment;
 pub use self::environment::Environment;
 pub use self::environment::MethodEnvironment;
 pub use self::environment::FieldEnvironment;
 pub use self::environment::VariableEnvironment;
+pub use self::types::annotation::Annotation;
+pub use self::types::annotation::Annotations;
+pub use self::types::annotation::Declaration;
 use self::types::verify;
 
-/// Runs a set of ASTs through the analysis stack (environment builder) and
-/// exits with code 42 on a failure.
+/// Runs a set of ASTs through the analysis stack (environment builder and
+/// type checker), reporting every error found. Suspicious but legal code is
+/// added to `warnings`, whether or not analysis succeeds.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// let filename = "Sample.java";
-/// let contents = juicyj::scanner::read_src_file(&filename.to_owned());
-/// let ast = juicyj::scanner::scan_or_exit(&filename, &contents);
-/// juicyj::analysis::analyze_or_exit(&vec![ast])
+/// let contents = juicyj::scanner::read_src_file(&filename).unwrap();
+/// let mut sources = juicyj::scanner::SourceMap::new();
+/// let file = sources.add(&filename, &contents);
+/// let ast = juicyj::scanner::scan(&sources, file).ok().unwrap();
+/// let mut warnings = Vec::new();
+/// match juicyj::analysis::analyze(&vec![ast], &mut warnings) {
+///     Ok(env) => println!("{} kinds, {} warnings", env.kinds.len(), warnings.len()),
+///     Err(errors) => println!("found {} errors", errors.len()),
+/// }
 /// ```
-pub fn analyze_or_exit(asts: &Vec<AST>) -> Environment {
-    let mut env = match Environment::new(asts) {
+pub fn analyze(asts: &Vec<AST>,
+               warnings: &mut Vec<Warning>)
+               -> Result<Environment, Vec<Diagnostic>> {
+    let mut env = match Environment::new(asts, warnings) {
         Ok(e) => e,
-        Err(e) => {
-            println!("{}", e);
-            std::process::exit(42);
-        }
+        Err(e) => return Err(e),
     };
 
-    match verify(&mut env) {
-        Ok(_) => (),
-        Err(e) => {
-            println!("{}", e);
-            std::process::exit(42);
-        }
+    match verify(&mut env, warnings) {
+        Ok(_) => Ok(env),
+        Err(e) => Err(e),
     }
-
-    env
 }
 
 // TODO: this should be #[cfg(test)], but for some reason the test macros can't
 // find this module in that case.
 #[allow(missing_docs)]
 pub mod tests {
-    use std;
-
-    use super::super::scanner::AST;
+    use compiler::Compiler;
 
-    use super::environment::Environment;
-    use super::types::verify;
-
-    pub fn analyze_and_assert(asts: &Vec<AST>) {
-        let mut env = match Environment::new(asts) {
-            Ok(e) => e,
+    pub fn analyze_and_assert(compiler: &mut Compiler) {
+        match compiler.parse() {
+            Ok(_) => (),
             Err(e) => {
-                println!("Annotation Error");
-                println!("{}", e);
-                assert!(true);
+                println!("Scanner Error");
+                for error in e {
+                    println!("{}", error.render(compiler.sources()));
+                }
+                assert!(false);
                 return;
             }
-        };
+        }
 
-        match verify(&mut env) {
-            Ok(_) => (),
+        match compiler.analyze() {
+            Ok(_) => {
+                println!("No Error Found");
+                assert!(false);
+            }
             Err(e) => {
-                println!("Verification Error");
-                println!("{}", e);
+                println!("Analysis Error");
+                for error in e {
+                    println!("{}", error.render(compiler.sources()));
+                }
                 assert!(true);
-                return;
             }
         }
-
-        println!("No Error Found");
-        assert!(false);
     }
 
-    pub fn analyze_or_assert(asts: &Vec<AST>) {
-        let mut env = match Environment::new(asts) {
-            Ok(e) => e,
-            Err(e) => {
-                println!("Annotation Error");
-                println!("{}", e);
-                assert!(false);
-                std::process::exit(1);
-            }
-        };
-
-        match verify(&mut env) {
+    pub fn analyze_or_assert(compiler: &mut Compiler) {
+        match compiler.analyze() {
             Ok(_) => (),
             Err(e) => {
-                println!("Verification Error");
-                println!("{}", e);
+                println!("Analysis Error");
+                for error in e {
+                    println!("{}", error.render(compiler.sources()));
+                }
                 assert!(false);
-                std::process::exit(1);
             }
         }
     }
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use analysis::environment::FieldEnvironment;
+use analysis::environment::MethodEnvironment;
+use analysis::environment::VariableEnvironment;
+use analysis::types::obj::Type;
+use scanner::ASTNode;
+use scanner::Span;
+use scanner::TokenKind;
+
+/// The declaration an expression refers to.
+#[derive(Clone,Debug)]
+pub enum Declaration {
+    /// a local variable or parameter
+    Local(VariableEnvironment),
+    /// a field, along with the name of the class which declares it
+    Field(ASTNode, FieldEnvironment),
+    /// a method or constructor, along with the name of the class which
+    /// declares it
+    Method(ASTNode, MethodEnvironment),
+    /// a class or interface, eg. the `Foo` in `Foo.bar()`
+    Type(ASTNode),
+}
+
+/// What type checking learned about a single expression.
+#[derive(Clone,Debug)]
+pub struct Annotation {
+    /// the name of the expression's type, eg. `int`, `java.lang.String` or
+    /// `int[]`
+    pub kind: ASTNode,
+    /// what the expression refers to, if it is a name, field access, method
+    /// invocation or class instance creation
+    pub declaration: Option<Declaration>,
+}
+
+/// A side table giving the Annotation of each expression in an Environment,
+/// once it has been type checked. Expressions are identified by where they
+/// were written, so nodes built during analysis, which have no Span, are never
+/// annotated. Variable and field declarations are annotated on their names.
+#[derive(Clone,Debug)]
+pub struct Annotations {
+    annotations: HashMap<Span, Annotation>,
+}
+
+impl Annotations {
+    pub fn new() -> Annotations {
+        Annotations { annotations: HashMap::new() }
+    }
+
+    /// The Annotation of an expression, if it has been type checked.
+    pub fn get(&self, node: &ASTNode) -> Option<&Annotation> {
+        match node.span() {
+            Some(span) => self.annotations.get(&span),
+            None => None,
+        }
+    }
+
+    /// The name of the type of an expression, as used in code generation, eg.
+    /// `int`, `java.lang.String` or `int[]`.
+    pub fn label(&self, node: &ASTNode) -> Option<String> {
+        self.get(node).map(|a| a.kind.source_text())
+    }
+
+    /// Records the type of an expression and, if it is known, what it refers
+    /// to. Resolving an expression again keeps the declaration found before.
+    pub fn annotate(&mut self, node: &ASTNode, kind: &Type, declaration: Option<Declaration>) {
+        let span = match node.span() {
+            Some(s) => s,
+            None => return,
+        };
+
+        let mut name = kind.kind.name.clone();
+        name.flatten();
+        if name.children.is_empty() && name.token.kind != TokenKind::Identifier {
+            // the type of a literal keeps its value, eg. `int` for `1`
+            name.token.lexeme = None;
+        }
+        let declaration = match (declaration, self.annotations.remove(&span)) {
+            (Some(d), _) => Some(d),
+            (None, Some(a)) => a.declaration,
+            (None, None) => None,
+        };
+        self.annotations.insert(span,
+                                Annotation {
+                                    kind: name,
+                                    declaration: declaration,
+                                });
+    }
+
+    /// Records that a name refers to a local variable or parameter, giving it
+    /// the type the variable was annotated with where it was declared.
+    pub fn refer(&mut self, node: &ASTNode, local: &VariableEnvironment) {
+        let span = match node.span() {
+            Some(s) => s,
+            None => return,
+        };
+        let kind = match self.get(&local.name) {
+            Some(a) => a.kind.clone(),
+            None => return,
+        };
+
+        self.annotations.insert(span,
+                                Annotation {
+                                    kind: kind,
+                                    declaration: Some(Declaration::Local(local.clone())),
+                                });
+    }
+}
@@ -0,0 +1,421 @@
+use std::char;
+use std::collections::HashMap;
+use std::fmt;
+
+use analysis::environment::ClassOrInterfaceEnvironment;
+use analysis::environment::Environment;
+use analysis::environment::VariableEnvironment;
+use analysis::types::lookup;
+use scanner::ASTNode;
+use scanner::Token;
+use scanner::TokenKind;
+
+/// The value of a constant expression (JLS 15.28).
+#[derive(Clone,Debug,PartialEq)]
+pub enum Constant {
+    Boolean(bool),
+    Character(char),
+    Integer(i32),
+    String(String),
+}
+
+impl Constant {
+    // Chars are promoted to ints by every numeric operator.
+    fn integer(&self) -> Option<i32> {
+        match *self {
+            Constant::Character(c) => Some(c as i32),
+            Constant::Integer(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    // Converts a value to the primitive or String type `kind`, as done by a
+    // cast or by assigning it to a variable of that type.
+    fn convert(&self, kind: &ASTNode) -> Option<Constant> {
+        match kind.token.kind {
+            TokenKind::Boolean => {
+                match *self {
+                    Constant::Boolean(b) => Some(Constant::Boolean(b)),
+                    _ => None,
+                }
+            }
+            TokenKind::Byte => self.integer().map(|i| Constant::Integer(i as i8 as i32)),
+            TokenKind::Char => {
+                match self.integer() {
+                    Some(i) => char::from_u32(i as u16 as u32).map(Constant::Character),
+                    None => None,
+                }
+            }
+            TokenKind::Int => self.integer().map(Constant::Integer),
+            TokenKind::Short => self.integer().map(|i| Constant::Integer(i as i16 as i32)),
+            _ => {
+                let name = kind.source_text();
+                match *self {
+                    Constant::String(_) if name == "String" || name == "java.lang.String" => {
+                        Some(self.clone())
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// A literal node holding this value, which replaces the expression
+    /// `node`.
+    pub fn to_node(&self, node: &ASTNode) -> ASTNode {
+        let kind = match *self {
+            Constant::Boolean(true) => TokenKind::True,
+            Constant::Boolean(false) => TokenKind::False,
+            Constant::Character(_) => TokenKind::CharValue,
+            Constant::Integer(_) => TokenKind::NumValue,
+            Constant::String(_) => TokenKind::StrValue,
+        };
+
+        ASTNode {
+            token: Token {
+                kind: kind,
+                lexeme: Some(self.to_string()),
+                span: node.span(),
+            },
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Writes a value as the lexeme of its literal: chars and Strings keep their
+/// escape sequences, as they do when read by the lexer.
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Constant::Boolean(b) => write!(f, "{}", b),
+            Constant::Character(c) => write!(f, "{}", escape(c)),
+            Constant::Integer(i) => write!(f, "{}", i),
+            Constant::String(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// Char literals keep their escape sequences, eg. `\n` or `\377`.
+fn character(lexeme: &str) -> Option<char> {
+    let mut chars = lexeme.chars();
+    match (chars.next(), chars.next()) {
+        (Some('\\'), Some('b')) => Some('\u{8}'),
+        (Some('\\'), Some('t')) => Some('\t'),
+        (Some('\\'), Some('n')) => Some('\n'),
+        (Some('\\'), Some('f')) => Some('\u{c}'),
+        (Some('\\'), Some('r')) => Some('\r'),
+        (Some('\\'), Some(c)) if c.is_digit(8) => {
+            match u8::from_str_radix(&lexeme[1..], 8) {
+                Ok(value) => Some(value as char),
+                Err(_) => None,
+            }
+        }
+        (Some('\\'), Some(c)) => Some(c),
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+// The inverse of `character`.
+fn escape(c: char) -> String {
+    match c {
+        '\u{8}' => "\\b".to_owned(),
+        '\t' => "\\t".to_owned(),
+        '\n' => "\\n".to_owned(),
+        '\u{c}' => "\\f".to_owned(),
+        '\r' => "\\r".to_owned(),
+        '\'' | '"' | '\\' => format!("\\{}", c),
+        c if (c as u32) < 0o40 || (c as u32) == 0o177 => format!("\\{:o}", c as u32),
+        c => c.to_string(),
+    }
+}
+
+fn lexeme(node: &ASTNode) -> &str {
+    match node.token.lexeme {
+        Some(ref l) => l,
+        None => "",
+    }
+}
+
+fn is(node: &ASTNode, nonterminal: &str) -> bool {
+    node.token.kind == TokenKind::NonTerminal && lexeme(node) == nonterminal
+}
+
+fn binary(kind: &TokenKind, lhs: Constant, rhs: Constant) -> Option<Constant> {
+    match (kind, &lhs, &rhs) {
+        (&TokenKind::Plus, &Constant::String(ref l), r) => {
+            return Some(Constant::String(format!("{}{}", l, r)))
+        }
+        (&TokenKind::Plus, l, &Constant::String(ref r)) => {
+            return Some(Constant::String(format!("{}{}", l, r)))
+        }
+        (_, &Constant::Boolean(l), &Constant::Boolean(r)) => {
+            return match *kind {
+                TokenKind::And | TokenKind::BitAnd => Some(Constant::Boolean(l && r)),
+                TokenKind::Or | TokenKind::BitOr => Some(Constant::Boolean(l || r)),
+                TokenKind::BitXor | TokenKind::NotEqual => Some(Constant::Boolean(l != r)),
+                TokenKind::Equality => Some(Constant::Boolean(l == r)),
+                _ => None,
+            }
+        }
+        _ => (),
+    }
+
+    let (l, r) = match (lhs.integer(), rhs.integer()) {
+        (Some(l), Some(r)) => (l, r),
+        _ => return None,
+    };
+
+    // Integer arithmetic wraps around on overflow (JLS 15.17, 15.18).
+    match *kind {
+        TokenKind::Plus => Some(Constant::Integer(l.wrapping_add(r))),
+        TokenKind::Minus => Some(Constant::Integer(l.wrapping_sub(r))),
+        TokenKind::Star => Some(Constant::Integer(l.wrapping_mul(r))),
+        TokenKind::FSlash if r == 0 => None,
+        TokenKind::FSlash => Some(Constant::Integer(l.wrapping_div(r))),
+        TokenKind::Percent if r == 0 => None,
+        TokenKind::Percent => Some(Constant::Integer(l.wrapping_rem(r))),
+        TokenKind::LessThan => Some(Constant::Boolean(l < r)),
+        TokenKind::LessThanOrEqual => Some(Constant::Boolean(l <= r)),
+        TokenKind::GreaterThan => Some(Constant::Boolean(l > r)),
+        TokenKind::GreaterThanOrEqual => Some(Constant::Boolean(l >= r)),
+        TokenKind::Equality => Some(Constant::Boolean(l == r)),
+        TokenKind::NotEqual => Some(Constant::Boolean(l != r)),
+        _ => None,
+    }
+}
+
+/// Evaluates an expression, if it is a constant expression (JLS 15.28). The
+/// value of each Name is found with `names`, which should only give values
+/// for constant variables. Division by zero is not a constant.
+pub fn evaluate(node: &ASTNode, names: &Fn(&ASTNode) -> Option<Constant>) -> Option<Constant> {
+    match node.token.kind {
+        TokenKind::True => Some(Constant::Boolean(true)),
+        TokenKind::False => Some(Constant::Boolean(false)),
+        // Literals are at most 2^31, which is only valid when negated: the
+        // wrapping subtraction from zero restores its sign.
+        TokenKind::NumValue => {
+            match lexeme(node).parse::<i64>() {
+                Ok(value) => Some(Constant::Integer(value as i32)),
+                Err(_) => None,
+            }
+        }
+        TokenKind::CharValue => character(lexeme(node)).map(Constant::Character),
+        TokenKind::StrValue => Some(Constant::String(lexeme(node).to_owned())),
+        TokenKind::Identifier => names(node),
+        TokenKind::Not if node.children.len() == 1 => {
+            match evaluate(&node.children[0], names) {
+                Some(Constant::Boolean(value)) => Some(Constant::Boolean(!value)),
+                _ => None,
+            }
+        }
+        _ if node.children.len() == 2 && node.token.kind != TokenKind::NonTerminal => {
+            let lhs = match evaluate(&node.children[0], names) {
+                Some(value) => value,
+                None => return None,
+            };
+            let rhs = match evaluate(&node.children[1], names) {
+                Some(value) => value,
+                None => return None,
+            };
+            binary(&node.token.kind, lhs, rhs)
+        }
+        _ if is(node, "Name") => names(node),
+        // ( Type ) Expression, without a Dim
+        _ if is(node, "CastExpression") && node.children.len() == 4 => {
+            match evaluate(&node.children[3], names) {
+                Some(value) => value.convert(&node.children[1]),
+                None => None,
+            }
+        }
+        _ if is(node, "PrimaryNoNewArray") && node.children.len() == 3 => {
+            evaluate(&node.children[1], names)
+        }
+        _ => None,
+    }
+}
+
+/// Replaces every constant expression within a node with a literal of its
+/// value. Names which are assigned to or invoked, and types, are left alone.
+pub fn fold(node: &mut ASTNode, names: &Fn(&ASTNode) -> Option<Constant>) {
+    if let Some(value) = evaluate(node, names) {
+        *node = value.to_node(node);
+        return;
+    }
+
+    let children = match node.token.kind {
+        TokenKind::NonTerminal => {
+            match lexeme(node) {
+                "Name" => Vec::new(),
+                // Type Declarator, where the declarator may be an Assignment
+                "LocalVariableDeclaration" => {
+                    match node.children[1].token.kind {
+                        TokenKind::Assignment => {
+                            fold(&mut node.children[1].children[1], names);
+                            return;
+                        }
+                        _ => Vec::new(),
+                    }
+                }
+                // Lhs = Rhs
+                "Assignment" => vec![2],
+                "ArrayCreationExpression" |
+                "ClassInstanceCreationExpression" => (1..node.children.len()).collect(),
+                "CastExpression" => vec![node.children.len() - 1],
+                "FieldAccess" => vec![0],
+                "MethodInvocation" => {
+                    (0..node.children.len())
+                        .filter(|&idx| {
+                                    !is(&node.children[idx], "Name") &&
+                                    node.children[idx].token.kind != TokenKind::Identifier
+                                })
+                        .collect()
+                }
+                _ => (0..node.children.len()).collect(),
+            }
+        }
+        TokenKind::Instanceof => vec![0],
+        _ => (0..node.children.len()).collect(),
+    };
+
+    for idx in children {
+        fold(&mut node.children[idx], names);
+    }
+}
+
+/// The names of every parameter and local variable of a method, any of which
+/// may hide a field.
+pub fn locals(parameters: &Vec<VariableEnvironment>, body: &ASTNode) -> Vec<String> {
+    fn declared(node: &ASTNode, names: &mut Vec<String>) {
+        if is(node, "LocalVariableDeclaration") {
+            names.push(VariableEnvironment::new(node.clone()).name.source_text());
+        }
+        for child in &node.children {
+            declared(child, names);
+        }
+    }
+
+    let mut names = parameters.iter().map(|p| p.name.source_text()).collect::<Vec<String>>();
+    declared(body, &mut names);
+    names
+}
+
+/// The values of every constant variable: each static final field whose
+/// initializer is a constant expression.
+pub struct Constants {
+    values: HashMap<String, Constant>,
+}
+
+impl Constants {
+    /// Evaluates the initializer of every static final field. Initializers
+    /// may refer to other constant variables, declared in any order, so they
+    /// are evaluated until no more values are found.
+    pub fn new(kinds: &Vec<ClassOrInterfaceEnvironment>) -> Constants {
+        let mut constants = Constants { values: HashMap::new() };
+
+        let mut found = true;
+        while found {
+            found = false;
+            for kind in kinds {
+                for field in &kind.fields {
+                    let key = format!("{}.{}", kind.name.source_text(), field.name.source_text());
+                    let modifiers = field.modifiers.iter().map(|m| &m.token.kind);
+                    let modifiers = modifiers.collect::<Vec<&TokenKind>>();
+                    if !modifiers.contains(&&TokenKind::Static) ||
+                       !modifiers.contains(&&TokenKind::Final) ||
+                       constants.values.contains_key(&key) {
+                        continue;
+                    }
+
+                    let value = match field.value {
+                        Some(ref value) => {
+                            evaluate(value, &|n| constants.name(n, kind, kinds, &Vec::new()))
+                        }
+                        None => None,
+                    };
+                    if let Some(value) = value.and_then(|v| v.convert(&field.kind)) {
+                        constants.values.insert(key, value);
+                        found = true;
+                    }
+                }
+            }
+        }
+
+        constants
+    }
+
+    /// The value of a Name used in class `current`, if it refers to a
+    /// constant variable: either a simple name of a field of `current` which
+    /// is not hidden by one of `locals`, or a field qualified by a type.
+    pub fn name(&self,
+                node: &ASTNode,
+                current: &ClassOrInterfaceEnvironment,
+                kinds: &Vec<ClassOrInterfaceEnvironment>,
+                locals: &Vec<String>)
+                -> Option<Constant> {
+        let mut name = node.clone();
+        name.flatten();
+        if name.token.kind == TokenKind::Identifier {
+            name = ASTNode {
+                token: Token::new(TokenKind::NonTerminal, Some("Name")),
+                children: vec![name],
+            };
+        }
+
+        let identifiers = name.children
+            .iter()
+            .filter(|c| c.token.kind != TokenKind::Dot)
+            .map(|c| c.source_text())
+            .collect::<Vec<String>>();
+        if identifiers.is_empty() || locals.contains(&identifiers[0]) {
+            return None;
+        }
+
+        let field = identifiers.last().unwrap();
+        if identifiers.len() == 1 {
+            let key = format!("{}.{}", current.name.source_text(), field);
+            return self.values.get(&key).cloned();
+        }
+
+        // A qualified name only names a type if its first identifier is not a
+        // field of the current class.
+        if current.fields.iter().any(|f| f.name.source_text() == identifiers[0]) {
+            return None;
+        }
+
+        let mut kind = name.clone();
+        kind.children.truncate(name.children.len() - 2);
+        match lookup::class::in_env(&kind, current, kinds) {
+            Ok(kind) => {
+                let key = format!("{}.{}", kind.name.source_text(), field);
+                self.values.get(&key).cloned()
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+/// Folds the constant expressions in every method, constructor and field
+/// initializer of a type checked Environment, so that code is only generated
+/// for the values which are not known at compile time.
+pub fn fold_env(env: &mut Environment) {
+    let kinds = env.kinds.clone();
+    let constants = Constants::new(&kinds);
+
+    for current in &mut env.kinds {
+        let curr = current.clone();
+
+        for field in &mut current.fields {
+            if let Some(ref mut value) = field.value {
+                fold(value, &|n| constants.name(n, &curr, &kinds, &Vec::new()));
+            }
+        }
+
+        for method in current.constructors.iter_mut().chain(current.methods.iter_mut()) {
+            if let Some(ref mut body) = method.body {
+                let locals = locals(&method.parameters, body);
+                fold(body, &|n| constants.name(n, &curr, &kinds, &locals));
+            }
+        }
+    }
+}
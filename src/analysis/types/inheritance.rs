@@ -2,6 +2,8 @@ use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::Environment;
 use analysis::types::lookup;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -18,9 +20,10 @@ lazy_static! {
 pub fn verify(env: &Environment,
               current: &ClassOrInterfaceEnvironment,
               visited: &mut Vec<ASTNode>)
-              -> Result<ClassOrInterfaceEnvironment, String> {
+              -> Result<ClassOrInterfaceEnvironment, AnalysisError> {
     if visited.contains(&current.name) {
-        return Err("cyclic class hierarchy detected".to_owned());
+        let message = "cyclic class hierarchy detected".to_owned();
+        return Err(AnalysisError::new(AnalysisCode::CyclicHierarchy, message));
     }
     visited.push(current.name.clone());
 
@@ -65,9 +68,10 @@ pub fn verify(env: &Environment,
     if child.kind == ClassOrInterface::CLASS && !child.modifiers.contains(&*ABSTRACT) {
         for method in &child.methods {
             if method.modifiers.contains(&*ABSTRACT) {
-                return Err(format!("abstract method {} found in non-abstract class {}",
-                                   method.name,
-                                   child.name));
+                let message = format!("abstract method {} found in non-abstract class {}",
+                                      method.name,
+                                      child.name);
+                return Err(AnalysisError::new(AnalysisCode::AbstractInConcrete, message));
             }
         }
     }
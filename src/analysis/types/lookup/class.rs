@@ -1,8 +1,11 @@
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::lookup;
 use analysis::types::lookup::array;
 use analysis::types::verify;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -24,7 +27,7 @@ const PRIMITIVES: [TokenKind; 7] = [TokenKind::Boolean,
 pub fn in_env(name: &ASTNode,
               current: &ClassOrInterfaceEnvironment,
               kinds: &Vec<ClassOrInterfaceEnvironment>)
-              -> Result<ClassOrInterfaceEnvironment, String> {
+              -> Result<ClassOrInterfaceEnvironment, AnalysisError> {
     let mut name = name.clone();
     name.flatten();
 
@@ -51,7 +54,7 @@ pub fn in_variables(name: &ASTNode,
                     current: &ClassOrInterfaceEnvironment,
                     kinds: &Vec<ClassOrInterfaceEnvironment>,
                     variables: &Vec<VariableEnvironment>)
-                    -> Result<ClassOrInterfaceEnvironment, String> {
+                    -> Result<ClassOrInterfaceEnvironment, AnalysisError> {
     for var in variables {
         if &var.name == name {
             return in_env(&var.kind, current, kinds);
@@ -59,13 +62,13 @@ pub fn in_variables(name: &ASTNode,
     }
 
     // TODO: list variables? print current class?
-    Err(format!("could not lookup kind {:?} in variables", name))
+    Err(AnalysisError::other(format!("could not lookup kind {:?} in variables", name)))
 }
 
 pub fn lookup_step0_canonical(name: &ASTNode,
                               current: &ClassOrInterfaceEnvironment,
                               kinds: &Vec<ClassOrInterfaceEnvironment>)
-                              -> Option<Result<ClassOrInterfaceEnvironment, String>> {
+                              -> Option<Result<ClassOrInterfaceEnvironment, AnalysisError>> {
     for kind in kinds {
         if name != &kind.name {
             continue;
@@ -84,7 +87,7 @@ pub fn lookup_step0_canonical(name: &ASTNode,
 
 fn lookup_step1_enclosing_class(name: &ASTNode,
                                 current: &ClassOrInterfaceEnvironment)
-                                -> Option<Result<ClassOrInterfaceEnvironment, String>> {
+                                -> Option<Result<ClassOrInterfaceEnvironment, AnalysisError>> {
     if let Some(class_name) = current.name.children.last() {
         if name != class_name {
             return None;
@@ -99,7 +102,7 @@ fn lookup_step1_enclosing_class(name: &ASTNode,
 fn lookup_step2_import_single(name: &ASTNode,
                               current: &ClassOrInterfaceEnvironment,
                               kinds: &Vec<ClassOrInterfaceEnvironment>)
-                              -> Option<Result<ClassOrInterfaceEnvironment, String>> {
+                              -> Option<Result<ClassOrInterfaceEnvironment, AnalysisError>> {
     for import in &current.imports {
         if let Some(import_name) = import.import.children.last() {
             if *import_name == *STAR || import_name != name {
@@ -113,16 +116,17 @@ fn lookup_step2_import_single(name: &ASTNode,
                 }
 
                 if found.is_some() {
-                    return Some(Err(format!("ambiguous type lookup for import {:?}",
-                                            import.import)));
+                    let message = format!("ambiguous type lookup for import {:?}", import.import);
+                    return Some(Err(AnalysisError::other(message)));
                 }
 
                 found = Some(Ok(kind.clone()));
             }
 
             if found.is_none() {
-                return Some(Err(format!("could not find type for imported lookup {:?}",
-                                        import.import)));
+                let message = format!("could not find type for imported lookup {:?}",
+                                      import.import);
+                return Some(Err(AnalysisError::other(message)));
             }
 
             return found;
@@ -132,10 +136,11 @@ fn lookup_step2_import_single(name: &ASTNode,
     None
 }
 
-pub fn lookup_step3_enclosing_package(name: &ASTNode,
-                                      current: &ClassOrInterfaceEnvironment,
-                                      kinds: &Vec<ClassOrInterfaceEnvironment>)
-                                      -> Option<Result<ClassOrInterfaceEnvironment, String>> {
+pub fn lookup_step3_enclosing_package
+    (name: &ASTNode,
+     current: &ClassOrInterfaceEnvironment,
+     kinds: &Vec<ClassOrInterfaceEnvironment>)
+     -> Option<Result<ClassOrInterfaceEnvironment, AnalysisError>> {
     let mut found = None;
     for kind in kinds {
         if let Some((kind_name, kind_package)) = kind.name.children.split_last() {
@@ -145,7 +150,8 @@ pub fn lookup_step3_enclosing_package(name: &ASTNode,
                 }
 
                 if found.is_some() {
-                    return Some(Err(format!("ambiguous type lookup in package {:?}", package)));
+                    let message = format!("ambiguous type lookup in package {:?}", package);
+                    return Some(Err(AnalysisError::other(message)));
                 }
 
                 found = Some(Ok(kind.clone()));
@@ -159,7 +165,7 @@ pub fn lookup_step3_enclosing_package(name: &ASTNode,
 fn lookup_step4_import_ondemand(name: &ASTNode,
                                 current: &ClassOrInterfaceEnvironment,
                                 kinds: &Vec<ClassOrInterfaceEnvironment>)
-                                -> Option<Result<ClassOrInterfaceEnvironment, String>> {
+                                -> Option<Result<ClassOrInterfaceEnvironment, AnalysisError>> {
     let mut found = None;
     for import in &current.imports {
         if let Some((import_name, import_package)) = import.import.children.split_last() {
@@ -174,9 +180,10 @@ fn lookup_step4_import_ondemand(name: &ASTNode,
                     }
 
                     if found.is_some() {
-                        return Some(Err(format!("ambiguous on-demand lookup for {:?} in {:?}",
-                                                name,
-                                                kind_package)));
+                        let message = format!("ambiguous on-demand lookup for {:?} in {:?}",
+                                              name,
+                                              kind_package);
+                        return Some(Err(AnalysisError::other(message)));
                     }
 
                     found = Some(Ok(kind.clone()));
@@ -191,7 +198,7 @@ fn lookup_step4_import_ondemand(name: &ASTNode,
 fn lookup(name: &ASTNode,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>)
-          -> Result<ClassOrInterfaceEnvironment, String> {
+          -> Result<ClassOrInterfaceEnvironment, AnalysisError> {
     let result = lookup_step0_canonical(name, current, kinds);
     if result.is_some() {
         return result.unwrap();
@@ -217,7 +224,41 @@ fn lookup(name: &ASTNode,
         return result.unwrap();
     }
 
-    Err(format!("could not lookup kind {:?} from class {:?}",
-                name,
-                current.name))
+    let message = format!("could not find type {} from class {}",
+                          name.source_text(),
+                          current.name.source_text());
+    let candidates = match name.children.len() {
+        0 | 1 => visible(current, kinds),
+        _ => kinds.iter().map(|k| k.name.source_text()).collect(),
+    };
+    let error = AnalysisError::new(AnalysisCode::UnresolvedType, message);
+    Err(lookup::suggest(error, &name.source_text(), candidates))
+}
+
+/// The simple names of every class and interface which can be referred to
+/// from `current` without qualification.
+pub fn visible(current: &ClassOrInterfaceEnvironment,
+               kinds: &Vec<ClassOrInterfaceEnvironment>)
+               -> Vec<String> {
+    let mut names = Vec::new();
+    for kind in kinds {
+        if let Some((kind_name, kind_package)) = kind.name.children.split_last() {
+            let mut is_visible = kind.name == current.name;
+            if let Some((_, package)) = current.name.children.split_last() {
+                is_visible |= package == kind_package;
+            }
+
+            for import in &current.imports {
+                if let Some((import_name, import_package)) = import.import.children.split_last() {
+                    is_visible |= import.import == kind.name ||
+                                  (*import_name == *STAR && import_package == kind_package);
+                }
+            }
+
+            if is_visible && !names.contains(&kind_name.source_text()) {
+                names.push(kind_name.source_text());
+            }
+        }
+    }
+    names
 }
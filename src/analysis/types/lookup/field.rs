@@ -3,6 +3,8 @@ use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -37,7 +39,7 @@ pub fn in_env(canonical: &ASTNode,
               field: &ASTNode,
               current: &ClassOrInterfaceEnvironment,
               kinds: &Vec<ClassOrInterfaceEnvironment>)
-              -> Result<Type, String> {
+              -> Result<Type, AnalysisError> {
     loop {
         let cls = match lookup::class::in_env(&canonical, current, kinds) {
             Ok(cls) => cls,
@@ -62,9 +64,10 @@ pub fn in_env(canonical: &ASTNode,
                 let result = match lookup::class::in_env(&f.kind, &cls, kinds) {
                     Ok(cls) => cls,
                     Err(_) => {
-                        return Err(format!("could not lookup kind {} of field in class {}",
-                                           f.kind,
-                                           cls))
+                        let message = format!("could not lookup kind {} of field in class {}",
+                                              f.kind,
+                                              cls);
+                        return Err(AnalysisError::other(message));
                     }
                 };
 
@@ -80,17 +83,23 @@ pub fn in_env(canonical: &ASTNode,
             }
         }
 
-        return Err(format!("resolved field {} to class {} without that field",
-                           field,
-                           cls.name));
+        let name = field.children.first().unwrap_or(field).source_text();
+        let message = format!("could not find field {} in class {}",
+                              name,
+                              cls.name.source_text());
+        let candidates = cls.fields.iter().map(|f| f.name.source_text());
+        let error = AnalysisError::new(AnalysisCode::UnresolvedField, message);
+        return Err(lookup::suggest(error, &name, candidates));
     }
 
     let mut canonical = canonical.clone();
     let mut field = field.clone();
     match move_two(&mut canonical, &mut field) {
         Ok(()) => in_env(&canonical, &field, current, kinds),
-        // TODO: better error message
-        Err(()) => Err(format!("could not lookup field in environment")),
+        Err(()) => {
+            Err(AnalysisError::other(format!("could not resolve {} to a type or field",
+                                             canonical.source_text())))
+        }
     }
 }
 
@@ -99,7 +108,7 @@ pub fn in_variables(canonical: &ASTNode,
                     current: &ClassOrInterfaceEnvironment,
                     kinds: &Vec<ClassOrInterfaceEnvironment>,
                     variables: &Vec<VariableEnvironment>)
-                    -> Result<Type, String> {
+                    -> Result<Type, AnalysisError> {
     let mut canonical = canonical.clone();
     let mut field = field.clone();
 
@@ -111,7 +120,9 @@ pub fn in_variables(canonical: &ASTNode,
 
     match move_two(&mut canonical, &mut field) {
         Ok(()) => in_variables(&canonical, &field, current, kinds, variables),
-        // TODO: better error message
-        Err(()) => Err(format!("could not lookup field in variables")),
+        Err(()) => {
+            let message = format!("could not find variable {}", canonical.source_text());
+            Err(AnalysisError::other(message))
+        }
     }
 }
@@ -3,6 +3,8 @@ use analysis::environment::MethodEnvironment;
 use analysis::environment::VariableEnvironment;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -32,11 +34,50 @@ fn move_two(cls: &mut ASTNode, method: &mut ASTNode) -> Result<(), ()> {
     Ok(())
 }
 
+// Names the type of an argument. Literals are typed by their token, eg. the
+// type of `1` is `int`.
+fn type_name(arg: &Type) -> String {
+    let name = &arg.kind.name;
+    match name.token.kind {
+        TokenKind::Boolean | TokenKind::Byte | TokenKind::Char | TokenKind::Int |
+        TokenKind::Null | TokenKind::Short if name.children.is_empty() => {
+            format!("{:?}", name.token.kind).to_lowercase()
+        }
+        _ => name.source_text(),
+    }
+}
+
+// Describes an invocation of one of `methods` with `args`, eg. `foo(int, Foo)`.
+fn signature(methods: &Vec<MethodEnvironment>, args: &Vec<Type>) -> String {
+    let name = match methods.first() {
+        Some(m) => m.name.source_text(),
+        None => "method".to_owned(),
+    };
+    let args = args.iter().map(type_name).collect::<Vec<String>>();
+    format!("{}({})", name, args.join(", "))
+}
+
+// Lists every overload which could have been invoked.
+fn with_candidates(error: AnalysisError, methods: &Vec<MethodEnvironment>) -> AnalysisError {
+    let mut error = error;
+    for method in methods {
+        let parameters = method.parameters
+            .iter()
+            .map(|p| p.kind.source_text())
+            .collect::<Vec<String>>();
+        let candidate = format!("candidate: {}({})",
+                                method.name.source_text(),
+                                parameters.join(", "));
+        error = error.with_note(candidate);
+    }
+    error
+}
+
 pub fn select_method(methods: &Vec<MethodEnvironment>,
                      args: &Vec<Type>,
                      cls: &ClassOrInterfaceEnvironment,
                      kinds: &Vec<ClassOrInterfaceEnvironment>)
-                     -> Result<MethodEnvironment, String> {
+                     -> Result<MethodEnvironment, AnalysisError> {
     let mut best: Option<MethodEnvironment> = None;
     let mut distance = <u32>::max_value();
 
@@ -82,11 +123,18 @@ pub fn select_method(methods: &Vec<MethodEnvironment>,
     }
 
     if distance == <u32>::max_value() {
-        return Err(format!("no method matching parameters was found"));
+        let message = format!("no method matching parameters was found for {}",
+                              signature(methods, args));
+        let error = AnalysisError::new(AnalysisCode::NoMatchingMethod, message);
+        return Err(with_candidates(error, methods));
     }
 
     if best.is_none() {
-        return Err(format!("ambiguous methods were found with distance {}", distance));
+        let message = format!("ambiguous methods were found with distance {} for {}",
+                              distance,
+                              signature(methods, args));
+        let error = AnalysisError::new(AnalysisCode::AmbiguousMethod, message);
+        return Err(with_candidates(error, methods));
     }
 
     Ok(best.unwrap())
@@ -98,7 +146,7 @@ pub fn in_env(canonical: &ASTNode,
               args: &Vec<Type>,
               current: &ClassOrInterfaceEnvironment,
               kinds: &Vec<ClassOrInterfaceEnvironment>)
-              -> Result<(ClassOrInterfaceEnvironment, MethodEnvironment), String> {
+              -> Result<(ClassOrInterfaceEnvironment, MethodEnvironment), AnalysisError> {
     loop {
         let cls = match lookup::class::in_env(&canonical, current, kinds) {
             Ok(cls) => cls,
@@ -123,7 +171,8 @@ pub fn in_env(canonical: &ASTNode,
 
                 if !remaining_method.children.is_empty() {
                     // Method chains should be seperate MethodInvocations, no?
-                    return Err(format!("TODO: clearly, I do not understand methods"));
+                    let message = format!("TODO: clearly, I do not understand methods");
+                    return Err(AnalysisError::other(message));
                 }
 
                 methods.push(m.clone());
@@ -144,7 +193,8 @@ pub fn in_env(canonical: &ASTNode,
 
                 if remaining_method.children.is_empty() {
                     // h was a field?
-                    return Err(format!("TODO: uh... what? MethodIsNotAMethodError."));
+                    let message = format!("TODO: uh... what? MethodIsNotAMethodError.");
+                    return Err(AnalysisError::other(message));
                 }
 
                 return match lookup::class::in_env(&f.kind, &cls, kinds) {
@@ -154,25 +204,33 @@ pub fn in_env(canonical: &ASTNode,
                     in_env(&cls.name, &remaining_method, args, &cls, kinds)
                 }
                            Err(_) => {
-                               Err(format!("could not lookup kind {} of field in class {}",
-                                           f.kind,
-                                           cls))
+                               let message = format!("could not lookup kind {} of field in \
+                                                      class {}",
+                                                     f.kind,
+                                                     cls);
+                               Err(AnalysisError::other(message))
                            }
                        };
             }
         }
 
-        return Err(format!("resolved method {} to class {} without that method",
-                           method,
-                           cls.name));
+        let name = method.children.first().unwrap_or(method).source_text();
+        let message = format!("could not find method {} in class {}",
+                              name,
+                              cls.name.source_text());
+        let candidates = cls.methods.iter().map(|m| m.name.source_text());
+        let error = AnalysisError::new(AnalysisCode::UnresolvedMethod, message);
+        return Err(lookup::suggest(error, &name, candidates));
     }
 
     let mut canonical = canonical.clone();
     let mut method = method.clone();
     match move_two(&mut canonical, &mut method) {
         Ok(()) => in_env(&canonical, &method, args, current, kinds),
-        // TODO: better error message
-        Err(()) => Err(format!("could not lookup method in environment")),
+        Err(()) => {
+            Err(AnalysisError::other(format!("could not resolve {} to a type or field",
+                                             canonical.source_text())))
+        }
     }
 }
 
@@ -182,7 +240,7 @@ pub fn in_variables(canonical: &ASTNode,
                     current: &ClassOrInterfaceEnvironment,
                     kinds: &Vec<ClassOrInterfaceEnvironment>,
                     variables: &Vec<VariableEnvironment>)
-                    -> Result<(ClassOrInterfaceEnvironment, MethodEnvironment), String> {
+                    -> Result<(ClassOrInterfaceEnvironment, MethodEnvironment), AnalysisError> {
     let mut canonical = canonical.clone();
     let mut method = method.clone();
 
@@ -194,7 +252,9 @@ pub fn in_variables(canonical: &ASTNode,
 
     match move_two(&mut canonical, &mut method) {
         Ok(()) => in_variables(&canonical, &method, args, current, kinds, variables),
-        // TODO: better error message
-        Err(()) => Err(format!("could not lookup method in variables")),
+        Err(()) => {
+            let message = format!("could not find variable {}", canonical.source_text());
+            Err(AnalysisError::other(message))
+        }
     }
 }
@@ -1,4 +1,74 @@
+use error::AnalysisError;
+
 pub mod array;
 pub mod class;
 pub mod field;
 pub mod method;
+
+#[cfg(test)]
+mod test;
+
+// The number of single-character insertions, deletions, substitutions and
+// transpositions of adjacent characters needed to turn `lhs` into `rhs`.
+fn distance(lhs: &str, rhs: &str) -> usize {
+    let lhs = lhs.chars().collect::<Vec<char>>();
+    let rhs = rhs.chars().collect::<Vec<char>>();
+
+    let mut table = vec![vec![0; rhs.len() + 1]; lhs.len() + 1];
+    for i in 0..lhs.len() + 1 {
+        table[i][0] = i;
+    }
+    for j in 0..rhs.len() + 1 {
+        table[0][j] = j;
+    }
+
+    for i in 1..lhs.len() + 1 {
+        for j in 1..rhs.len() + 1 {
+            let cost = if lhs[i - 1] == rhs[j - 1] { 0 } else { 1 };
+            let mut best = *[table[i - 1][j] + 1, table[i][j - 1] + 1, table[i - 1][j - 1] + cost]
+                .iter()
+                .min()
+                .unwrap();
+            if i > 1 && j > 1 && lhs[i - 1] == rhs[j - 2] && lhs[i - 2] == rhs[j - 1] {
+                best = ::std::cmp::min(best, table[i - 2][j - 2] + 1);
+            }
+            table[i][j] = best;
+        }
+    }
+
+    table[lhs.len()][rhs.len()]
+}
+
+/// Finds the candidate closest to `name`, if any is close enough that `name`
+/// is likely to be a typo of it. Candidates equal to `name` are ignored.
+pub fn closest<I>(name: &str, candidates: I) -> Option<String>
+    where I: IntoIterator<Item = String>
+{
+    let limit = ::std::cmp::max(1, name.chars().count() / 3);
+
+    let mut best: Option<(usize, String)> = None;
+    for candidate in candidates {
+        let d = distance(&name.to_lowercase(), &candidate.to_lowercase());
+        if candidate == name || d > limit {
+            continue;
+        }
+
+        match best {
+            Some((bd, _)) if bd <= d => (),
+            _ => best = Some((d, candidate)),
+        }
+    }
+
+    best.map(|(_, candidate)| candidate)
+}
+
+/// Notes a suggestion of the candidate closest to `name` on an error, if there
+/// is one.
+pub fn suggest<I>(error: AnalysisError, name: &str, candidates: I) -> AnalysisError
+    where I: IntoIterator<Item = String>
+{
+    match closest(name, candidates) {
+        Some(candidate) => error.with_note(format!("did you mean `{}`?", candidate)),
+        None => error,
+    }
+}
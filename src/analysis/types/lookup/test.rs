@@ -0,0 +1,44 @@
+use super::closest;
+use super::suggest;
+use error::AnalysisError;
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn test_closest_typo() {
+    assert_eq!(closest("totl", names(&["count", "total"])), Some("total".to_owned()));
+}
+
+#[test]
+fn test_closest_transposition() {
+    assert_eq!(closest("lenght", names(&["length"])), Some("length".to_owned()));
+}
+
+#[test]
+fn test_closest_ignores_case() {
+    assert_eq!(closest("string", names(&["String", "Object"])), Some("String".to_owned()));
+}
+
+#[test]
+fn test_closest_too_far() {
+    assert_eq!(closest("foo", names(&["bar", "baz"])), None);
+}
+
+#[test]
+fn test_closest_ignores_exact_match() {
+    assert_eq!(closest("foo", names(&["foo"])), None);
+}
+
+#[test]
+fn test_suggest() {
+    let error = AnalysisError::other("could not find x".to_owned());
+    let error = suggest(error, "cont", names(&["count"]));
+    assert_eq!(error.message, "could not find x");
+    assert_eq!(error.notes, vec!["did you mean `count`?".to_owned()]);
+
+    let error = AnalysisError::other("could not find x".to_owned());
+    let error = suggest(error, "x", Vec::new());
+    assert!(error.notes.is_empty());
+}
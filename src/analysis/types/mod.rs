@@ -1,3 +1,5 @@
+pub mod annotation;
+mod constant;
 mod inheritance;
 pub mod lookup;
 mod obj;
@@ -7,11 +9,24 @@ pub mod verify;
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::Environment;
+use analysis::environment::FieldEnvironment;
+use analysis::environment::MethodEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::annotation::Declaration;
+use analysis::types::constant::Constants;
 use analysis::types::obj::Type;
+use analysis::types::verify::method::lint;
 use analysis::types::verify::method::statement;
+use error::AnalysisCode;
+use error::AnalysisError;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+use error::Warning;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
+use scanner::typed::Statement;
 
 lazy_static! {
     static ref ABSTRACT: ASTNode = {
@@ -65,306 +80,503 @@ lazy_static! {
     };
 }
 
-fn rebuild_env(mut env: &mut Environment) -> Result<(), String> {
+fn hierarchy_error(reason: AnalysisError, node: &ASTNode) -> Diagnostic {
+    Diagnostic::new(Phase::Hierarchy, ErrorMessage::HierarchyError(reason), node.span())
+}
+
+fn type_error(reason: AnalysisError, node: &ASTNode) -> Diagnostic {
+    Diagnostic::new(Phase::Types, ErrorMessage::TypeError(reason), node.span())
+}
+
+fn rebuild_env(mut env: &mut Environment, errors: &mut Vec<Diagnostic>) {
     let old = env.clone();
 
     env.kinds = Vec::new();
     for current in &old.kinds {
         match inheritance::verify(&old, &current, &mut Vec::new()) {
             Ok(inherit) => env.kinds.push(inherit),
-            Err(e) => return Err(e),
+            Err(e) => {
+                let message = ErrorMessage::HierarchyError(e);
+                errors.push(Diagnostic::new(Phase::Hierarchy, message, current.span()));
+            }
         };
     }
-
-    Ok(())
 }
 
-fn verify_env_inheritable(mut env: &mut Environment) -> Result<(), String> {
-    let kinds = env.kinds.clone();
-
-    for mut current in &mut env.kinds {
-        match verify::prefixes::package(&current.name, &current, &kinds) {
-            Ok(_) => (),
-            Err(e) => return Err(e),
+fn verify_kind_inheritable(current: &mut ClassOrInterfaceEnvironment,
+                           kinds: &Vec<ClassOrInterfaceEnvironment>)
+                           -> Result<(), Diagnostic> {
+    match verify::prefixes::package(&current.name, &current, kinds) {
+        Ok(_) => (),
+        Err(e) => {
+            let message = ErrorMessage::TypeError(e);
+            return Err(Diagnostic::new(Phase::Types, message, current.span()));
         }
+    }
 
-        if current.kind == ClassOrInterface::CLASS {
-            for extended in &current.extends {
-                let found = match lookup::class::in_env(&extended, &current, &kinds) {
-                    Ok(f) => f,
-                    Err(e) => return Err(e),
-                };
-                if found.kind == ClassOrInterface::CLASS && found.modifiers.contains(&*FINAL) {
-                    return Err(format!("class {} cannot extend final class {}", current, found));
-                } else if found.kind == ClassOrInterface::INTERFACE {
-                    return Err(format!("class {} cannot extend interface {}", current, found));
-                }
+    if current.kind == ClassOrInterface::CLASS {
+        for extended in &current.extends {
+            let found = match lookup::class::in_env(&extended, &current, kinds) {
+                Ok(f) => f,
+                Err(e) => return Err(hierarchy_error(e, extended)),
+            };
+            if found.kind == ClassOrInterface::CLASS && found.modifiers.contains(&*FINAL) {
+                let message = format!("class {} cannot extend final class {}", current, found);
+                let reason = AnalysisError::new(AnalysisCode::ExtendsFinal, message);
+                return Err(hierarchy_error(reason, extended)
+                               .with_label(found.span(), "declared final here".to_owned()));
+            } else if found.kind == ClassOrInterface::INTERFACE {
+                let message = format!("class {} cannot extend interface {}", current, found);
+                let reason = AnalysisError::new(AnalysisCode::ClassExtendsInterface, message);
+                return Err(hierarchy_error(reason, extended));
+            }
 
-                if current.name != *OBJECT {
-                    let mut zero_argument_parent = false;
-                    for parent_constructor in found.constructors {
-                        if parent_constructor.parameters.is_empty() {
-                            zero_argument_parent = true;
-
-                            let mut fully_qualified = found.name.clone();
-                            fully_qualified.flatten();
-                            fully_qualified.children.push(DOT.clone());
-                            fully_qualified
-                                .children
-                                .push(parent_constructor.name.clone());
-                            for mut constructor in &mut current.constructors {
-                                constructor.parent = Some(fully_qualified.clone());
-                            }
-
-                            break;
+            if current.name != *OBJECT {
+                let mut zero_argument_parent = false;
+                for parent_constructor in &found.constructors {
+                    if parent_constructor.parameters.is_empty() {
+                        zero_argument_parent = true;
+
+                        let mut fully_qualified = found.name.clone();
+                        fully_qualified.flatten();
+                        fully_qualified.children.push(DOT.clone());
+                        fully_qualified
+                            .children
+                            .push(parent_constructor.name.clone());
+                        for mut constructor in &mut current.constructors {
+                            constructor.parent = Some(fully_qualified.clone());
                         }
+
+                        break;
                     }
-                    if !zero_argument_parent {
-                        return Err(format!("class {} has missing zero-argument constructor in {}",
-                                           current.name,
-                                           found.name));
-                    }
+                }
+                if !zero_argument_parent {
+                    let message = format!("class {} has missing zero-argument constructor \
+                                           in {}",
+                                          current.name,
+                                          found.name);
+                    let reason = AnalysisError::other(message);
+                    return Err(hierarchy_error(reason, extended)
+                                   .with_label(found.span(), "superclass declared here"
+                                                                 .to_owned()));
                 }
             }
+        }
 
-            let mut resolved = Vec::new();
-            for implemented in &current.implements {
-                let found = match lookup::class::in_env(&implemented, &current, &kinds) {
-                    Ok(f) => f,
-                    Err(e) => return Err(e),
-                };
-                if resolved.contains(&found.name) {
-                    return Err(format!("interface {} must not be repeated in class implements",
-                                       found.name));
-                }
-                if found.kind == ClassOrInterface::CLASS {
-                    return Err(format!("class {} cannot implement class {}", current, found));
-                }
-                resolved.push(found.name);
+        let mut resolved = Vec::new();
+        for implemented in &current.implements {
+            let found = match lookup::class::in_env(&implemented, &current, kinds) {
+                Ok(f) => f,
+                Err(e) => return Err(hierarchy_error(e, implemented)),
+            };
+            if resolved.contains(&found.name) {
+                let message = format!("interface {} must not be repeated in class implements",
+                                      found.name);
+                let reason = AnalysisError::new(AnalysisCode::RepeatedInterface, message);
+                return Err(hierarchy_error(reason, implemented));
             }
-        } else if current.kind == ClassOrInterface::INTERFACE {
-            let mut resolved = Vec::new();
-            for extended in &current.extends {
-                let found = match lookup::class::in_env(&extended, &current, &kinds) {
-                    Ok(f) => f,
-                    Err(e) => return Err(e),
-                };
-                if resolved.contains(&found.name) {
-                    return Err(format!("type {} must not be repeated in interface extends",
-                                       found.name));
-                }
-                if found.kind == ClassOrInterface::CLASS && found.name != *OBJECT {
-                    return Err(format!("interface {} cannot extend class {}", current, found));
-                }
-                resolved.push(found.name);
+            if found.kind == ClassOrInterface::CLASS {
+                let message = format!("class {} cannot implement class {}", current, found);
+                let reason = AnalysisError::new(AnalysisCode::ClassImplementsClass, message);
+                return Err(hierarchy_error(reason, implemented));
             }
+            resolved.push(found.name);
+        }
+    } else if current.kind == ClassOrInterface::INTERFACE {
+        let mut resolved = Vec::new();
+        for extended in &current.extends {
+            let found = match lookup::class::in_env(&extended, &current, kinds) {
+                Ok(f) => f,
+                Err(e) => return Err(hierarchy_error(e, extended)),
+            };
+            if resolved.contains(&found.name) {
+                let message = format!("type {} must not be repeated in interface extends",
+                                      found.name);
+                let reason = AnalysisError::new(AnalysisCode::RepeatedInterface, message);
+                return Err(hierarchy_error(reason, extended));
+            }
+            if found.kind == ClassOrInterface::CLASS && found.name != *OBJECT {
+                let message = format!("interface {} cannot extend class {}", current, found);
+                let reason = AnalysisError::new(AnalysisCode::InterfaceExtendsClass, message);
+                return Err(hierarchy_error(reason, extended));
+            }
+            resolved.push(found.name);
         }
     }
 
     Ok(())
 }
 
-fn verify_env(mut env: &mut Environment) -> Result<(), String> {
+fn verify_env_inheritable(mut env: &mut Environment, errors: &mut Vec<Diagnostic>) {
     let kinds = env.kinds.clone();
 
     for mut current in &mut env.kinds {
-        let curr = current.clone();
+        match verify_kind_inheritable(&mut current, &kinds) {
+            Ok(_) => (),
+            Err(e) => errors.push(e),
+        }
+    }
+}
 
-        for mut constructor in &mut current.constructors {
-            let mut params = Vec::new();
-            for parameter in &constructor.parameters {
-                if params.contains(&parameter.name) {
-                    return Err(format!("constructor has multiple parameters with same name {}",
-                                       parameter.name));
-                }
-                params.push(parameter.name.clone());
+fn verify_constructor(constructor: &mut MethodEnvironment,
+                      curr: &ClassOrInterfaceEnvironment,
+                      kinds: &Vec<ClassOrInterfaceEnvironment>,
+                      constants: &Constants,
+                      warnings: &mut Vec<Warning>,
+                      annotations: &mut Annotations)
+                      -> Result<(), Diagnostic> {
+    let mut params = Vec::new();
+    for parameter in &constructor.parameters {
+        if params.contains(&parameter.name) {
+            let message = format!("constructor has multiple parameters with same name {}",
+                                  parameter.name);
+            let reason = AnalysisError::new(AnalysisCode::DuplicateParameter, message);
+            return Err(type_error(reason, &parameter.name));
+        }
+        params.push(parameter.name.clone());
 
-                let result = verify::prefixes::canonical(&parameter.kind, &curr, &kinds);
-                if result.is_err() {
-                    return result;
-                }
-            }
+        match verify::prefixes::canonical(&parameter.kind, curr, kinds) {
+            Ok(_) => (),
+            Err(e) => return Err(type_error(e, &parameter.kind)),
+        }
 
-            if &constructor.name != curr.name.children.last().unwrap() {
-                return Err(format!("constructor {} does not share class name {}",
-                                   constructor.name,
-                                   curr.name));
+        let mut kind = parameter.kind.clone();
+        match resolve::expression::go(&mut kind,
+                                      &constructor.modifiers,
+                                      curr,
+                                      kinds,
+                                      &mut Vec::new(),
+                                      annotations) {
+            Ok(t) => {
+                let declaration = Declaration::Local(parameter.clone());
+                annotations.annotate(&parameter.name, &t, Some(declaration));
             }
+            Err(e) => return Err(type_error(e, &parameter.kind)),
+        }
+    }
 
-            let globals = constructor.parameters.clone();
-            let mut body = constructor.clone().body.unwrap().clone();
-            let return_types = match statement::block(&mut body,
-                                                      &constructor.modifiers,
-                                                      &curr,
-                                                      &kinds,
-                                                      &globals) {
-                Ok(rts) => {
-                    constructor.body = Some(body);
-                    rts
-                }
-                Err(e) => return Err(e),
-            };
+    if &constructor.name != curr.name.children.last().unwrap() {
+        let message = format!("constructor {} does not share class name {}",
+                              constructor.name,
+                              curr.name);
+        let reason = AnalysisError::new(AnalysisCode::ConstructorName, message);
+        return Err(type_error(reason, &constructor.name)
+                       .with_label(curr.span(), "class declared here".to_owned()));
+    }
 
-            let constructor_return_type = Type::new(curr.clone());
-            for return_type in &return_types {
-                match constructor_return_type.assign(&return_type, &curr, &kinds) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!("constructor {} has invalid return type\nerror: {:?}",
-                                           curr.name,
-                                           e))
-                    }
-                }
+    let globals = constructor.parameters.clone();
+    let mut body = constructor.clone().body.unwrap().clone();
+    let written = body.clone();
+    lint::unused_parameters(&globals, &written, warnings);
+    let return_types = match statement::block(&mut body,
+                                              &constructor.modifiers,
+                                              curr,
+                                              kinds,
+                                              &globals,
+                                              warnings,
+                                              annotations) {
+        Ok(rts) => {
+            constructor.body = Some(body);
+            rts
+        }
+        Err(e) => return Err(e),
+    };
+    lint::string_comparisons(&written, annotations, warnings);
+
+    // Definite assignment and reachability follow the body as written, before
+    // resolution rewrites it, with its constant expressions folded.
+    let mut folded = written.clone();
+    let locals = constant::locals(&globals, &written);
+    constant::fold(&mut folded, &|n| constants.name(n, curr, kinds, &locals));
+    let typed = match Statement::new(&folded) {
+        Ok(s) => s,
+        Err(e) => return Err(Diagnostic::from(e)),
+    };
+
+    match verify::variable::definitely_assigned(&typed) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    match verify::reachability::reachable(&typed, true) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let constructor_return_type = Type::new(curr.clone());
+    for return_type in &return_types {
+        match constructor_return_type.assign(&return_type, curr, kinds) {
+            Ok(_) => (),
+            Err(e) => {
+                let message = format!("constructor {} has invalid return type", curr.name);
+                let reason = AnalysisError::new(AnalysisCode::ReturnType, message);
+                return Err(type_error(reason, &constructor.name).with_note(e.message));
             }
         }
+    }
 
-        let mut current_builder = current.clone();
-        current_builder.fields = Vec::new();
+    Ok(())
+}
 
-        let mut env_builder = Vec::new();
-        for cls in &kinds {
-            if cls.name != current.name {
-                env_builder.push(cls.clone());
+fn verify_field(field: &mut FieldEnvironment,
+                current_builder: &ClassOrInterfaceEnvironment,
+                env_builder: &Vec<ClassOrInterfaceEnvironment>,
+                annotations: &mut Annotations)
+                -> Result<(), Diagnostic> {
+    match verify::prefixes::canonical(&field.kind, current_builder, env_builder) {
+        Ok(_) => (),
+        Err(e) => return Err(type_error(e, &field.kind)),
+    }
+
+    let lvalue = match lookup::class::in_env(&field.kind, current_builder, env_builder) {
+        Ok(c) => Type::new(c),
+        Err(e) => return Err(type_error(e, &field.kind)),
+    };
+    let declaration = Declaration::Field(current_builder.name.clone(), field.clone());
+    annotations.annotate(&field.name, &lvalue, Some(declaration));
+
+    if field.value.is_none() {
+        return Ok(());
+    }
+
+    // TODO: allow qualified names to be resolved to future fields
+    let mut rexpr = field.clone().value.unwrap();
+    let rvalue = match resolve::expression::go(&mut rexpr,
+                                               &field.modifiers,
+                                               current_builder,
+                                               env_builder,
+                                               &mut Vec::new(),
+                                               annotations) {
+        Ok(t) => {
+            field.value = Some(rexpr);
+            t
+        }
+        Err(e) => return Err(type_error(e, &rexpr)),
+    };
+
+    match lvalue.assign(&rvalue, current_builder, env_builder) {
+        Ok(_) => (),
+        Err(e) => return Err(type_error(e, &field.name)),
+    }
+
+
+    Ok(())
+}
+
+fn verify_method(method: &mut MethodEnvironment,
+                 curr: &ClassOrInterfaceEnvironment,
+                 kinds: &Vec<ClassOrInterfaceEnvironment>,
+                 constants: &Constants,
+                 warnings: &mut Vec<Warning>,
+                 annotations: &mut Annotations)
+                 -> Result<(), Diagnostic> {
+    if method.body.is_none() {
+        if !method.modifiers.contains(&*ABSTRACT) && !method.modifiers.contains(&*NATIVE) {
+            let message = format!("concrete method {} has no body", method);
+            let reason = AnalysisError::other(message);
+            return Err(type_error(reason, &method.name));
+        }
+    }
+
+    let globals = method.parameters.clone();
+
+    let mut params = Vec::new();
+    for mut parameter in &mut method.parameters {
+        if params.contains(&parameter.name) {
+            let message = format!("method has multiple parameters with same name {}",
+                                  parameter.name);
+            let reason = AnalysisError::new(AnalysisCode::DuplicateParameter, message);
+            return Err(type_error(reason, &parameter.name));
+        }
+        params.push(parameter.name.clone());
+
+        match resolve::expression::go(&mut parameter.kind,
+                                      &method.modifiers,
+                                      curr,
+                                      kinds,
+                                      &mut Vec::new(),
+                                      annotations) {
+            Ok(t) => {
+                parameter.kind = t.kind.name.clone();
+                let declaration = Declaration::Local(parameter.clone());
+                annotations.annotate(&parameter.name, &t, Some(declaration));
             }
+            Err(e) => return Err(type_error(e, &parameter.kind)),
         }
+    }
 
-        for mut field in &mut current.fields {
-            env_builder.push(current_builder.clone());
+    match verify::prefixes::canonical(&method.return_type, curr, kinds) {
+        Ok(_) => (),
+        Err(e) => return Err(type_error(e, &method.return_type)),
+    }
 
-            let result = verify::prefixes::canonical(&field.kind, &current_builder, &env_builder);
-            if result.is_err() {
-                return result;
+    if method.modifiers.contains(&*ABSTRACT) {
+        if method.modifiers.contains(&*FINAL) {
+            if method.name != *GETCLASS {
+                let message = format!("final method {} is abstract", method);
+                let reason = AnalysisError::other(message);
+                return Err(type_error(reason, &method.name));
             }
+        }
+
+        if method.modifiers.contains(&*STATIC) {
+            let message = format!("static method {} is abstract", method);
+            let reason = AnalysisError::other(message);
+            return Err(type_error(reason, &method.name));
+        }
+    }
 
-            if field.value.is_none() {
-                continue;
+    if method.body.is_some() {
+        let mut body = method.clone().body.unwrap().clone();
+        let written = body.clone();
+        lint::unused_parameters(&globals, &written, warnings);
+        let return_types = match statement::block(&mut body,
+                                                  &method.modifiers,
+                                                  curr,
+                                                  kinds,
+                                                  &globals,
+                                                  warnings,
+                                                  annotations) {
+            Ok(rts) => {
+                method.body = Some(body);
+                rts
             }
+            Err(e) => return Err(e),
+        };
+        lint::string_comparisons(&written, annotations, warnings);
+
+        let mut folded = written.clone();
+        let locals = constant::locals(&globals, &written);
+        constant::fold(&mut folded, &|n| constants.name(n, curr, kinds, &locals));
+        let typed = match Statement::new(&folded) {
+            Ok(s) => s,
+            Err(e) => return Err(Diagnostic::from(e)),
+        };
 
-            // TODO: allow qualified names to be resolved to future fields
-            let mut rexpr = field.clone().value.unwrap();
-            let rvalue = match resolve::expression::go(&mut rexpr,
-                                                       &field.modifiers,
-                                                       &current_builder,
-                                                       &env_builder,
-                                                       &mut Vec::new()) {
-                Ok(t) => {
-                    field.value = Some(rexpr);
-                    t
-                }
-                Err(e) => return Err(e),
-            };
+        match verify::variable::definitely_assigned(&typed) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
 
-            let lvalue = match lookup::class::in_env(&field.kind, &current_builder, &env_builder) {
-                Ok(c) => Type::new(c),
-                Err(e) => return Err(e),
+        let method_return_type =
+            match lookup::class::in_env(&method.return_type, curr, kinds) {
+                Ok(rt) => Type::new(rt),
+                Err(e) => return Err(type_error(e, &method.return_type)),
             };
 
-            match lvalue.assign(&rvalue, &current_builder, &env_builder) {
+        for return_type in &return_types {
+            match method_return_type.assign(&return_type, curr, kinds) {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => {
+                    let message = format!("{} method {} has invalid return type",
+                                          curr.name,
+                                          method.name);
+                    let reason = AnalysisError::new(AnalysisCode::ReturnType, message);
+                    return Err(type_error(reason, &method.name).with_note(e.message));
+                }
             }
+        }
 
-            current_builder.fields.push(field.clone());
-
-            env_builder.pop();
+        match verify::reachability::reachable(&typed, method_return_type == *VOID) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
         }
+    }
 
-        for mut method in &mut current.methods {
-            if method.body.is_none() {
-                if !method.modifiers.contains(&*ABSTRACT) && !method.modifiers.contains(&*NATIVE) {
-                    return Err(format!("concrete method {} has no body", method));
-                }
-            }
+    Ok(())
+}
 
-            let globals = method.parameters.clone();
+// Each constructor, field and method is verified on its own, so that an error
+// in one of them is reported without hiding errors in the others.
+fn verify_env(mut env: &mut Environment,
+              errors: &mut Vec<Diagnostic>,
+              warnings: &mut Vec<Warning>) {
+    let kinds = env.kinds.clone();
+    let constants = Constants::new(&kinds);
 
-            let mut params = Vec::new();
-            for mut parameter in &mut method.parameters {
-                if params.contains(&parameter.name) {
-                    return Err(format!("method has multiple parameters with same name {}",
-                                       parameter.name));
-                }
-                params.push(parameter.name.clone());
-
-                match resolve::expression::go(&mut parameter.kind,
-                                              &method.modifiers,
-                                              &curr,
-                                              &kinds,
-                                              &mut Vec::new()) {
-                    Ok(t) => parameter.kind = t.kind.name,
-                    Err(e) => return Err(e),
-                }
-            }
+    for mut current in &mut env.kinds {
+        let curr = current.clone();
 
-            let result = verify::prefixes::canonical(&method.return_type, &curr, &kinds);
-            if result.is_err() {
-                return result;
+        for mut constructor in &mut current.constructors {
+            match verify_constructor(&mut constructor,
+                                     &curr,
+                                     &kinds,
+                                     &constants,
+                                     warnings,
+                                     &mut env.annotations) {
+                Ok(_) => (),
+                Err(e) => errors.push(e),
             }
+        }
 
-            if method.modifiers.contains(&*ABSTRACT) {
-                if method.modifiers.contains(&*FINAL) {
-                    if method.name != *GETCLASS {
-                        return Err(format!("final method {} is abstract", method));
-                    }
-                }
+        let mut current_builder = current.clone();
+        current_builder.fields = Vec::new();
 
-                if method.modifiers.contains(&*STATIC) {
-                    return Err(format!("static method {} is abstract", method));
-                }
+        let mut env_builder = Vec::new();
+        for cls in &kinds {
+            if cls.name != current.name {
+                env_builder.push(cls.clone());
             }
+        }
 
-            if method.body.is_some() {
-                let mut body = method.clone().body.unwrap().clone();
-                let return_types =
-                    match statement::block(&mut body, &method.modifiers, &curr, &kinds, &globals) {
-                        Ok(rts) => {
-                            method.body = Some(body);
-                            rts
-                        }
-                        Err(e) => return Err(e),
-                    };
-
-                let method_return_type =
-                    match lookup::class::in_env(&method.return_type, &curr, &kinds) {
-                        Ok(rt) => Type::new(rt),
-                        Err(e) => return Err(e),
-                    };
-
-                for return_type in &return_types {
-                    match method_return_type.assign(&return_type, &curr, &kinds) {
-                        Ok(_) => (),
-                        Err(e) => {
-                            return Err(format!("{} method {} has invalid return type\nerror: {:?}",
-                                               curr.name,
-                                               method.name,
-                                               e))
-                        }
-                    }
-                }
+        for mut field in &mut current.fields {
+            env_builder.push(current_builder.clone());
 
-                if return_types.is_empty() && method_return_type != *VOID {
-                    return Err(format!("non-void {} method {} has no return type",
-                                       curr.name,
-                                       method.name));
+            match verify_field(&mut field,
+                               &current_builder,
+                               &env_builder,
+                               &mut env.annotations) {
+                Ok(_) if field.value.is_none() => (),
+                Ok(_) => {
+                    current_builder.fields.push(field.clone());
+                    env_builder.pop();
+                }
+                Err(e) => {
+                    errors.push(e);
+                    env_builder.pop();
                 }
             }
+        }
 
+        for mut method in &mut current.methods {
+            match verify_method(&mut method,
+                                &curr,
+                                &kinds,
+                                &constants,
+                                warnings,
+                                &mut env.annotations) {
+                Ok(_) => (),
+                Err(e) => errors.push(e),
+            }
         }
     }
-
-    Ok(())
 }
 
-pub fn verify(mut env: &mut Environment) -> Result<(), String> {
-    match verify_env_inheritable(&mut env) {
-        Ok(_) => (),
-        Err(e) => return Err(e),
+/// Type checks an Environment, reporting every error found. Later passes
+/// build on the earlier ones, so verification stops after the first pass
+/// which fails. Suspicious method bodies are added to `warnings`. Once
+/// verified, the constant expressions in the Environment are folded.
+pub fn verify(mut env: &mut Environment,
+              warnings: &mut Vec<Warning>)
+              -> Result<(), Vec<Diagnostic>> {
+    let mut errors = Vec::new();
+
+    verify_env_inheritable(&mut env, &mut errors);
+    if !errors.is_empty() {
+        return Err(errors);
     }
 
-    match rebuild_env(&mut env) {
-        Ok(_) => (),
-        Err(e) => return Err(e),
-    };
+    rebuild_env(&mut env, &mut errors);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    verify_env(&mut env, &mut errors, warnings);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
 
-    verify_env(&mut env)
+    constant::fold_env(&mut env);
+
+    Ok(())
 }
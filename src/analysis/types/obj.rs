@@ -1,6 +1,8 @@
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::types::lookup;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -113,7 +115,7 @@ impl Type {
                       other: &Type,
                       current: &ClassOrInterfaceEnvironment,
                       kinds: &Vec<ClassOrInterfaceEnvironment>)
-                      -> Result<Type, String> {
+                      -> Result<Type, AnalysisError> {
         let lhs = match lookup::class::in_env(&self.kind.name, current, kinds) {
             Ok(cls) => Type::new(cls),
             Err(e) => return Err(e),
@@ -140,11 +142,12 @@ impl Type {
             return Ok(result);
         }
 
-        Err(format!("could not cast {:?} to {:?}\ngot errors:\n\t{:?}\n\t{:?}",
-                    rhs.kind.name,
-                    lhs.kind.name,
-                    lhs_result.unwrap_err(),
-                    rhs_result.unwrap_err()))
+        let message = format!("could not cast {:?} to {:?}\ngot errors:\n\t{:?}\n\t{:?}",
+                              rhs.kind.name,
+                              lhs.kind.name,
+                              lhs_result.unwrap_err().message,
+                              rhs_result.unwrap_err().message);
+        Err(AnalysisError::new(AnalysisCode::InvalidCast, message))
     }
 
     pub fn apply_comparison(&self,
@@ -152,7 +155,7 @@ impl Type {
                             other: &Type,
                             current: &ClassOrInterfaceEnvironment,
                             kinds: &Vec<ClassOrInterfaceEnvironment>)
-                            -> Result<Type, String> {
+                            -> Result<Type, AnalysisError> {
         if *self == *INTEGER && *other == *INTEGER {
             let mut result = BOOLEAN.clone();
 
@@ -199,20 +202,22 @@ impl Type {
             return Ok(BOOLEAN.clone());
         }
 
-        Err(format!("could not apply comparison {:?} to {:?} and {:?}\ngot errors:\n\t{:?}\n\t{:?}",
-                    operation,
-                    self.kind.name,
-                    other.kind.name,
-                    lhs_result.unwrap_err(),
-                    rhs_result.unwrap_err()))
+        let message = format!("could not apply comparison {:?} to {:?} and {:?}\ngot \
+                               errors:\n\t{:?}\n\t{:?}",
+                              operation,
+                              self.kind.name,
+                              other.kind.name,
+                              lhs_result.unwrap_err().message,
+                              rhs_result.unwrap_err().message);
+        Err(AnalysisError::other(message))
     }
 
-    pub fn apply_math(&self, operation: &TokenKind, other: &Type) -> Result<Type, String> {
+    pub fn apply_math(&self, operation: &TokenKind, other: &Type) -> Result<Type, AnalysisError> {
         if *self == *VOID || *other == *VOID {
-            return Err(format!("could not apply {:?} to voids {:?} and {:?}",
-                               operation,
-                               self.kind.name,
-                               other.kind.name));
+            return Err(AnalysisError::other(format!("could not apply {:?} to voids {:?} and {:?}",
+                                                    operation,
+                                                    self.kind.name,
+                                                    other.kind.name)));
         }
 
         if *self == *CHAR && *other == *CHAR {
@@ -251,13 +256,14 @@ impl Type {
                 _ => return Ok(result),
             };
 
+            // Integer arithmetic wraps around on overflow.
             result.kind.name.token.lexeme = match *operation {
-                TokenKind::FSlash if vrhs == 0 => None,
-                TokenKind::FSlash => Some((vlhs / vrhs).to_string()),
-                TokenKind::Minus => Some((vlhs - vrhs).to_string()),
-                TokenKind::Percent => Some((vlhs % vrhs).to_string()),
-                TokenKind::Plus => Some((vlhs + vrhs).to_string()),
-                TokenKind::Star => Some((vlhs * vrhs).to_string()),
+                TokenKind::FSlash | TokenKind::Percent if vrhs == 0 => None,
+                TokenKind::FSlash => Some(vlhs.wrapping_div(vrhs).to_string()),
+                TokenKind::Minus => Some(vlhs.wrapping_sub(vrhs).to_string()),
+                TokenKind::Percent => Some(vlhs.wrapping_rem(vrhs).to_string()),
+                TokenKind::Plus => Some(vlhs.wrapping_add(vrhs).to_string()),
+                TokenKind::Star => Some(vlhs.wrapping_mul(vrhs).to_string()),
                 _ => None,
             };
 
@@ -281,17 +287,18 @@ impl Type {
             _ => (),
         }
 
-        Err(format!("could not apply math {:?} to {:?} and {:?}",
-                    operation,
-                    self.kind.name,
-                    other.kind.name))
+        let message = format!("could not apply math {:?} to {:?} and {:?}",
+                              operation,
+                              self.kind.name,
+                              other.kind.name);
+        Err(AnalysisError::other(message))
     }
 
     pub fn assign(&self,
                   rhs: &Type,
                   current: &ClassOrInterfaceEnvironment,
                   kinds: &Vec<ClassOrInterfaceEnvironment>)
-                  -> Result<Type, String> {
+                  -> Result<Type, AnalysisError> {
         let mut lhs = match lookup::class::in_env(&self.kind.name, current, kinds) {
             Ok(cls) => Type::new(cls),
             Err(e) => return Err(e),
@@ -305,7 +312,8 @@ impl Type {
 
         // can not assign anything to voids (except nulls as returns...)
         if lhs == *VOID && rhs != *NULL {
-            return Err(format!("cannot assign {} to void", rhs.kind.name));
+            let message = format!("cannot assign {} to void", rhs.kind.name);
+            return Err(AnalysisError::new(AnalysisCode::Assignment, message));
         }
 
         // can't assign classes to arrays, but can assign arrays to Object
@@ -332,9 +340,10 @@ impl Type {
                     return Ok(result);
                 }
 
-                return Err(format!("cannot assign class {} to array {}",
-                                   rhs.kind.name,
-                                   lhs.kind.name));
+                let message = format!("cannot assign class {} to array {}",
+                                      rhs.kind.name,
+                                      lhs.kind.name);
+                return Err(AnalysisError::new(AnalysisCode::Assignment, message));
             }
 
             lhs = match lookup::class::in_env(&lhs.kind.name.children[0], current, kinds) {
@@ -352,9 +361,10 @@ impl Type {
                 None => (),
             }
 
-            return Err(format!("cannot assign non-subtype array {} to {}",
-                               rhs.kind.name,
-                               lhs.kind.name));
+            let message = format!("cannot assign non-subtype array {} to {}",
+                                  rhs.kind.name,
+                                  lhs.kind.name);
+            return Err(AnalysisError::new(AnalysisCode::Assignment, message));
         }
 
         if lhs == rhs {
@@ -388,7 +398,8 @@ impl Type {
             None => (),
         }
 
-        Err(format!("can not assign {} to {}", rhs.kind.name, lhs.kind.name))
+        let message = format!("can not assign {} to {}", rhs.kind.name, lhs.kind.name);
+        Err(AnalysisError::new(AnalysisCode::Assignment, message))
     }
 
     // TODO: dedup with assign
@@ -396,7 +407,7 @@ impl Type {
                          rhs: &Type,
                          current: &ClassOrInterfaceEnvironment,
                          kinds: &Vec<ClassOrInterfaceEnvironment>)
-                         -> Result<u32, String> {
+                         -> Result<u32, AnalysisError> {
         let mut lhs = match lookup::class::in_env(&self.kind.name, current, kinds) {
             Ok(cls) => Type::new(cls),
             Err(e) => return Err(e),
@@ -493,7 +504,7 @@ impl Type {
     fn inherit_distance(&self,
                         child: &Type,
                         kinds: &Vec<ClassOrInterfaceEnvironment>)
-                        -> Result<u32, String> {
+                        -> Result<u32, AnalysisError> {
         let mut distance = 0;
 
         let mut parents = vec![child.kind.clone()];
@@ -527,10 +538,14 @@ impl Type {
         primitives.contains(&self)
     }
 
+    pub fn is_string(&self) -> bool {
+        self == &*STRING_CANONICAL || self == &*STRING_SHORT
+    }
+
     fn is_parent(&self,
                  child: &Type,
                  kinds: &Vec<ClassOrInterfaceEnvironment>)
-                 -> Option<Result<(), String>> {
+                 -> Option<Result<(), AnalysisError>> {
         let mut parents = vec![child.kind.clone()];
         while let Some(parent) = parents.pop() {
             if parent.name == self.kind.name {
@@ -1,21 +1,27 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
-use analysis::types::verify;
+use error::AnalysisError;
 use scanner::ASTNode;
 
 pub fn go(mut node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
-    let mut array =
-        match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
-            Ok(a) => a,
-            Err(e) => return Err(e),
-        };
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
+    let mut array = match resolve::expression::go(&mut node.children[0],
+                                                  modifiers,
+                                                  current,
+                                                  kinds,
+                                                  globals,
+                                                  annotations) {
+        Ok(a) => a,
+        Err(e) => return Err(e),
+    };
 
     if array
            .kind
@@ -24,17 +30,20 @@ pub fn go(mut node: &mut ASTNode,
            .token
            .lexeme
            .unwrap_or("".to_owned()) != "ArrayType" {
-        return Err(format!("got invalid array type {:?}", array));
-    }
-
-    match verify::variable::initialized(&node.children[0], current, globals) {
-        Ok(_) => (),
-        Err(e) => return Err(e),
+        return Err(AnalysisError::other(format!("got invalid array type {:?}", array)));
     }
 
-    match resolve::expression::go(&mut node.children[2], modifiers, current, kinds, globals) {
+    match resolve::expression::go(&mut node.children[2],
+                                  modifiers,
+                                  current,
+                                  kinds,
+                                  globals,
+                                  annotations) {
         Ok(ref idx) if idx.is_coercible_to_int() => (),
-        Ok(idx) => return Err(format!("got invalid index type {:?}", idx.kind.name)),
+        Ok(idx) => {
+            let message = format!("got invalid index type {:?}", idx.kind.name);
+            return Err(AnalysisError::other(message));
+        }
         Err(e) => return Err(e),
     }
 
@@ -42,5 +51,6 @@ pub fn go(mut node: &mut ASTNode,
                             modifiers,
                             current,
                             kinds,
-                            globals)
+                            globals,
+                            annotations)
 }
@@ -1,8 +1,10 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
 use analysis::types::lookup::array;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -15,21 +17,31 @@ pub fn go(mut node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
     if node.children[1].clone().token.lexeme.unwrap() == "DimExpr" {
         match resolve::expression::go(&mut node.children[1].children[1],
                                       modifiers,
                                       current,
                                       kinds,
-                                      globals) {
+                                      globals,
+                                      annotations) {
             Ok(ref idx) if idx.is_coercible_to_int() => (),
-            Ok(idx) => return Err(format!("got invalid index type {:?}", idx.kind.name)),
+            Ok(idx) => {
+                let message = format!("got invalid index type {:?}", idx.kind.name);
+                return Err(AnalysisError::other(message));
+            }
             Err(e) => return Err(e),
         }
     }
 
-    match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
+    match resolve::expression::go(&mut node.children[0],
+                                  modifiers,
+                                  current,
+                                  kinds,
+                                  globals,
+                                  annotations) {
         Ok(x) => {
             let kind = ASTNode {
                 token: ARRAYTYPE.clone(),
@@ -1,8 +1,10 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
 use analysis::types::lookup::array;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -15,31 +17,37 @@ pub fn go(mut node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
     let idx = node.children.len() - 1;
     let rhs = match resolve::expression::go(&mut node.children[idx],
                                             modifiers,
                                             current,
                                             kinds,
-                                            globals) {
+                                            globals,
+                                            annotations) {
         Ok(t) => t,
         Err(e) => return Err(e),
     };
 
-    let lhs =
-        match resolve::expression::go(&mut node.children[1], modifiers, current, kinds, globals) {
-            // CastExpression has 5 children iff it contains a DimExpr
-            Ok(ref t) if node.children.len() == 5 => {
-                let kind = ASTNode {
-                    token: ARRAYTYPE.clone(),
-                    children: vec![t.kind.name.clone()],
-                };
-                Type::new(array::create(&kind))
-            }
-            Ok(t) => t,
-            Err(e) => return Err(e),
-        };
+    let lhs = match resolve::expression::go(&mut node.children[1],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        // CastExpression has 5 children iff it contains a DimExpr
+        Ok(ref t) if node.children.len() == 5 => {
+            let kind = ASTNode {
+                token: ARRAYTYPE.clone(),
+                children: vec![t.kind.name.clone()],
+            };
+            Type::new(array::create(&kind))
+        }
+        Ok(t) => t,
+        Err(e) => return Err(e),
+    };
 
     lhs.apply_cast(&rhs, current, kinds)
 }
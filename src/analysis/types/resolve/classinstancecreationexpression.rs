@@ -1,8 +1,12 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::annotation::Declaration;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -17,8 +21,9 @@ pub fn go(mut node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
     let idx = match node.children.len() {
         2 => 1,
         _ => 0,
@@ -29,7 +34,8 @@ pub fn go(mut node: &mut ASTNode,
                                                          modifiers,
                                                          current,
                                                          kinds,
-                                                         globals) {
+                                                         globals,
+                                                         annotations) {
         Ok(a) => a,
         Err(e) => return Err(e),
     };
@@ -42,8 +48,11 @@ pub fn go(mut node: &mut ASTNode,
                                                              kinds) {
                 Ok(m) => m,
                 Err(e) => {
-                    return Err(format!("could not find matching constructor\ngot errors:\n\t{:?}",
-                                       e))
+                    let message = format!("could not find matching constructor\n{}", e);
+                    return Err(AnalysisError {
+                        notes: e.notes,
+                        ..AnalysisError::new(AnalysisCode::NoMatchingMethod, message)
+                    });
                 }
             };
 
@@ -53,7 +62,9 @@ pub fn go(mut node: &mut ASTNode,
             fully_qualified.children.push(method.name.clone());
             node.children[0] = fully_qualified;
 
-            Ok(Type::new(cls))
+            let kind = Type::new(cls.clone());
+            annotations.annotate(node, &kind, Some(Declaration::Method(cls.name, method)));
+            Ok(kind)
         }
         Err(_) => {
             // TODO: duplicate resolve?
@@ -63,7 +74,7 @@ pub fn go(mut node: &mut ASTNode,
             node_copy.children[0].children.pop();
             node_copy.children[0].children.pop();
 
-            go(&mut node_copy, modifiers, current, kinds, globals)
+            go(&mut node_copy, modifiers, current, kinds, globals, annotations)
         }
     }
 }
@@ -1,13 +1,18 @@
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::lookup::array;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
 
 lazy_static! {
+    static ref ARRAYTYPE: Token = Token::new(TokenKind::NonTerminal, Some("ArrayType"));
     static ref BOOLEAN: Type = {
         let node = ASTNode { token: Token::new(TokenKind::Boolean, None), children: Vec::new() };
         Type::new(ClassOrInterfaceEnvironment::new(node, ClassOrInterface::CLASS))
@@ -43,13 +48,18 @@ pub fn onearg_boolean(mut node: &mut ASTNode,
                       modifiers: &Vec<ASTNode>,
                       current: &ClassOrInterfaceEnvironment,
                       kinds: &Vec<ClassOrInterfaceEnvironment>,
-                      globals: &mut Vec<VariableEnvironment>)
-                      -> Result<Type, String> {
-    let arg =
-        match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
-            Ok(l) => l,
-            Err(e) => return Err(e),
-        };
+                      globals: &mut Vec<VariableEnvironment>,
+                      annotations: &mut Annotations)
+                      -> Result<Type, AnalysisError> {
+    let arg = match resolve::expression::go(&mut node.children[0],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(l) => l,
+        Err(e) => return Err(e),
+    };
 
     if arg == *BOOLEAN {
         let mut result = BOOLEAN.clone();
@@ -66,9 +76,9 @@ pub fn onearg_boolean(mut node: &mut ASTNode,
 
         Ok(result)
     } else {
-        Err(format!("could not apply {:?} to {:?}",
-                    node.token.kind,
-                    arg.kind.name))
+        Err(AnalysisError::other(format!("could not apply {:?} to {:?}",
+                                         node.token.kind,
+                                         arg.kind.name)))
     }
 }
 
@@ -76,18 +86,27 @@ pub fn twoarg(mut node: &mut ASTNode,
               modifiers: &Vec<ASTNode>,
               current: &ClassOrInterfaceEnvironment,
               kinds: &Vec<ClassOrInterfaceEnvironment>,
-              globals: &mut Vec<VariableEnvironment>)
-              -> Result<Type, String> {
-    let lhs =
-        match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
-            Ok(l) => l,
-            Err(e) => return Err(e),
-        };
-    let rhs =
-        match resolve::expression::go(&mut node.children[1], modifiers, current, kinds, globals) {
-            Ok(r) => r,
-            Err(e) => return Err(e),
-        };
+              globals: &mut Vec<VariableEnvironment>,
+              annotations: &mut Annotations)
+              -> Result<Type, AnalysisError> {
+    let lhs = match resolve::expression::go(&mut node.children[0],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(l) => l,
+        Err(e) => return Err(e),
+    };
+    let rhs = match resolve::expression::go(&mut node.children[1],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(r) => r,
+        Err(e) => return Err(e),
+    };
 
     lhs.apply_comparison(&node.token.kind, &rhs, current, kinds)
 }
@@ -96,18 +115,27 @@ pub fn twoarg_boolean(mut node: &mut ASTNode,
                       modifiers: &Vec<ASTNode>,
                       current: &ClassOrInterfaceEnvironment,
                       kinds: &Vec<ClassOrInterfaceEnvironment>,
-                      globals: &mut Vec<VariableEnvironment>)
-                      -> Result<Type, String> {
-    let lhs =
-        match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
-            Ok(l) => l,
-            Err(e) => return Err(e),
-        };
-    let rhs =
-        match resolve::expression::go(&mut node.children[1], modifiers, current, kinds, globals) {
-            Ok(r) => r,
-            Err(e) => return Err(e),
-        };
+                      globals: &mut Vec<VariableEnvironment>,
+                      annotations: &mut Annotations)
+                      -> Result<Type, AnalysisError> {
+    let lhs = match resolve::expression::go(&mut node.children[0],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(l) => l,
+        Err(e) => return Err(e),
+    };
+    let rhs = match resolve::expression::go(&mut node.children[1],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(r) => r,
+        Err(e) => return Err(e),
+    };
 
     if lhs == *BOOLEAN && rhs == *BOOLEAN {
         let mut result = BOOLEAN.clone();
@@ -149,12 +177,13 @@ pub fn twoarg_boolean(mut node: &mut ASTNode,
 
         Ok(result)
     } else if BITWISE.contains(&node.token.kind) {
-        Err(format!("bitwise operations are not allowed"))
+        let message = format!("bitwise operations are not allowed");
+        Err(AnalysisError::new(AnalysisCode::Bitwise, message))
     } else {
-        Err(format!("could not apply {:?} to {:?} and {:?}",
-                    node.token.kind,
-                    lhs.kind.name,
-                    rhs.kind.name))
+        Err(AnalysisError::other(format!("could not apply {:?} to {:?} and {:?}",
+                                         node.token.kind,
+                                         lhs.kind.name,
+                                         rhs.kind.name)))
     }
 }
 
@@ -162,34 +191,52 @@ pub fn twoarg_instanceof(mut node: &mut ASTNode,
                          modifiers: &Vec<ASTNode>,
                          current: &ClassOrInterfaceEnvironment,
                          kinds: &Vec<ClassOrInterfaceEnvironment>,
-                         globals: &mut Vec<VariableEnvironment>)
-                         -> Result<Type, String> {
-    let lhs =
-        match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
-            Ok(t) => t,
-            Err(e) => return Err(e),
-        };
-    let rhs =
-        match resolve::expression::go(&mut node.children[1], modifiers, current, kinds, globals) {
-            Ok(t) => t,
-            Err(e) => return Err(e),
-        };
+                         globals: &mut Vec<VariableEnvironment>,
+                         annotations: &mut Annotations)
+                         -> Result<Type, AnalysisError> {
+    let lhs = match resolve::expression::go(&mut node.children[0],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(t) => t,
+        Err(e) => return Err(e),
+    };
+    let rhs = match resolve::expression::go(&mut node.children[1],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(t) => t,
+        Err(e) => return Err(e),
+    };
 
     if PRIMITIVES.contains(&lhs) || PRIMITIVES.contains(&rhs) {
-        return Err(format!("can not apply instanceof to primitive types"));
+        let message = format!("can not apply instanceof to primitive types");
+        return Err(AnalysisError::new(AnalysisCode::PrimitiveInstanceof, message));
     }
 
-    let mut result = BOOLEAN.clone();
-    match lhs.assign(&rhs, current, kinds) {
-        Ok(_) => {
-            node.token = TRUE.clone();
-            result.kind.name.token.lexeme = Some("true".to_owned());
-        }
-        Err(_) => {
-            node.token = FALSE.clone();
-            result.kind.name.token.lexeme = Some("false".to_owned());
-        }
+    // record the canonical name of an array type, for code generation
+    if node.children[1].token.lexeme == Some("ArrayType".to_owned()) {
+        let element = match resolve::expression::go(&mut node.children[1].children[0],
+                                                    modifiers,
+                                                    current,
+                                                    kinds,
+                                                    globals,
+                                                    annotations) {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+        let kind = ASTNode {
+            token: ARRAYTYPE.clone(),
+            children: vec![element.kind.name.clone()],
+        };
+        annotations.annotate(&node.children[1], &Type::new(array::create(&kind)), None);
     }
 
-    Ok(result)
+    // the result depends on the runtime type of the operand, so is never a
+    // constant, even when the operand's static type is a subtype
+    Ok(BOOLEAN.clone())
 }
@@ -1,8 +1,10 @@
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -17,48 +19,81 @@ pub fn go(mut node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
-    match node.clone().token.lexeme {
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
+    let kind = match node.clone().token.lexeme {
+        // Arguments and fully qualified methods are annotated by what they
+        // wrap: their own spans include the declaration of its type.
         Some(ref l) if l == "Argument" => {
-            go(&mut node.children[1], modifiers, current, kinds, globals)
+            return go(&mut node.children[1], modifiers, current, kinds, globals, annotations);
         }
         Some(ref l) if l == "ArrayAccess" => {
-            resolve::arrayaccess::go(node, modifiers, current, kinds, globals)
+            resolve::arrayaccess::go(node, modifiers, current, kinds, globals, annotations)
         }
         Some(ref l) if l == "ArrayCreationExpression" => {
-            resolve::arraycreationexpression::go(node, modifiers, current, kinds, globals)
+            resolve::arraycreationexpression::go(node,
+                                                 modifiers,
+                                                 current,
+                                                 kinds,
+                                                 globals,
+                                                 annotations)
         }
         Some(ref l) if l == "ArrayType" => {
             Ok(Type::new(ClassOrInterfaceEnvironment::new(node.clone(), ClassOrInterface::CLASS)))
         }
         Some(ref l) if l == "Assignment" => {
-            resolve::assignment::go(node, modifiers, current, kinds, globals)
+            resolve::assignment::go(node, modifiers, current, kinds, globals, annotations)
         }
         Some(ref l) if l == "CastExpression" => {
-            resolve::castexpression::go(node, modifiers, current, kinds, globals)
+            resolve::castexpression::go(node, modifiers, current, kinds, globals, annotations)
         }
         Some(ref l) if l == "ClassInstanceCreationExpression" => {
-            resolve::classinstancecreationexpression::go(node, modifiers, current, kinds, globals)
+            resolve::classinstancecreationexpression::go(node,
+                                                         modifiers,
+                                                         current,
+                                                         kinds,
+                                                         globals,
+                                                         annotations)
         }
         Some(ref l) if l == "FieldAccess" => {
-            resolve::fieldaccess::go(node, modifiers, current, kinds, globals)
+            resolve::fieldaccess::go(node, modifiers, current, kinds, globals, annotations)
         }
         Some(ref l) if l == "FullyQualifiedMethod" => {
-            go(&mut node.children[1], modifiers, current, kinds, globals)
+            return go(&mut node.children[1], modifiers, current, kinds, globals, annotations);
         }
         Some(ref l) if l == "MethodInvocation" => {
-            resolve::methodinvocation::go(&mut node, modifiers, current, kinds, globals)
+            resolve::methodinvocation::go(&mut node,
+                                          modifiers,
+                                          current,
+                                          kinds,
+                                          globals,
+                                          annotations)
         }
-        Some(ref l) if l == "Name" => resolve::name::go(node, modifiers, current, kinds, globals),
+        Some(ref l) if l == "Name" => resolve::name::go(node,
+                                                        modifiers,
+                                                        current,
+                                                        kinds,
+                                                        globals,
+                                                        annotations),
         _ => {
             match node.token.kind {
                 TokenKind::And | TokenKind::BitAnd | TokenKind::Or | TokenKind::BitOr |
                 TokenKind::BitXor => {
-                    resolve::comparison::twoarg_boolean(node, modifiers, current, kinds, globals)
+                    resolve::comparison::twoarg_boolean(node,
+                                                        modifiers,
+                                                        current,
+                                                        kinds,
+                                                        globals,
+                                                        annotations)
                 }
                 TokenKind::Not => {
-                    resolve::comparison::onearg_boolean(node, modifiers, current, kinds, globals)
+                    resolve::comparison::onearg_boolean(node,
+                                                        modifiers,
+                                                        current,
+                                                        kinds,
+                                                        globals,
+                                                        annotations)
                 }
                 TokenKind::Equality |
                 TokenKind::NotEqual |
@@ -66,13 +101,28 @@ pub fn go(mut node: &mut ASTNode,
                 TokenKind::LessThanOrEqual |
                 TokenKind::GreaterThan |
                 TokenKind::GreaterThanOrEqual => {
-                    resolve::comparison::twoarg(node, modifiers, current, kinds, globals)
+                    resolve::comparison::twoarg(node,
+                                                modifiers,
+                                                current,
+                                                kinds,
+                                                globals,
+                                                annotations)
                 }
                 TokenKind::Instanceof => {
-                    resolve::comparison::twoarg_instanceof(node, modifiers, current, kinds, globals)
+                    resolve::comparison::twoarg_instanceof(node,
+                                                           modifiers,
+                                                           current,
+                                                           kinds,
+                                                           globals,
+                                                           annotations)
                 }
                 TokenKind::FSlash | TokenKind::Minus | TokenKind::Percent | TokenKind::Plus |
-                TokenKind::Star => resolve::math::go(node, modifiers, current, kinds, globals),
+                TokenKind::Star => resolve::math::go(node,
+                                                     modifiers,
+                                                     current,
+                                                     kinds,
+                                                     globals,
+                                                     annotations),
                 TokenKind::Boolean | TokenKind::Byte | TokenKind::Char | TokenKind::CharValue |
                 TokenKind::False | TokenKind::Int | TokenKind::Null | TokenKind::NumValue |
                 TokenKind::Short | TokenKind::StrValue | TokenKind::True => {
@@ -82,8 +132,13 @@ pub fn go(mut node: &mut ASTNode,
                 // Naked returns and empty statements resolve to Null
                 TokenKind::Return => resolve::primitive::go(&NULL.clone()),
                 TokenKind::Semicolon => resolve::primitive::go(&NULL.clone()),
-                _ => Err(format!("could not resolve expression {:?}", node)),
+                _ => Err(AnalysisError::other(format!("could not resolve expression {:?}", node))),
             }
         }
+    };
+
+    if let Ok(ref t) = kind {
+        annotations.annotate(node, t, None);
     }
+    kind
 }
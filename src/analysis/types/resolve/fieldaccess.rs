@@ -1,8 +1,12 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::annotation::Declaration;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -19,7 +23,7 @@ lazy_static! {
 //           current: &ClassOrInterfaceEnvironment,
 //           kinds: &Vec<ClassOrInterfaceEnvironment>,
 //           globals: &Vec<VariableEnvironment>)
-//           -> Result<Type, String> {
+//           -> Result<Type, AnalysisError> {
 //     let lhs = match resolve::expression::go(&mut node.children[0],
 //                                             modifiers, current, kinds, globals) {
 //         Ok(t) => t,
@@ -34,11 +38,13 @@ pub fn go(node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
     let cls = match node.children[0].token.kind {
         TokenKind::This if modifiers.contains(&*STATIC) => {
-            return Err(format!("can not use 'this' in static method"));
+            let message = format!("can not use 'this' in static method");
+            return Err(AnalysisError::new(AnalysisCode::ThisInStatic, message));
         }
         TokenKind::This => current.clone(),
         _ => {
@@ -46,7 +52,8 @@ pub fn go(node: &mut ASTNode,
                                                     modifiers,
                                                     current,
                                                     kinds,
-                                                    globals) {
+                                                    globals,
+                                                    annotations) {
                 Ok(l) => l,
                 Err(e) => return Err(e),
             };
@@ -67,13 +74,20 @@ pub fn go(node: &mut ASTNode,
     for field in &cls.fields {
         if field.name == node.children[2] {
             match lookup::class::in_env(&field.kind, &cls, kinds) {
-                Ok(cls) => return Ok(Type::new(cls)),
+                Ok(kind) => {
+                    let kind = Type::new(kind);
+                    let declaration = Declaration::Field(cls.name.clone(), field.clone());
+                    annotations.annotate(node, &kind, Some(declaration));
+                    return Ok(kind);
+                }
                 Err(_) => (),
             }
         }
     }
 
-    Err(format!("could not find field {} in class {}",
-                node.children[2],
-                cls.name))
+    let name = node.children[2].source_text();
+    let message = format!("could not find field {} in class {}", name, cls.name.source_text());
+    let candidates = cls.fields.iter().map(|f| f.name.source_text());
+    let error = AnalysisError::new(AnalysisCode::UnresolvedField, message);
+    Err(lookup::suggest(error, &name, candidates))
 }
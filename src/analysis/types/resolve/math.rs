@@ -1,25 +1,36 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisError;
 use scanner::ASTNode;
 
 pub fn go(node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
-    let lhs =
-        match resolve::expression::go(&mut node.children[0], modifiers, current, kinds, globals) {
-            Ok(l) => l,
-            Err(e) => return Err(e),
-        };
-    let rhs =
-        match resolve::expression::go(&mut node.children[1], modifiers, current, kinds, globals) {
-            Ok(r) => r,
-            Err(e) => return Err(e),
-        };
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
+    let lhs = match resolve::expression::go(&mut node.children[0],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(l) => l,
+        Err(e) => return Err(e),
+    };
+    let rhs = match resolve::expression::go(&mut node.children[1],
+                                            modifiers,
+                                            current,
+                                            kinds,
+                                            globals,
+                                            annotations) {
+        Ok(r) => r,
+        Err(e) => return Err(e),
+    };
 
     lhs.apply_math(&node.token.kind, &rhs)
 }
@@ -1,9 +1,13 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::MethodEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::annotation::Declaration;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -37,8 +41,9 @@ pub fn get_args(mut node: &mut ASTNode,
                 modifiers: &Vec<ASTNode>,
                 current: &ClassOrInterfaceEnvironment,
                 kinds: &Vec<ClassOrInterfaceEnvironment>,
-                globals: &mut Vec<VariableEnvironment>)
-                -> Result<Vec<Type>, String> {
+                globals: &mut Vec<VariableEnvironment>,
+                annotations: &mut Annotations)
+                -> Result<Vec<Type>, AnalysisError> {
     if idx == 0 {
         return Ok(Vec::new());
     }
@@ -57,14 +62,20 @@ pub fn get_args(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          globals) {
+                                          globals,
+                                          annotations) {
                 Ok(t) => resolved.push(t),
                 Err(e) => return Err(e),
             }
             continue;
         }
 
-        let kind = match resolve::expression::go(&mut arg, modifiers, current, kinds, globals) {
+        let kind = match resolve::expression::go(&mut arg,
+                                                 modifiers,
+                                                 current,
+                                                 kinds,
+                                                 globals,
+                                                 annotations) {
             Ok(t) => {
                 resolved.push(t.clone());
                 t.kind.name
@@ -84,15 +95,16 @@ fn get_method(mut node: &mut ASTNode,
               modifiers: &Vec<ASTNode>,
               current: &ClassOrInterfaceEnvironment,
               kinds: &Vec<ClassOrInterfaceEnvironment>,
-              globals: &mut Vec<VariableEnvironment>)
-              -> Result<(ClassOrInterfaceEnvironment, MethodEnvironment), String> {
+              globals: &mut Vec<VariableEnvironment>,
+              annotations: &mut Annotations)
+              -> Result<(ClassOrInterfaceEnvironment, MethodEnvironment), AnalysisError> {
     let idx = match node.children.len() {
         6 => 4,
         4 => 2,
         _ => 0,
     };
 
-    let args = match get_args(node, idx, modifiers, current, kinds, globals) {
+    let args = match get_args(node, idx, modifiers, current, kinds, globals, annotations) {
         Ok(a) => a,
         Err(e) => return Err(e),
     };
@@ -107,9 +119,11 @@ fn get_method(mut node: &mut ASTNode,
             }
 
             canonical.flatten();
+            resolve::name::prefix(&canonical, globals, annotations);
             if modifiers.contains(&*STATIC) &&
                canonical.children.first().unwrap().token.kind == TokenKind::This {
-                return Err(format!("can not use 'this' in static method"));
+                let message = format!("can not use 'this' in static method");
+                return Err(AnalysisError::new(AnalysisCode::ThisInStatic, message));
             }
 
             let var_result = lookup::method::in_variables(&canonical,
@@ -129,7 +143,8 @@ fn get_method(mut node: &mut ASTNode,
             }
 
             // implicit `this`
-            let mut imp_result = Err(format!("implicit 'this' can not be used in static methods"));
+            let message = format!("implicit 'this' can not be used in static methods");
+            let mut imp_result = Err(AnalysisError::new(AnalysisCode::ThisInStatic, message));
             if !modifiers.contains(&*STATIC) {
                 // TODO: in_class would save some effort
                 imp_result =
@@ -139,13 +154,28 @@ fn get_method(mut node: &mut ASTNode,
                 }
             }
 
-            Err(format!("could not resolve {:?} to method from class {:?}\n{}",
-                        canonical,
-                        current.name,
-                        format!("got errors:\n\t{:?}\n\t{:?}\n\t{:?}",
-                                var_result,
-                                exp_result,
-                                imp_result)))
+            // Report the failure from whichever lookup got furthest, as
+            // given by what the first identifier names.
+            let result = match canonical.children.first() {
+                Some(f) if canonical.children.len() > 1 &&
+                           globals.iter().any(|g| g.name == *f) => var_result,
+                Some(f) if canonical.children.len() > 1 &&
+                           !current.fields.iter().any(|cf| cf.name == *f) => exp_result,
+                _ => imp_result,
+            };
+            match result {
+                Ok(m) => Ok(m),
+                Err(e) => {
+                    let message = format!("could not resolve method {} from class {}: {}",
+                                          canonical.source_text(),
+                                          current.name.source_text(),
+                                          e);
+                    Err(AnalysisError {
+                        notes: e.notes,
+                        ..AnalysisError::new(AnalysisCode::UnresolvedMethod, message)
+                    })
+                }
+            }
         }
         // child[0] is class/field. child[2] is method on previous.
         5 | 6 => {
@@ -162,14 +192,16 @@ fn get_method(mut node: &mut ASTNode,
             primary.flatten();
             if modifiers.contains(&*STATIC) &&
                primary.children.first().unwrap().token.kind == TokenKind::This {
-                return Err(format!("can not use 'this' in static method"));
+                let message = format!("can not use 'this' in static method");
+                return Err(AnalysisError::new(AnalysisCode::ThisInStatic, message));
             }
 
             let lhs = match resolve::expression::go(&mut node.children[0],
                                                     modifiers,
                                                     current,
                                                     kinds,
-                                                    globals) {
+                                                    globals,
+                                                    annotations) {
                 Ok(t) => t,
                 Err(e) => return Err(e),
             };
@@ -180,15 +212,20 @@ fn get_method(mut node: &mut ASTNode,
 
             // TODO: in_class would save some effort
             match lookup::method::in_env(&lhs.kind.name, &name, &args, current, kinds) {
-                Ok(m) => return Ok(m),
-                Err(_) => (),
+                Ok(m) => Ok(m),
+                Err(e) => {
+                    let message = format!("could not resolve method {} on class {}: {}",
+                                          name.source_text(),
+                                          lhs.kind.name.source_text(),
+                                          e);
+                    Err(AnalysisError {
+                        notes: e.notes,
+                        ..AnalysisError::new(AnalysisCode::UnresolvedMethod, message)
+                    })
+                }
             }
-
-            Err(format!("could not resolve {:?} to method on class {:?}",
-                        name,
-                        lhs.kind.name))
         }
-        _ => Err(format!("malformed MethodInvocation {:?}", node)),
+        _ => Err(AnalysisError::other(format!("malformed MethodInvocation {:?}", node))),
     }
 }
 
@@ -196,9 +233,10 @@ pub fn go(node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &mut Vec<VariableEnvironment>)
-          -> Result<Type, String> {
-    let (cls, method) = match get_method(node, modifiers, current, kinds, globals) {
+          globals: &mut Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
+    let (cls, method) = match get_method(node, modifiers, current, kinds, globals, annotations) {
         Ok(m) => m,
         Err(e) => return Err(e),
     };
@@ -248,10 +286,11 @@ pub fn go(node: &mut ASTNode,
             match tcls.assign(&tcurrent, current, kinds) {
                 Ok(_) => (),
                 Err(_) => {
-                    return Err(format!("could not access method {} on class {} from class {}",
-                                       method.name,
-                                       cls.name,
-                                       current.name))
+                    let message = format!("could not access method {} on class {} from class {}",
+                                          method.name,
+                                          cls.name,
+                                          current.name);
+                    return Err(AnalysisError::new(AnalysisCode::InaccessibleMethod, message));
                 }
             }
         }
@@ -259,7 +298,14 @@ pub fn go(node: &mut ASTNode,
 
     let kind = method.return_type.clone();
     match lookup::class::in_env(&kind, &cls, kinds) {
-        Ok(cls) => Ok(Type::new(cls)),
-        Err(_) => Err(format!("could not lookup kind {} of method in class {}", kind, cls)),
+        Ok(found) => {
+            let kind = Type::new(found);
+            annotations.annotate(node, &kind, Some(Declaration::Method(cls.name, method)));
+            Ok(kind)
+        }
+        Err(_) => {
+            let message = format!("could not lookup kind {} of method in class {}", kind, cls);
+            Err(AnalysisError::other(message))
+        }
     }
 }
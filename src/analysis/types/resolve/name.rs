@@ -1,7 +1,11 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::annotation::Declaration;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -15,6 +19,43 @@ lazy_static! {
     };
 }
 
+// The field a qualified Name refers to, if it is a static field of the class
+// named by the rest of it, eg. `Foo.bar`.
+fn static_field(node: &ASTNode,
+                current: &ClassOrInterfaceEnvironment,
+                kinds: &Vec<ClassOrInterfaceEnvironment>)
+                -> Option<Declaration> {
+    if node.children.len() < 3 {
+        return None;
+    }
+
+    let mut class = node.clone();
+    let field = class.children.pop().unwrap();
+    class.children.pop();
+    match lookup::class::in_env(&class, current, kinds) {
+        Ok(cls) => {
+            cls.fields
+                .iter()
+                .find(|f| f.name == field)
+                .map(|f| Declaration::Field(cls.name.clone(), f.clone()))
+        }
+        Err(_) => None,
+    }
+}
+
+/// Records the local variable or parameter a qualified name starts with, eg.
+/// the `s` in `s.length`, so that code generation can find where it lives.
+pub fn prefix(node: &ASTNode, globals: &Vec<VariableEnvironment>, annotations: &mut Annotations) {
+    let first = match node.children.first() {
+        Some(f) if node.children.len() > 1 => f,
+        _ => return,
+    };
+
+    if let Some(local) = globals.iter().find(|g| g.name == *first) {
+        annotations.refer(first, local);
+    }
+}
+
 // A "Name" can refer to a bunch of things.
 //   - instantiated/uninstantiated classes
 //   - fields on either of the above classes
@@ -23,42 +64,100 @@ pub fn go(node: &ASTNode,
           modifiers: &Vec<ASTNode>,
           current: &ClassOrInterfaceEnvironment,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
-          globals: &Vec<VariableEnvironment>)
-          -> Result<Type, String> {
+          globals: &Vec<VariableEnvironment>,
+          annotations: &mut Annotations)
+          -> Result<Type, AnalysisError> {
     let mut node = node.clone();
     node.flatten();
+    prefix(&node, globals, annotations);
     if modifiers.contains(&*STATIC) &&
        node.children.first().unwrap().token.kind == TokenKind::This {
-        return Err(format!("can not use 'this' in static method"));
+        let message = format!("can not use 'this' in static method");
+        return Err(AnalysisError::new(AnalysisCode::ThisInStatic, message));
     }
 
     // TODO: make lookup::field return a FieldEnv. Then, when looking up type, keep the modifiers
-    match lookup::field::in_variables(&node, &NAME.clone(), current, kinds, globals) {
-        Ok(t) => return Ok(t),
-        Err(_) => (),
+    let var_result = lookup::field::in_variables(&node, &NAME.clone(), current, kinds, globals);
+    if let Ok(ref t) = var_result {
+        if let Some(local) = globals.iter().find(|g| g.name == node) {
+            annotations.annotate(&node, t, Some(Declaration::Local(local.clone())));
+        }
+        return var_result;
     }
 
     match lookup::class::in_variables(&node, current, kinds, globals) {
-        Ok(c) => return Ok(Type::new(c)),
+        Ok(c) => {
+            let kind = Type::new(c);
+            if let Some(local) = globals.iter().find(|g| g.name == node) {
+                annotations.annotate(&node, &kind, Some(Declaration::Local(local.clone())));
+            }
+            return Ok(kind);
+        }
         Err(_) => (),
     }
 
     match lookup::field::in_env(&node, &NAME.clone(), current, kinds) {
-        Ok(t) => return Ok(t),
+        Ok(t) => {
+            annotations.annotate(&node, &t, static_field(&node, current, kinds));
+            return Ok(t);
+        }
         Err(_) => (),
     }
 
     // implicit `this`
+    let message = format!("implicit 'this' can not be used in static methods");
+    let mut imp_result = Err(AnalysisError::new(AnalysisCode::ThisInStatic, message));
     if !modifiers.contains(&*STATIC) {
         // TODO: in_class would save some effort
-        match lookup::field::in_env(&current.name, &node, current, kinds) {
-            Ok(t) => return Ok(t),
-            Err(_) => (),
+        imp_result = lookup::field::in_env(&current.name, &node, current, kinds);
+        if let Ok(ref t) = imp_result {
+            if let Some(field) = current.fields.iter().find(|f| f.name == node) {
+                let declaration = Declaration::Field(current.name.clone(), field.clone());
+                annotations.annotate(&node, t, Some(declaration));
+            }
+            return imp_result;
         }
     }
 
-    match lookup::class::in_env(&node, current, kinds) {
-        Ok(f) => Ok(Type::new(f)),
-        Err(e) => Err(e),
+    let cls_result = lookup::class::in_env(&node, current, kinds);
+    if let Ok(f) = cls_result {
+        let kind = Type::new(f);
+        annotations.annotate(&node, &kind, Some(Declaration::Type(kind.kind.name.clone())));
+        return Ok(kind);
     }
+
+    // Report the failure from whichever lookup got furthest, as given by what
+    // the first identifier names.
+    let first = match node.children.first() {
+        Some(f) if node.children.len() > 1 => f,
+        _ => return Err(unresolved(&node, current, kinds, globals)),
+    };
+    if globals.iter().any(|g| g.name == *first) {
+        return var_result;
+    }
+    if current.fields.iter().any(|f| f.name == *first) {
+        return imp_result;
+    }
+    cls_result.map(Type::new)
+}
+
+// Suggests the closest local, field or type to a simple name which could not
+// be resolved.
+fn unresolved(node: &ASTNode,
+              current: &ClassOrInterfaceEnvironment,
+              kinds: &Vec<ClassOrInterfaceEnvironment>,
+              globals: &Vec<VariableEnvironment>)
+              -> AnalysisError {
+    let name = node.source_text();
+    let candidates = globals.iter()
+        .map(|g| g.name.source_text())
+        .chain(current.fields.iter().map(|f| f.name.source_text()))
+        .chain(lookup::class::visible(current, kinds).into_iter())
+        .collect::<Vec<String>>();
+
+    let message = format!("could not find variable, field or type {} in class {}",
+                          name,
+                          current.name.source_text());
+    let error = AnalysisError::new(AnalysisCode::UnresolvedName, message);
+    lookup::suggest(error, &name, candidates)
 }
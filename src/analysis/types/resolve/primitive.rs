@@ -1,6 +1,7 @@
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::types::obj::Type;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -58,7 +59,7 @@ lazy_static! {
     };
 }
 
-pub fn go(node: &ASTNode) -> Result<Type, String> {
+pub fn go(node: &ASTNode) -> Result<Type, AnalysisError> {
     match node.token.kind {
         // primitives
         TokenKind::Boolean => Ok(BOOLEAN.clone()),
@@ -76,6 +77,7 @@ pub fn go(node: &ASTNode) -> Result<Type, String> {
                 token: Token {
                     kind: TokenKind::Int,
                     lexeme: node.token.clone().lexeme,
+                    span: node.token.span,
                 },
                 children: Vec::new(),
             };
@@ -96,6 +98,6 @@ pub fn go(node: &ASTNode) -> Result<Type, String> {
             };
             Ok(Type::new(ClassOrInterfaceEnvironment::new(node, ClassOrInterface::CLASS)))
         }
-        _ => Err(format!("invalid primitive type {:?}", node)),
+        _ => Err(AnalysisError::other(format!("invalid primitive type {:?}", node))),
     }
 }
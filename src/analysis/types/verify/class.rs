@@ -1,11 +1,12 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::types::lookup;
+use error::AnalysisError;
 use scanner::ASTNode;
 
 pub fn resolveable(name: &ASTNode,
                    current: &ClassOrInterfaceEnvironment,
                    kinds: &Vec<ClassOrInterfaceEnvironment>)
-                   -> Result<(), String> {
+                   -> Result<(), AnalysisError> {
     let cls = match lookup::class::in_env(name, current, kinds) {
         Ok(c) => c,
         Err(e) => return Err(e),
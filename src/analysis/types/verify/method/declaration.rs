@@ -1,19 +1,33 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use analysis::types::annotation::Declaration;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
 use analysis::types::verify;
+use error::AnalysisCode;
+use error::AnalysisError;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+use error::Warning;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
+fn error(reason: AnalysisError, node: &ASTNode) -> Diagnostic {
+    Diagnostic::new(Phase::Types, ErrorMessage::TypeError(reason), node.span())
+}
+
 pub fn go(mut node: &mut ASTNode,
           modifiers: &Vec<ASTNode>,
           kinds: &Vec<ClassOrInterfaceEnvironment>,
           current: &ClassOrInterfaceEnvironment,
           globals: &Vec<VariableEnvironment>,
-          locals: &mut Vec<VariableEnvironment>)
-          -> Result<(), String> {
+          locals: &mut Vec<VariableEnvironment>,
+          warnings: &mut Vec<Warning>,
+          annotations: &mut Annotations)
+          -> Result<(), Diagnostic> {
     let new = VariableEnvironment::new(node.clone());
     // TODO: chain
     for global in globals.clone() {
@@ -21,16 +35,24 @@ pub fn go(mut node: &mut ASTNode,
             continue;
         }
 
-        return Err(format!("cannot declare variable {} with conflict in outer scope",
-                           new.name));
+        let message = format!("cannot declare variable {} with conflict in outer scope",
+                              new.name);
+        let reason = AnalysisError::new(AnalysisCode::ShadowedVariable, message);
+        let message = ErrorMessage::TypeError(reason);
+        return Err(Diagnostic::new(Phase::Types, message, new.span())
+                       .with_label(global.span(), "previously declared here".to_owned()));
     }
     for local in locals.clone() {
         if local.name != new.name {
             continue;
         }
 
-        return Err(format!("cannot declare variable {} with conflict in local scope",
-                           new.name));
+        let message = format!("cannot declare variable {} with conflict in local scope",
+                              new.name);
+        let reason = AnalysisError::new(AnalysisCode::ShadowedVariable, message);
+        let message = ErrorMessage::TypeError(reason);
+        return Err(Diagnostic::new(Phase::Types, message, new.span())
+                       .with_label(local.span(), "previously declared here".to_owned()));
     }
 
     locals.push(new.clone());
@@ -42,28 +64,19 @@ pub fn go(mut node: &mut ASTNode,
                                                       current,
                                                       kinds,
                                                       globals,
-                                                      locals) {
+                                                      locals,
+                                                      warnings,
+                                                      annotations) {
                 Ok(_) => (),
                 Err(e) => return Err(e),
             }
 
-            let rvalue = node.children[1].clone().children[1].clone();
-            match verify::variable::initialized(&rvalue, current, globals) {
-                Ok(_) => {
-                    match verify::variable::initialized(&rvalue, current, locals) {
-                        Ok(_) => (),
-                        Err(e) => return Err(e),
-                    }
-                }
-                Err(e) => return Err(e),
-            }
-
             let lhs = match lookup::class::in_env(&new.kind, current, kinds) {
                 Ok(l) => {
                     node.children[0] = l.name.clone();
                     Type::new(l)
                 }
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, &node.children[0])),
             };
 
             let mut block_globals = globals.clone();
@@ -72,21 +85,30 @@ pub fn go(mut node: &mut ASTNode,
                                                     modifiers,
                                                     current,
                                                     kinds,
-                                                    &mut block_globals) {
+                                                    &mut block_globals,
+                                                    annotations) {
                 Ok(r) => r,
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, &node.children[1].children[1])),
             };
 
             match lhs.assign(&rhs, current, kinds) {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, &node.children[1])),
             }
-
-            let idx = &locals.len() - 1;
-            locals[idx].initialized = true;
         }
         _ => (),
     }
 
-    verify::class::resolveable(&new.kind, current, kinds)
+    match verify::class::resolveable(&new.kind, current, kinds) {
+        Ok(_) => (),
+        Err(e) => return Err(error(e, &node.children[0])),
+    }
+
+    match lookup::class::in_env(&new.kind, current, kinds) {
+        Ok(c) => {
+            annotations.annotate(&new.name, &Type::new(c), Some(Declaration::Local(new.clone())));
+            Ok(())
+        }
+        Err(e) => Err(error(e, &node.children[0])),
+    }
 }
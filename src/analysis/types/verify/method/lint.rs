@@ -0,0 +1,114 @@
+use analysis::environment::ClassOrInterfaceEnvironment;
+use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
+use error::Lint;
+use error::Warning;
+use scanner::ASTNode;
+use scanner::TokenKind;
+
+fn is_string(label: Option<String>) -> bool {
+    label == Some("java.lang.String".to_owned())
+}
+
+fn is_block(node: &ASTNode) -> bool {
+    node.token.kind == TokenKind::NonTerminal && node.token.lexeme == Some("Block".to_owned())
+}
+
+// Whether a node reads the variable `name`. Assigning to the variable itself,
+// eg. `x = 1`, does not read it, though `x = x + 1` and `x[0] = 1` do.
+fn reads(node: &ASTNode, name: &str) -> bool {
+    if node.token.lexeme == Some("Assignment".to_owned()) && node.children.len() == 3 {
+        let mut lhs = node.children[0].clone();
+        lhs.flatten();
+        if lhs.children.len() == 1 && lhs.mentions(name) {
+            return reads(&node.children[2], name);
+        }
+    }
+
+    if node.token.lexeme == Some("Name".to_owned()) {
+        return node.mentions(name);
+    }
+    node.children.iter().any(|c| reads(c, name))
+}
+
+/// Warns about every parameter which is never read in a method or
+/// constructor body.
+pub fn unused_parameters(parameters: &Vec<VariableEnvironment>,
+                         body: &ASTNode,
+                         warnings: &mut Vec<Warning>) {
+    for parameter in parameters {
+        let name = parameter.name.source_text();
+        if !reads(body, &name) {
+            let message = format!("unused parameter {}", name);
+            warnings.push(Warning::new(Lint::UnusedParameter, message, parameter.name.span()));
+        }
+    }
+}
+
+/// Warns about every local variable declared in a list of statements which is
+/// never read by any of the statements after its declaration.
+pub fn unused_locals(statements: &[ASTNode], warnings: &mut Vec<Warning>) {
+    for (idx, statement) in statements.iter().enumerate() {
+        if statement.token.lexeme != Some("LocalVariableDeclaration".to_owned()) {
+            continue;
+        }
+
+        let local = VariableEnvironment::new(statement.clone());
+        let name = local.name.source_text();
+        if !statements[idx + 1..].iter().any(|s| reads(s, &name)) {
+            let message = format!("unused variable {}", name);
+            warnings.push(Warning::new(Lint::UnusedVariable, message, local.name.span()));
+        }
+    }
+}
+
+/// Warns if a newly declared local variable hides a field of the current
+/// class.
+pub fn shadowed_field(local: &VariableEnvironment,
+                      current: &ClassOrInterfaceEnvironment,
+                      warnings: &mut Vec<Warning>) {
+    let name = local.name.source_text();
+    for field in &current.fields {
+        if field.name.source_text() == name {
+            let message = format!("variable {} shadows a field of {}",
+                                  name,
+                                  current.name.source_text());
+            warnings.push(Warning::new(Lint::ShadowedField, message, local.name.span())
+                              .with_label(field.name.span(), "field declared here".to_owned()));
+            return;
+        }
+    }
+}
+
+/// Warns if the body of a statement is either `;` or `{}`.
+pub fn empty_body(statement: &str, body: &ASTNode, warnings: &mut Vec<Warning>) {
+    if body.token.kind == TokenKind::Semicolon || (is_block(body) && body.children.len() == 2) {
+        let message = format!("{} statement has an empty body", statement);
+        warnings.push(Warning::new(Lint::EmptyBody, message, body.span()));
+    }
+}
+
+/// Warns about every `==` or `!=` in a method or constructor body which
+/// compares two Strings, using the types found when the body was resolved.
+pub fn string_comparisons(node: &ASTNode, annotations: &Annotations, warnings: &mut Vec<Warning>) {
+    let comparison = node.token.kind == TokenKind::Equality ||
+                     node.token.kind == TokenKind::NotEqual;
+    if comparison && node.children.len() == 2 {
+        let lhs = annotations.label(&node.children[0]);
+        let rhs = annotations.label(&node.children[1]);
+        if is_string(lhs) && is_string(rhs) {
+            let operator = match node.token.kind {
+                TokenKind::Equality => "==",
+                _ => "!=",
+            };
+            let message = format!("Strings compared by reference with {}; use equals to \
+                                   compare their contents",
+                                  operator);
+            warnings.push(Warning::new(Lint::StringComparison, message, node.span()));
+        }
+    }
+
+    for child in &node.children {
+        string_comparisons(child, annotations, warnings);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod declaration;
+pub mod lint;
 pub mod statement;
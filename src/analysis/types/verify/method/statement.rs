@@ -1,10 +1,18 @@
 use analysis::environment::ClassOrInterface;
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::environment::VariableEnvironment;
+use analysis::types::annotation::Annotations;
 use analysis::types::lookup;
 use analysis::types::obj::Type;
 use analysis::types::resolve;
 use analysis::types::verify;
+use analysis::types::verify::method::lint;
+use error::AnalysisCode;
+use error::AnalysisError;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+use error::Warning;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -27,13 +35,26 @@ lazy_static! {
     };
 }
 
+fn error(reason: AnalysisError, node: &ASTNode) -> Diagnostic {
+    Diagnostic::new(Phase::Types, ErrorMessage::TypeError(reason), node.span())
+}
+
+// The types returned by the return statements within a statement. Whether
+// those statements are reachable is checked separately, by
+// verify::reachability.
+fn returned(rts: Vec<(Type, bool)>) -> Vec<(Type, bool)> {
+    rts.into_iter().filter(|&(ref rt, is_ret)| is_ret && *rt != *VOID).collect()
+}
+
 // TODO: cleanup by splitting into entrance point and recursion fn
 pub fn block(mut node: &mut ASTNode,
              modifiers: &Vec<ASTNode>,
              current: &ClassOrInterfaceEnvironment,
              kinds: &Vec<ClassOrInterfaceEnvironment>,
-             globals: &Vec<VariableEnvironment>)
-             -> Result<Vec<Type>, String> {
+             globals: &Vec<VariableEnvironment>,
+             warnings: &mut Vec<Warning>,
+             annotations: &mut Annotations)
+             -> Result<Vec<Type>, Diagnostic> {
     match node.clone().token.lexeme {
         Some(ref l) if l == "BlockStatements" => {
             node.flatten();
@@ -44,20 +65,10 @@ pub fn block(mut node: &mut ASTNode,
                            current,
                            kinds,
                            globals,
-                           &mut Vec::new()) {
-                Ok(rts) => {
-                    let mut return_types: Vec<Type> = Vec::new();
-                    for (rt, is_ret) in rts {
-                        if !return_types.is_empty() {
-                            // TODO: too naive
-                            return Err(format!("unreachable code in block::Block"));
-                        } else if !is_ret || rt == *VOID {
-                            continue;
-                        }
-                        return_types.push(rt);
-                    }
-                    return Ok(return_types);
-                }
+                           &mut Vec::new(),
+                           warnings,
+                           annotations) {
+                Ok(rts) => return Ok(returned(rts).into_iter().map(|(rt, _)| rt).collect()),
                 Err(e) => return Err(e),
             };
         }
@@ -68,30 +79,29 @@ pub fn block(mut node: &mut ASTNode,
         }
     }
 
+    // Warnings are checked against the statements as written, before they are
+    // rewritten by resolution.
+    let statements = node.children.clone();
+
     let mut locals = Vec::new();
     let mut return_types = Vec::new();
     for mut child in &mut node.children {
-        match nonblock(&mut child, modifiers, current, kinds, globals, &mut locals) {
-            Ok(rts) => {
-                for (rt, is_ret) in rts {
-                    if !return_types.is_empty() {
-                        // TODO: too naive
-                        return Err(format!("unreachable code in block"));
-                    } else if !is_ret || rt == *VOID {
-                        continue;
-                    }
-                    return_types.push(rt);
-                }
-            }
+        match nonblock(&mut child,
+                       modifiers,
+                       current,
+                       kinds,
+                       globals,
+                       &mut locals,
+                       warnings,
+                       annotations) {
+            Ok(rts) => return_types.extend(returned(rts).into_iter().map(|(rt, _)| rt)),
             Err(e) => return Err(e),
         }
     }
 
-    if return_types.is_empty() {
-        Ok(Vec::new())
-    } else {
-        Ok(return_types.clone())
-    }
+    lint::unused_locals(&statements, warnings);
+
+    Ok(return_types)
 }
 
 // TODO hack: return type sucks. Currently, its a vector of Types and whether
@@ -101,8 +111,10 @@ pub fn nonblock(mut node: &mut ASTNode,
                 current: &ClassOrInterfaceEnvironment,
                 kinds: &Vec<ClassOrInterfaceEnvironment>,
                 globals: &Vec<VariableEnvironment>,
-                locals: &mut Vec<VariableEnvironment>)
-                -> Result<Vec<(Type, bool)>, String> {
+                locals: &mut Vec<VariableEnvironment>,
+                warnings: &mut Vec<Warning>,
+                annotations: &mut Annotations)
+                -> Result<Vec<(Type, bool)>, Diagnostic> {
     match node.clone().token.lexeme {
         // TODO: check accesses of protected fields, methods, and constructors are in
         // subtype or same package
@@ -120,13 +132,17 @@ pub fn nonblock(mut node: &mut ASTNode,
 
             match lookup::class::in_env(&kind, current, kinds) {
                 Ok(ref k) if k.modifiers.contains(&*ABSTRACT) => {
-                    Err(format!("instantiated abstract class {}", k.name))
+                    let message = format!("instantiated abstract class {}", k.name);
+                    let reason = AnalysisError::new(AnalysisCode::InstantiateAbstract, message);
+                    Err(error(reason, node))
                 }
                 Ok(ref k) if k.kind == ClassOrInterface::INTERFACE => {
-                    Err(format!("instantiated interface {}", k.name))
+                    let message = format!("instantiated interface {}", k.name);
+                    let reason = AnalysisError::new(AnalysisCode::InstantiateAbstract, message);
+                    Err(error(reason, node))
                 }
                 Ok(k) => Ok(vec![(Type::new(k.clone()), false)]),
-                Err(e) => Err(e),
+                Err(e) => Err(error(e, node)),
             }
         }
         Some(ref l) if l == "ClassInstanceCreationExpression" => {
@@ -138,9 +154,10 @@ pub fn nonblock(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          &mut block_globals) {
+                                          &mut block_globals,
+                                          annotations) {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, node)),
             }
 
             let mut kind = node.children[0].clone();
@@ -152,13 +169,17 @@ pub fn nonblock(mut node: &mut ASTNode,
 
             match lookup::class::in_env(&kind, current, kinds) {
                 Ok(ref k) if k.modifiers.contains(&*ABSTRACT) => {
-                    Err(format!("instantiated abstract class {}", k.name))
+                    let message = format!("instantiated abstract class {}", k.name);
+                    let reason = AnalysisError::new(AnalysisCode::InstantiateAbstract, message);
+                    Err(error(reason, node))
                 }
                 Ok(ref k) if k.kind == ClassOrInterface::INTERFACE => {
-                    Err(format!("instantiated interface {}", k.name))
+                    let message = format!("instantiated interface {}", k.name);
+                    let reason = AnalysisError::new(AnalysisCode::InstantiateAbstract, message);
+                    Err(error(reason, node))
                 }
                 Ok(k) => Ok(vec![(Type::new(k.clone()), false)]),
-                Err(e) => Err(e),
+                Err(e) => Err(error(e, node)),
             }
         }
         // TODO: look into TokenKind::Assignment vs "Assignment"
@@ -169,9 +190,10 @@ pub fn nonblock(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          &mut block_globals) {
+                                          &mut block_globals,
+                                          annotations) {
                 Ok(t) => Ok(vec![(t.clone(), false)]),
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, node)),
             }
         }
         Some(ref l) if l == "Block" && node.children.len() == 3 => {
@@ -181,7 +203,9 @@ pub fn nonblock(mut node: &mut ASTNode,
                         modifiers,
                         current,
                         kinds,
-                        &mut block_globals) {
+                        &mut block_globals,
+                        warnings,
+                        annotations) {
                 Ok(rts) => {
                     let mut return_types = Vec::new();
                     for rt in rts {
@@ -194,6 +218,8 @@ pub fn nonblock(mut node: &mut ASTNode,
         }
         Some(ref l) if l == "Block" => Ok(vec![(VOID.clone(), false)]),
         Some(ref l) if l == "ForStatement" || l == "ForStatementNoShortIf" => {
+            let statements = node.children.clone();
+
             let mut block_globals = globals.clone();
             block_globals.extend(locals.clone());
 
@@ -204,7 +230,9 @@ pub fn nonblock(mut node: &mut ASTNode,
                            current,
                            kinds,
                            &block_globals,
-                           &mut block_locals) {
+                           &mut block_locals,
+                           warnings,
+                           annotations) {
                 Ok(_) => (),
                 Err(e) => return Err(e),
             }
@@ -213,28 +241,31 @@ pub fn nonblock(mut node: &mut ASTNode,
             if node.children[2].token.kind != TokenKind::Semicolon {
                 cond_idx += 1;
             }
-            match nonblock(&mut node.children[cond_idx],
-                           modifiers,
-                           current,
-                           kinds,
-                           &block_globals,
-                           &mut block_locals) {
-                Ok(ts) => {
-                    if ts.len() != 1 {
-                        return Err(format!("for condition has multiple types"));
-                    }
 
-                    if ts[0].0.kind.name.token.kind != TokenKind::Boolean {
-                        return Err(format!("for condition is not a boolean"));
-                    }
+            // A missing condition is always true.
+            if node.children[cond_idx].token.kind != TokenKind::Semicolon {
+                match nonblock(&mut node.children[cond_idx],
+                               modifiers,
+                               current,
+                               kinds,
+                               &block_globals,
+                               &mut block_locals,
+                               warnings,
+                               annotations) {
+                    Ok(ts) => {
+                        if ts.len() != 1 {
+                            let message = format!("for condition has multiple types");
+                            return Err(error(AnalysisError::other(message), node));
+                        }
 
-                    if let Some(value) = ts[0].0.kind.name.token.lexeme.clone() {
-                        if value == "false".to_owned() {
-                            return Err(format!("for statement condition is false"));
+                        if ts[0].0.kind.name.token.kind != TokenKind::Boolean {
+                            let message = format!("for condition is not a boolean");
+                            let code = AnalysisCode::NonBooleanCondition;
+                            return Err(error(AnalysisError::new(code, message), node));
                         }
                     }
+                    Err(e) => return Err(e),
                 }
-                Err(e) => return Err(e),
             }
 
             // Update statement is always 2 children before last child. If there
@@ -246,24 +277,30 @@ pub fn nonblock(mut node: &mut ASTNode,
                                current,
                                kinds,
                                &block_globals,
-                               &mut block_locals) {
+                               &mut block_locals,
+                               warnings,
+                               annotations) {
                     Ok(_) => (),
                     Err(e) => return Err(e),
                 }
             }
 
             let idx = node.children.len() - 1;
-            match nonblock(&mut node.children[idx],
-                           modifiers,
-                           current,
-                           kinds,
-                           &block_globals,
-                           &mut block_locals) {
-                Ok(_) => (),
+            let return_types = match nonblock(&mut node.children[idx],
+                                              modifiers,
+                                              current,
+                                              kinds,
+                                              &block_globals,
+                                              &mut block_locals,
+                                              warnings,
+                                              annotations) {
+                Ok(rts) => returned(rts),
                 Err(e) => return Err(e),
-            }
+            };
+
+            lint::unused_locals(&statements[2..], warnings);
 
-            Ok(vec![(VOID.clone(), false)])
+            Ok(return_types)
         }
         Some(ref l) if l == "IfStatement" => {
             let mut block_globals = globals.clone();
@@ -272,34 +309,29 @@ pub fn nonblock(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          &mut block_globals) {
+                                          &mut block_globals,
+                                          annotations) {
                 Ok(ref t) if t == &*BOOLEAN => (),
-                Ok(_) => return Err(format!("condition {} is not boolean", node.children[2])),
-                Err(e) => return Err(e),
+                Ok(_) => {
+                    let message = format!("condition {} is not boolean", node.children[2]);
+                    let reason = AnalysisError::new(AnalysisCode::NonBooleanCondition, message);
+                    return Err(error(reason, &node.children[2]));
+                }
+                Err(e) => return Err(error(e, &node.children[2])),
             }
 
+            lint::empty_body("if", &node.children[4], warnings);
             match nonblock(&mut node.children[4],
                            modifiers,
                            current,
                            kinds,
                            &block_globals,
-                           &mut Vec::new()) {
-                Ok(rts) => {
-                    let mut return_types: Vec<Type> = Vec::new();
-                    for (rt, is_ret) in rts {
-                        if !return_types.is_empty() {
-                            // TODO: too naive
-                            return Err(format!("unreachable code in IfStatement"));
-                        } else if !is_ret || rt == *VOID {
-                            continue;
-                        }
-                        return_types.push(rt);
-                    }
-                }
-                Err(e) => return Err(e),
+                           &mut Vec::new(),
+                           warnings,
+                           annotations) {
+                Ok(rts) => Ok(returned(rts)),
+                Err(e) => Err(e),
             }
-
-            Ok(vec![(VOID.clone(), false)])
         }
         Some(ref l) if l == "WhileStatement" || l == "WhileStatementNoShortIf" => {
             let mut block_globals = globals.clone();
@@ -308,32 +340,29 @@ pub fn nonblock(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          &mut block_globals) {
-                Ok(ref t) if t == &*BOOLEAN => {
-                    if let Some(value) = t.kind.name.token.lexeme.clone() {
-                        if value == "false".to_owned() {
-                            return Err(format!("while statement condition is false"));
-                        } else if value == "true".to_owned() {
-                            // TODO: only if there is another statement after this
-                            return Err(format!("while statement condition is true"));
-                        }
-                    }
+                                          &mut block_globals,
+                                          annotations) {
+                Ok(ref t) if t == &*BOOLEAN => (),
+                Ok(_) => {
+                    let message = format!("condition {} is not boolean", node.children[2]);
+                    let reason = AnalysisError::new(AnalysisCode::NonBooleanCondition, message);
+                    return Err(error(reason, &node.children[2]));
                 }
-                Ok(_) => return Err(format!("condition {} is not boolean", node.children[2])),
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, &node.children[2])),
             }
 
+            lint::empty_body("while", &node.children[4], warnings);
             match nonblock(&mut node.children[4],
                            modifiers,
                            current,
                            kinds,
                            &block_globals,
-                           &mut Vec::new()) {
-                Ok(_) => (),
-                Err(e) => return Err(e),
+                           &mut Vec::new(),
+                           warnings,
+                           annotations) {
+                Ok(rts) => Ok(returned(rts)),
+                Err(e) => Err(e),
             }
-
-            Ok(vec![(VOID.clone(), false)])
         }
         Some(ref l) if l == "IfElseStatement" || l == "IfElseStatementNoShortIf" => {
             let mut block_globals = globals.clone();
@@ -342,73 +371,43 @@ pub fn nonblock(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          &mut block_globals) {
+                                          &mut block_globals,
+                                          annotations) {
                 Ok(ref t) if t == &*BOOLEAN => (),
-                Ok(_) => return Err(format!("condition {} is not boolean", node.children[2])),
-                Err(e) => return Err(e),
+                Ok(_) => {
+                    let message = format!("condition {} is not boolean", node.children[2]);
+                    let reason = AnalysisError::new(AnalysisCode::NonBooleanCondition, message);
+                    return Err(error(reason, &node.children[2]));
+                }
+                Err(e) => return Err(error(e, &node.children[2])),
             }
 
-            let mut if_return_types: Vec<Type> = Vec::new();
-            match nonblock(&mut node.children[4],
-                           modifiers,
-                           current,
-                           kinds,
-                           &block_globals,
-                           &mut Vec::new()) {
-                Ok(rts) => {
-                    for (rt, is_ret) in rts {
-                        if !if_return_types.is_empty() {
-                            // TODO: too naive
-                            return Err(format!("unreachable code in IfElseStatement::if"));
-                        } else if !is_ret || rt == *VOID {
-                            continue;
-                        }
-                        if_return_types.push(rt);
-                    }
-                }
+            lint::empty_body("if", &node.children[4], warnings);
+            let mut return_types = match nonblock(&mut node.children[4],
+                                                  modifiers,
+                                                  current,
+                                                  kinds,
+                                                  &block_globals,
+                                                  &mut Vec::new(),
+                                                  warnings,
+                                                  annotations) {
+                Ok(rts) => returned(rts),
                 Err(e) => return Err(e),
-            }
+            };
 
-            let mut else_return_types: Vec<Type> = Vec::new();
             match nonblock(&mut node.children[6],
                            modifiers,
                            current,
                            kinds,
                            &block_globals,
-                           &mut Vec::new()) {
-                Ok(rts) => {
-                    for (rt, is_ret) in rts {
-                        if !else_return_types.is_empty() {
-                            // TODO: too naive
-                            return Err(format!("unreachable code in IfElseStatement::else"));
-                        } else if !is_ret || rt == *VOID {
-                            continue;
-                        }
-                        else_return_types.push(rt);
-                    }
-                }
+                           &mut Vec::new(),
+                           warnings,
+                           annotations) {
+                Ok(rts) => return_types.extend(returned(rts)),
                 Err(e) => return Err(e),
             }
 
-            if if_return_types.is_empty() && else_return_types.is_empty() {
-                Ok(vec![(VOID.clone(), false)])
-            } else if if_return_types.is_empty() != else_return_types.is_empty() {
-                // TODO: if neq issues?
-                Ok(vec![(VOID.clone(), false)])
-            } else {
-                let mut return_types: Vec<(Type, bool)> = Vec::new();
-                for rt in if_return_types.iter().chain(else_return_types.iter()) {
-                    let new = (rt.clone(), true);
-                    if return_types.contains(&new) {
-                        continue;
-                    } else if !return_types.is_empty() {
-                        return Err(format!("TODO: IfElseStatement with multiple return types"));
-                    }
-
-                    return_types.push(new);
-                }
-                Ok(return_types)
-            }
+            Ok(return_types)
         }
         Some(ref l) if l == "LocalVariableDeclaration" => {
             match verify::method::declaration::go(&mut node,
@@ -416,9 +415,16 @@ pub fn nonblock(mut node: &mut ASTNode,
                                                   kinds,
                                                   current,
                                                   globals,
-                                                  locals) {
+                                                  locals,
+                                                  warnings,
+                                                  annotations) {
                 // TODO: what type is this?
-                Ok(_) => Ok(vec![(VOID.clone(), false)]),
+                Ok(_) => {
+                    if let Some(local) = locals.last() {
+                        lint::shadowed_field(local, current, warnings);
+                    }
+                    Ok(vec![(VOID.clone(), false)])
+                }
                 Err(e) => Err(e),
             }
         }
@@ -431,9 +437,10 @@ pub fn nonblock(mut node: &mut ASTNode,
                                                        modifiers,
                                                        current,
                                                        kinds,
-                                                       &mut block_globals) {
+                                                       &mut block_globals,
+                                                       annotations) {
                 Ok(t) => t,
-                Err(e) => return Err(e),
+                Err(e) => return Err(error(e, node)),
             };
 
             if node.children.len() >= 5 {
@@ -444,7 +451,9 @@ pub fn nonblock(mut node: &mut ASTNode,
                                current,
                                kinds,
                                &globals,
-                               &mut locals.clone()) {
+                               &mut locals.clone(),
+                               warnings,
+                               annotations) {
                     Ok(_) => (),
                     Err(e) => return Err(e),
                 }
@@ -461,7 +470,9 @@ pub fn nonblock(mut node: &mut ASTNode,
                      current,
                      kinds,
                      &globals,
-                     &mut locals.clone())
+                     &mut locals.clone(),
+                     warnings,
+                     annotations)
         }
         Some(ref l) if l == "ReturnStatement" => {
             if node.children.is_empty() {
@@ -473,7 +484,9 @@ pub fn nonblock(mut node: &mut ASTNode,
                            current,
                            kinds,
                            &globals,
-                           &mut locals.clone()) {
+                           &mut locals.clone(),
+                           warnings,
+                           annotations) {
                 Ok(_) => (),
                 Err(e) => return Err(e),
             }
@@ -484,17 +497,23 @@ pub fn nonblock(mut node: &mut ASTNode,
                                           modifiers,
                                           current,
                                           kinds,
-                                          &mut block_globals) {
+                                          &mut block_globals,
+                                          annotations) {
                 Ok(rt) => Ok(vec![(rt, true)]),
-                Err(e) => Err(e),
+                Err(e) => Err(error(e, &node.children[1])),
             }
         }
         _ => {
             let mut block_globals = globals.clone();
             block_globals.extend(locals.clone());
-            match resolve::expression::go(node, modifiers, current, kinds, &mut block_globals) {
+            match resolve::expression::go(node,
+                                          modifiers,
+                                          current,
+                                          kinds,
+                                          &mut block_globals,
+                                          annotations) {
                 Ok(t) => Ok(vec![(t.clone(), false)]),
-                Err(e) => Err(e),
+                Err(e) => Err(error(e, node)),
             }
         }
     }
@@ -1,4 +1,5 @@
 pub mod class;
 pub mod method;
 pub mod prefixes;
+pub mod reachability;
 pub mod variable;
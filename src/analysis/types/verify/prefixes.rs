@@ -1,5 +1,7 @@
 use analysis::environment::ClassOrInterfaceEnvironment;
 use analysis::types::lookup;
+use error::AnalysisCode;
+use error::AnalysisError;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -18,7 +20,7 @@ const PRIMITIVES: [TokenKind; 7] = [TokenKind::Boolean,
 pub fn canonical(canonical: &ASTNode,
                  current: &ClassOrInterfaceEnvironment,
                  kinds: &Vec<ClassOrInterfaceEnvironment>)
-                 -> Result<(), String> {
+                 -> Result<(), AnalysisError> {
     // TODO: This might be wrong. Seems to fix the issue caused by always
     // resolving types early (ie. class foo in foo.bar refering to self as "foo"
     // is pre-resolved to foo.bar.foo before this method is called)
@@ -38,8 +40,9 @@ pub fn canonical(canonical: &ASTNode,
     }
 
     if PRIMITIVES.contains(&canonical.children[0].token.kind) {
-        return Err(format!("strict prefix of {:?} resolves to primitive type",
-                           canonical));
+        let message = format!("strict prefix of {:?} resolves to primitive type",
+                              canonical);
+        return Err(AnalysisError::new(AnalysisCode::PrefixResolvesToType, message));
     }
 
     let mut prefix = Vec::new();
@@ -60,12 +63,18 @@ pub fn canonical(canonical: &ASTNode,
 
         // TODO: remove recursive references
         match lookup::class::lookup_step0_canonical(&name, current, kinds) {
-            Some(Ok(_)) => return Err(format!("strict prefix {} resolves to canonical type", name)),
+            Some(Ok(_)) => {
+                let message = format!("strict prefix {} resolves to canonical type", name);
+                return Err(AnalysisError::new(AnalysisCode::PrefixResolvesToType, message));
+            }
             _ => (),
         }
 
         match lookup::class::lookup_step3_enclosing_package(&name, current, kinds) {
-            Some(Ok(_)) => return Err(format!("strict prefix {} resolves to local type", name)),
+            Some(Ok(_)) => {
+                let message = format!("strict prefix {} resolves to local type", name);
+                return Err(AnalysisError::new(AnalysisCode::PrefixResolvesToType, message));
+            }
             _ => (),
         }
     }
@@ -76,10 +85,11 @@ pub fn canonical(canonical: &ASTNode,
 pub fn package(canonical: &ASTNode,
                current: &ClassOrInterfaceEnvironment,
                kinds: &Vec<ClassOrInterfaceEnvironment>)
-               -> Result<(), String> {
+               -> Result<(), AnalysisError> {
     if !canonical.children.is_empty() && PRIMITIVES.contains(&canonical.children[0].token.kind) {
-        return Err(format!("strict prefix of {:?} resolves to primitive type",
-                           canonical));
+        let message = format!("strict prefix of {:?} resolves to primitive type",
+                              canonical);
+        return Err(AnalysisError::new(AnalysisCode::PrefixResolvesToType, message));
     }
 
     let mut prefix = Vec::new();
@@ -100,7 +110,10 @@ pub fn package(canonical: &ASTNode,
 
         // TODO: this should recurse to self, not verify::prefixes::canonical
         match lookup::class::lookup_step0_canonical(&name, current, kinds) {
-            Some(Ok(_)) => return Err(format!("strict prefix {} resolves to canonical type", name)),
+            Some(Ok(_)) => {
+                let message = format!("strict prefix {} resolves to canonical type", name);
+                return Err(AnalysisError::new(AnalysisCode::PrefixResolvesToType, message));
+            }
             _ => (),
         }
     }
@@ -0,0 +1,129 @@
+use error::AnalysisCode;
+use error::AnalysisError;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+use scanner::Span;
+use scanner::TokenKind;
+use scanner::typed::Expression;
+use scanner::typed::Statement;
+use scanner::visit::Visitor;
+
+fn unreachable(span: Option<Span>) -> Diagnostic {
+    let reason = AnalysisError::new(AnalysisCode::UnreachableStatement,
+                                    "unreachable statement".to_owned());
+    Diagnostic::new(Phase::Types, ErrorMessage::TypeError(reason), span)
+}
+
+fn missing_return(span: Option<Span>) -> Diagnostic {
+    let reason = AnalysisError::new(AnalysisCode::MissingReturn,
+                                    "missing return statement".to_owned());
+    Diagnostic::new(Phase::Types, ErrorMessage::TypeError(reason), span)
+}
+
+// The value of a boolean constant expression. The body has already been
+// folded, so every constant expression is a literal.
+fn constant(condition: &Expression) -> Option<bool> {
+    match *condition {
+        Expression::Literal(ref token) if token.kind == TokenKind::True => Some(true),
+        Expression::Literal(ref token) if token.kind == TokenKind::False => Some(false),
+        _ => None,
+    }
+}
+
+// Follows whether each statement of a method body can complete normally (JLS
+// 14.20), starting from the reachable beginning of the body. Only statements
+// are visited, since expressions can not affect reachability. The first
+// unreachable statement found is an error, after which nothing is visited.
+struct Reachability {
+    normal: bool,
+    error: Option<Diagnostic>,
+}
+
+impl Reachability {
+    fn new() -> Reachability {
+        Reachability {
+            normal: true,
+            error: None,
+        }
+    }
+
+    // Whether a loop whose condition is `condition` can complete normally,
+    // given that its body is reachable unless the condition is the constant
+    // false.
+    fn loop_(&mut self, condition: Option<&Expression>, body: &Statement) {
+        // A missing condition is the constant true.
+        let value = match condition {
+            Some(condition) => constant(condition),
+            None => Some(true),
+        };
+
+        if value == Some(false) {
+            self.error = Some(unreachable(body.span()));
+            return;
+        }
+
+        self.visit_statement(body);
+        self.normal = value != Some(true);
+    }
+}
+
+impl Visitor for Reachability {
+    fn visit_statement(&mut self, node: &Statement) {
+        if self.error.is_some() {
+            return;
+        }
+
+        match *node {
+            Statement::Block { ref statements, .. } => {
+                for child in statements {
+                    if !self.normal {
+                        self.error = Some(unreachable(child.span()));
+                        return;
+                    }
+                    self.visit_statement(child);
+                }
+            }
+            // An if statement can always complete normally, even when its
+            // condition is a constant, so that it can be used for conditional
+            // compilation.
+            Statement::If { ref then, otherwise: None, .. } => {
+                self.visit_statement(then);
+                self.normal = true;
+            }
+            Statement::If { ref then, otherwise: Some(ref otherwise), .. } => {
+                self.visit_statement(then);
+                let then = self.normal;
+                self.normal = true;
+                self.visit_statement(otherwise);
+                self.normal = then || self.normal;
+            }
+            Statement::While { ref condition, ref body, .. } => self.loop_(Some(condition), body),
+            Statement::For { ref condition, ref body, .. } => self.loop_(condition.as_ref(), body),
+            Statement::Return { .. } => self.normal = false,
+            Statement::LocalVariableDeclaration { .. } |
+            Statement::Expression(_) |
+            Statement::Empty(_) => self.normal = true,
+        }
+    }
+}
+
+/// Checks that every statement in a method or constructor body is reachable
+/// (JLS 14.20), treating `while` and `for` conditions which are constant
+/// expressions as always or never taken. The body of a method which returns a
+/// value must not be able to complete normally; if it can, the error points
+/// at the closing brace of the method.
+pub fn reachable(body: &Statement, void: bool) -> Result<(), Diagnostic> {
+    let end = match *body {
+        Statement::Block { end, .. } => end,
+        _ => body.span(),
+    };
+
+    let mut reachability = Reachability::new();
+    reachability.visit_statement(body);
+    match reachability.error {
+        Some(e) => Err(e),
+        None if reachability.normal && !void => Err(missing_return(end)),
+        None => Ok(()),
+    }
+}
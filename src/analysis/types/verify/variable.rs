@@ -1,39 +1,362 @@
-use analysis::environment::ClassOrInterfaceEnvironment;
-use analysis::environment::VariableEnvironment;
+use std::collections::BTreeMap;
+
+use error::AnalysisCode;
+use error::AnalysisError;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
 use scanner::ASTNode;
+use scanner::Span;
+use scanner::Token;
 use scanner::TokenKind;
+use scanner::typed::Expression;
+use scanner::typed::Statement;
+
+// The reason a variable may be unassigned at some point in a method body: a
+// description of the path taken to get there and the code which chose it.
+#[derive(Clone)]
+struct Path {
+    description: String,
+    span: Option<Span>,
+}
+
+impl Path {
+    fn new(description: &str, span: Option<Span>) -> Path {
+        Path {
+            description: description.to_owned(),
+            span: span,
+        }
+    }
+}
+
+// The local variables which are not definitely assigned at some point in a
+// method body, each with the span of its declaration and the path which left
+// it unassigned. Code which can not complete normally, eg. the code after a
+// return statement, vacuously has every variable assigned.
+#[derive(Clone)]
+struct Assigned {
+    vacuous: bool,
+    unassigned: BTreeMap<String, (Option<Span>, Path)>,
+}
+
+impl Assigned {
+    fn new() -> Assigned {
+        Assigned {
+            vacuous: false,
+            unassigned: BTreeMap::new(),
+        }
+    }
+
+    fn vacuous() -> Assigned {
+        Assigned {
+            vacuous: true,
+            unassigned: BTreeMap::new(),
+        }
+    }
+
+    fn declare(&mut self, name: &Token, description: &str) {
+        if !self.vacuous {
+            let path = Path::new(description, name.span);
+            let name = name.lexeme.clone().unwrap_or("".to_owned());
+            self.unassigned.insert(name, (path.span, path));
+        }
+    }
+
+    fn assign(&mut self, name: &str) {
+        self.unassigned.remove(name);
+    }
+
+    // Forgets the variables declared since `outer`, once they go out of
+    // scope.
+    fn close_scope(&mut self, outer: &Assigned) {
+        self.unassigned.retain(|name, _| outer.unassigned.contains_key(name));
+    }
+
+    fn check(&self, name: &str, span: Option<Span>) -> Result<(), Diagnostic> {
+        if self.vacuous {
+            return Ok(());
+        }
 
-pub fn initialized(node: &ASTNode,
-                   current: &ClassOrInterfaceEnvironment,
-                   globals: &Vec<VariableEnvironment>)
-                   -> Result<(), String> {
-    if node.token.kind == TokenKind::Identifier ||
-       (node.token.kind == TokenKind::NonTerminal &&
-        node.clone().token.lexeme.unwrap_or("".to_owned()) == "Name") {
-        for var in globals {
-            if &var.name != node {
-                continue;
+        match self.unassigned.get(name) {
+            Some(&(declaration, ref path)) => {
+                let message = format!("variable {} might not have been initialized", name);
+                let reason = AnalysisError::new(AnalysisCode::Unassigned, message);
+                let mut error = Diagnostic::new(Phase::Types,
+                                                ErrorMessage::TypeError(reason),
+                                                span);
+                if path.span != declaration {
+                    error = error.with_label(declaration, "declared here".to_owned());
+                }
+                Err(error.with_label(path.span, path.description.clone()))
+            }
+            None => Ok(()),
+        }
+    }
+
+    // Merges the states at the end of two paths which join. A variable is
+    // only definitely assigned if it is assigned along both paths; if only
+    // one of them leaves it unassigned, that path is blamed.
+    fn join(lhs: Assigned, lhs_path: Path, rhs: Assigned, rhs_path: Path) -> Assigned {
+        if lhs.vacuous {
+            return rhs;
+        }
+        if rhs.vacuous {
+            return lhs;
+        }
+
+        let mut joined = Assigned::new();
+        for (name, &(declaration, ref path)) in &lhs.unassigned {
+            let path = match rhs.unassigned.contains_key(name) {
+                true => path.clone(),
+                false => lhs_path.clone(),
+            };
+            joined.unassigned.insert(name.clone(), (declaration, path));
+        }
+        for (name, &(declaration, _)) in &rhs.unassigned {
+            if !lhs.unassigned.contains_key(name) {
+                joined.unassigned.insert(name.clone(), (declaration, rhs_path.clone()));
             }
+        }
+        joined
+    }
+
+    // Blames `path` for every variable which would have been assigned by the
+    // body of a loop, had it run.
+    fn skipped(mut self, body: &Assigned, path: Path) -> Assigned {
+        for (name, entry) in &mut self.unassigned {
+            if body.vacuous || !body.unassigned.contains_key(name) {
+                entry.1 = path.clone();
+            }
+        }
+        self
+    }
+}
+
+// Checks the variable a Name starts with, if any.
+fn name(node: &ASTNode, state: &Assigned) -> Result<(), Diagnostic> {
+    match node.children.first() {
+        Some(first) if first.token.kind == TokenKind::Identifier => {
+            state.check(&first.source_text(), node.span())
+        }
+        _ => Ok(()),
+    }
+}
+
+// The value of a boolean constant expression. The body has already been
+// folded, so every constant expression is a literal.
+fn constant(node: &Expression) -> Option<bool> {
+    match *node {
+        Expression::Literal(ref token) if token.kind == TokenKind::True => Some(true),
+        Expression::Literal(ref token) if token.kind == TokenKind::False => Some(false),
+        _ => None,
+    }
+}
+
+// The states after a boolean expression when it is true and when it is false.
+fn condition(node: &Expression, state: Assigned) -> Result<(Assigned, Assigned), Diagnostic> {
+    match constant(node) {
+        Some(true) => return Ok((state, Assigned::vacuous())),
+        Some(false) => return Ok((Assigned::vacuous(), state)),
+        None => (),
+    }
 
-            return match var.initialized {
-                       false => {
-                           Err(format!("using un-initialized variable {} in class {}",
-                                       var.name,
-                                       current.name))
-                       }
-                       true => Ok(()),
-                   };
+    match *node {
+        Expression::Binary { ref operator, ref lhs, ref rhs, .. } if operator.kind ==
+                                                                     TokenKind::And => {
+            let (lhs_true, lhs_false) = try!(condition(lhs, state));
+            let (rhs_true, rhs_false) = try!(condition(rhs, lhs_true));
+            let when_false = Assigned::join(lhs_false,
+                                            Path::new("if this is false", lhs.span()),
+                                            rhs_false,
+                                            Path::new("if this is false", rhs.span()));
+            Ok((rhs_true, when_false))
         }
+        Expression::Binary { ref operator, ref lhs, ref rhs, .. } if operator.kind ==
+                                                                     TokenKind::Or => {
+            let (lhs_true, lhs_false) = try!(condition(lhs, state));
+            let (rhs_true, rhs_false) = try!(condition(rhs, lhs_false));
+            let when_true = Assigned::join(lhs_true,
+                                           Path::new("if this is true", lhs.span()),
+                                           rhs_true,
+                                           Path::new("if this is true", rhs.span()));
+            Ok((when_true, rhs_false))
+        }
+        Expression::Unary { ref operand, .. } => {
+            let (when_true, when_false) = try!(condition(operand, state));
+            Ok((when_false, when_true))
+        }
+        _ => {
+            let state = try!(expression(node, state));
+            Ok((state.clone(), state))
+        }
+    }
+}
 
-        return Ok(());
+fn expressions(nodes: &[Expression], mut state: Assigned) -> Result<Assigned, Diagnostic> {
+    for node in nodes {
+        state = try!(expression(node, state));
+    }
+    Ok(state)
+}
+
+fn expression(node: &Expression, mut state: Assigned) -> Result<Assigned, Diagnostic> {
+    match *node {
+        Expression::Literal(_) |
+        Expression::This(_) => Ok(state),
+        Expression::Name(ref n) => {
+            try!(name(n, &state));
+            Ok(state)
+        }
+        Expression::Binary { ref operator, .. } if operator.kind == TokenKind::And ||
+                                                   operator.kind == TokenKind::Or => {
+            let (when_true, when_false) = try!(condition(node, state));
+            Ok(Assigned::join(when_true,
+                              Path::new("if this is true", node.span()),
+                              when_false,
+                              Path::new("if this is false", node.span())))
+        }
+        Expression::Unary { .. } => {
+            let (when_true, when_false) = try!(condition(node, state));
+            Ok(Assigned::join(when_true,
+                              Path::new("if this is true", node.span()),
+                              when_false,
+                              Path::new("if this is false", node.span())))
+        }
+        Expression::Binary { ref lhs, ref rhs, .. } => {
+            state = try!(expression(lhs, state));
+            expression(rhs, state)
+        }
+        Expression::InstanceOf { ref operand, .. } |
+        Expression::Cast { ref operand, .. } => expression(operand, state),
+        Expression::Assignment { ref lhs, ref rhs, .. } => {
+            let simple = match **lhs {
+                Expression::Name(ref n) if n.children.len() == 1 => Some(n.source_text()),
+                _ => None,
+            };
+            if simple.is_none() {
+                state = try!(expression(lhs, state));
+            }
+            state = try!(expression(rhs, state));
+            if let Some(ref name) = simple {
+                state.assign(name);
+            }
+            Ok(state)
+        }
+        Expression::MethodInvocation { ref object, ref method, ref arguments, .. } => {
+            // A method called by name only refers to a variable if it is
+            // qualified, eg. `x.foo()`.
+            match *object {
+                Some(ref object) => state = try!(expression(object, state)),
+                None if method.children.len() > 1 => try!(name(method, &state)),
+                None => (),
+            }
+            expressions(arguments, state)
+        }
+        Expression::FieldAccess { ref object, .. } => expression(object, state),
+        Expression::ArrayAccess { ref array, ref index, .. } => {
+            state = try!(expression(array, state));
+            expression(index, state)
+        }
+        Expression::ArrayCreation { ref size, .. } => {
+            match *size {
+                Some(ref size) => expression(size, state),
+                None => Ok(state),
+            }
+        }
+        Expression::ClassInstanceCreation { ref arguments, .. } => expressions(arguments, state),
     }
+}
 
-    for child in &node.children {
-        match initialized(&child, current, globals) {
-            Ok(_) => (),
-            Err(e) => return Err(e),
+fn statement(node: &Statement, mut state: Assigned) -> Result<Assigned, Diagnostic> {
+    match *node {
+        Statement::Block { ref statements, .. } => {
+            let outer = state.clone();
+            for child in statements {
+                state = try!(statement(child, state));
+            }
+            state.close_scope(&outer);
+            Ok(state)
+        }
+        // A variable is in scope, but unassigned, within its own initializer.
+        Statement::LocalVariableDeclaration { ref name,
+                                              initializer: Some(ref initializer),
+                                              .. } => {
+            state.declare(name, "not assigned until its initializer completes");
+            state = try!(expression(initializer, state));
+            state.assign(&name.lexeme.clone().unwrap_or("".to_owned()));
+            Ok(state)
+        }
+        Statement::LocalVariableDeclaration { ref name, initializer: None, .. } => {
+            state.declare(name, "declared without an initializer");
+            Ok(state)
+        }
+        Statement::If { ref condition, ref then, ref otherwise, .. } => {
+            let cond = condition.span();
+            let (when_true, when_false) = try!(self::condition(condition, state));
+            let then = try!(statement(then, when_true));
+            let otherwise = match *otherwise {
+                Some(ref otherwise) => try!(statement(otherwise, when_false)),
+                None => when_false,
+            };
+            Ok(Assigned::join(then,
+                              Path::new("if this condition is true", cond),
+                              otherwise,
+                              Path::new("if this condition is false", cond)))
+        }
+        Statement::While { ref condition, ref body, .. } => {
+            let cond = condition.span();
+            let (when_true, when_false) = try!(self::condition(condition, state));
+            let body = try!(statement(body, when_true));
+            Ok(when_false.skipped(&body,
+                                  Path::new("if this condition is false before the loop runs",
+                                            cond)))
         }
+        Statement::For { ref init, ref condition, ref update, ref body, .. } => {
+            let outer = state.clone();
+            if let Some(ref init) = *init {
+                state = try!(statement(init, state));
+            }
+
+            let (when_true, when_false) = match *condition {
+                Some(ref condition) => try!(self::condition(condition, state)),
+                None => (state, Assigned::vacuous()),
+            };
+
+            let body = try!(statement(body, when_true));
+            if let Some(ref update) = *update {
+                try!(expression(update, body.clone()));
+            }
+
+            let mut state = match *condition {
+                Some(ref condition) => {
+                    let path = Path::new("if this condition is false before the loop runs",
+                                         condition.span());
+                    when_false.skipped(&body, path)
+                }
+                None => when_false,
+            };
+            state.close_scope(&outer);
+            Ok(state)
+        }
+        Statement::Return { ref value, .. } => {
+            if let Some(ref value) = *value {
+                try!(expression(value, state));
+            }
+            Ok(Assigned::vacuous())
+        }
+        Statement::Expression(ref expr) => expression(expr, state),
+        Statement::Empty(_) => Ok(state),
     }
+}
 
-    Ok(())
+/// Checks that every local variable in a method or constructor body is
+/// definitely assigned before it is used (JLS 16). Every path through `if`,
+/// `while` and `for` statements and the short-circuit `&&` and `||` operators
+/// is followed, taking boolean constant conditions into account. Errors point
+/// at the path which left the variable unassigned.
+pub fn definitely_assigned(body: &Statement) -> Result<(), Diagnostic> {
+    match statement(body, Assigned::new()) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e),
+    }
 }
@@ -0,0 +1,202 @@
+//! The compiler session for juicyj. Drives a set of sources through every
+//! stage of the compiler, without printing anything or exiting the process.
+use analysis;
+use analysis::Environment;
+use error::Diagnostic;
+use error::Lint;
+use error::Warning;
+use generator;
+use scanner;
+use scanner::AST;
+use scanner::SourceMap;
+use source;
+use source::SourceProvider;
+
+/// A single compilation. Sources are added up front, then each step runs any
+/// earlier steps which have not yet been run.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut compiler = juicyj::Compiler::new();
+/// compiler.add_source("Foo.java", "public class Foo { public Foo() {} }");
+/// match compiler.parse() {
+///     Ok(_) => println!("parsed {} files", compiler.asts().len()),
+///     Err(errors) => println!("found {} errors", errors.len()),
+/// }
+/// ```
+pub struct Compiler {
+    sources: SourceMap,
+    asts: Vec<AST>,
+    parsed: bool,
+    env: Option<Environment>,
+    warnings: Vec<Warning>,
+    allowed: Vec<Lint>,
+}
+
+impl Compiler {
+    /// Constructs a Compiler with no sources.
+    pub fn new() -> Compiler {
+        Compiler {
+            sources: SourceMap::new(),
+            asts: Vec::new(),
+            parsed: false,
+            env: None,
+            warnings: Vec::new(),
+            allowed: Vec::new(),
+        }
+    }
+
+    /// Adds a source file, given its name and contents.
+    pub fn add_source(&mut self, name: &str, text: &str) {
+        self.sources.add(name, text);
+        self.asts = Vec::new();
+        self.parsed = false;
+        self.env = None;
+        self.warnings = Vec::new();
+    }
+
+    /// Stops reporting warnings of a given kind.
+    pub fn allow(&mut self, lint: Lint) {
+        if !self.allowed.contains(&lint) {
+            self.allowed.push(lint);
+        }
+    }
+
+    /// Reports warnings of a given kind, undoing any earlier `allow`. Every
+    /// kind of warning is reported by default.
+    pub fn warn(&mut self, lint: Lint) {
+        self.allowed.retain(|l| l != &lint);
+    }
+
+    /// Reads a source file from disk and adds it. A path of `-` reads the
+    /// source from standard input instead.
+    pub fn add_file(&mut self, path: &str) -> Result<(), Diagnostic> {
+        let (name, text) = if path == "-" {
+            (source::STDIN, source::read_stdin())
+        } else {
+            (path, scanner::read_src_file(path))
+        };
+
+        match text {
+            Ok(text) => {
+                self.add_source(name, &text);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Adds every source supplied by a provider, such as a directory on disk
+    /// or an in-memory file system.
+    pub fn add_sources<P: SourceProvider>(&mut self, provider: &P) -> Result<(), Diagnostic> {
+        match provider.sources() {
+            Ok(sources) => {
+                for &(ref name, ref text) in &sources {
+                    self.add_source(name, text);
+                }
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Runs every source through the scanning stack (lexer, parser, weeder,
+    /// AST), reporting the errors from all of them.
+    pub fn parse(&mut self) -> Result<(), Vec<Diagnostic>> {
+        if self.parsed {
+            return Ok(());
+        }
+
+        let mut asts = Vec::new();
+        let mut errors = Vec::new();
+        for file in 0..self.sources.len() {
+            match scanner::scan(&self.sources, file) {
+                Ok(ast) => asts.push(ast),
+                Err(e) => errors.extend(e),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.asts = asts;
+        self.parsed = true;
+        Ok(())
+    }
+
+    /// Builds and type checks the environment for every source, parsing them
+    /// first if need be.
+    pub fn analyze(&mut self) -> Result<(), Vec<Diagnostic>> {
+        if self.env.is_some() {
+            return Ok(());
+        }
+
+        match self.parse() {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+
+        self.warnings = Vec::new();
+        match analysis::analyze(&self.asts, &mut self.warnings) {
+            Ok(env) => {
+                self.env = Some(env);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Generates assembly for every class, analyzing the sources first if need
+    /// be. Returns a list of (filename, assembly) pairs.
+    pub fn generate(&mut self) -> Result<Vec<(String, String)>, Vec<Diagnostic>> {
+        match self.analyze() {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+
+        match self.env {
+            Some(ref env) => generator::generate(env),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Every source added so far. Spans in the ASTs, environment and errors of
+    /// this compilation are resolved through it.
+    pub fn sources(&self) -> &SourceMap {
+        &self.sources
+    }
+
+    /// The ASTs built by `parse`, or an empty list if it has not succeeded.
+    pub fn asts(&self) -> &Vec<AST> {
+        &self.asts
+    }
+
+    /// The warnings found by `analyze` whose kinds have not been allowed.
+    /// Warnings are found whether or not analysis succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use juicyj::error::Lint;
+    ///
+    /// let mut compiler = juicyj::Compiler::new();
+    /// compiler.add_source("Foo.java",
+    ///                     "public class Foo { public Foo() { int x = 1; } }");
+    /// compiler.add_sources(&juicyj::source::stdlib()).unwrap();
+    /// compiler.analyze().unwrap();
+    /// assert_eq!(compiler.warnings().len(), 1);
+    ///
+    /// compiler.allow(Lint::UnusedVariable);
+    /// assert!(compiler.warnings().is_empty());
+    /// ```
+    pub fn warnings(&self) -> Vec<&Warning> {
+        self.warnings.iter().filter(|w| !self.allowed.contains(&w.lint)).collect()
+    }
+
+    /// The environment built by `analyze`, if it has succeeded.
+    pub fn environment(&self) -> Option<&Environment> {
+        self.env.as_ref()
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+use error::codes::AnalysisCode;
+
+/// An error struct generated while building environments, checking the class
+/// hierarchy or type checking. Carries the kind of error from the check which
+/// found it, so that its code does not depend on the wording of its message.
+#[derive(Clone,Debug,PartialEq)]
+pub struct AnalysisError {
+    /// kind of error, if it is a known one
+    pub code: Option<AnalysisCode>,
+    /// description of the error
+    pub message: String,
+    /// additional context, eg. suggestions, carried into the Diagnostic
+    pub notes: Vec<String>,
+}
+
+impl AnalysisError {
+    /// Constructs an AnalysisError of a known kind.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use juicyj::error::AnalysisCode;
+    /// use juicyj::error::AnalysisError;
+    ///
+    /// let error = AnalysisError::new(AnalysisCode::Bitwise,
+    ///                                "bitwise operations are not allowed".to_owned());
+    /// assert_eq!(error.code.unwrap().code(), "J0315");
+    /// ```
+    pub fn new(code: AnalysisCode, message: String) -> AnalysisError {
+        AnalysisError {
+            code: Some(code),
+            message: message,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Constructs an AnalysisError which is not one of the known kinds, eg. an
+    /// internal inconsistency. It is reported with the generic code of the
+    /// stage which reports it.
+    pub fn other(message: String) -> AnalysisError {
+        AnalysisError {
+            code: None,
+            message: message,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Adds a note to an AnalysisError.
+    pub fn with_note(mut self, note: String) -> AnalysisError {
+        self.notes.push(note);
+        self
+    }
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
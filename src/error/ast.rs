@@ -1,6 +1,7 @@
 use std::fmt;
 
 use error::message::ErrorMessage;
+use scanner::Span;
 
 /// An error struct generated by AST conversion.
 pub struct ASTError {
@@ -8,6 +9,8 @@ pub struct ASTError {
     pub message: ErrorMessage,
     /// `format!`'ed representation of relevant `ParseNode`
     pub node: String,
+    /// location of the offending node, added with self.with_span()
+    pub span: Option<Span>,
 }
 
 impl ASTError {
@@ -18,8 +21,15 @@ impl ASTError {
         ASTError {
             message: message,
             node: format!("{}", node),
+            span: None,
         }
     }
+
+    /// Add the location of the offending node to an ASTError.
+    pub fn with_span(mut self, span: Option<Span>) -> ASTError {
+        self.span = span;
+        self
+    }
 }
 
 impl fmt::Display for ASTError {
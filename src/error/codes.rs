@@ -0,0 +1,933 @@
+//! The catalog of error codes. Every ErrorMessage has a code of the form
+//! `J00xx`; the catch-all analysis messages (EnvironmentError, HierarchyError
+//! and TypeError) are further split into `J01xx` for declarations, `J02xx` for
+//! the class hierarchy and `J03xx` for type checking.
+//!
+//! Codes are stable: once published, a code is never renumbered or reused.
+//! New codes are appended to the end of their range.
+
+/// A longer explanation of an error, as printed by `juicyj --explain CODE`.
+pub struct Explanation {
+    /// the stable error code, eg. `J0012`
+    pub code: &'static str,
+    /// one-line summary of the error
+    pub title: &'static str,
+    /// the Joos or JLS rule which is being enforced
+    pub rule: &'static str,
+    /// a few sentences describing when the error occurs
+    pub text: &'static str,
+    /// a small program which causes the error, if there is one
+    pub failing: &'static str,
+    /// the same program, fixed
+    pub passing: &'static str,
+}
+
+/// Finds the explanation for an error code.
+///
+/// # Examples
+///
+/// ```rust
+/// let explanation = juicyj::error::explain("J0012").unwrap();
+/// assert_eq!(explanation.title, "final field has no initializer");
+/// ```
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    CATALOG.iter().find(|e| e.code == code)
+}
+
+/// The known kinds of analysis error, each with its own stable code. Every
+/// error reported by the environment builder, hierarchy checker and type
+/// checker is constructed with one of these, unless it is not a known error.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum AnalysisCode {
+    /// Two classes or interfaces share a canonical name.
+    DuplicateType,
+    /// Two constructors of a class share a signature.
+    DuplicateConstructor,
+    /// Two fields of a class share a name.
+    DuplicateField,
+    /// Two methods of a class or interface share a signature.
+    DuplicateMethod,
+    /// An import names a package or type which does not exist.
+    MissingImport,
+    /// A single-type-import clashes with a declared type.
+    ImportClash,
+    /// Two parameters of a method or constructor share a name.
+    DuplicateParameter,
+    /// A constructor is not named after its class.
+    ConstructorName,
+    /// A strict prefix of a qualified name resolves to a type.
+    PrefixResolvesToType,
+    /// A type name could not be resolved.
+    UnresolvedType,
+    /// A name is neither a variable, a field nor a type.
+    UnresolvedName,
+    /// A class or interface is its own supertype.
+    CyclicHierarchy,
+    /// A class extends a final class.
+    ExtendsFinal,
+    /// A class extends an interface.
+    ClassExtendsInterface,
+    /// A class implements a class.
+    ClassImplementsClass,
+    /// An interface extends a class.
+    InterfaceExtendsClass,
+    /// An interface is named twice in an implements or extends clause.
+    RepeatedInterface,
+    /// A method overrides a final method.
+    OverrideFinal,
+    /// A method overrides one with a different return type.
+    OverrideReturnType,
+    /// A static method overrides an instance method, or vice versa.
+    OverrideStatic,
+    /// A protected method overrides a public one.
+    WeakerAccess,
+    /// A non-abstract class has an abstract method.
+    AbstractInConcrete,
+    /// A value is assigned to a variable of an incompatible type.
+    Assignment,
+    /// A condition is not a boolean.
+    NonBooleanCondition,
+    /// `this` is used, explicitly or implicitly, in a static context.
+    ThisInStatic,
+    /// An abstract class or an interface is instantiated.
+    InstantiateAbstract,
+    /// A local variable shadows another local or a parameter.
+    ShadowedVariable,
+    /// A field access does not name a field of its class.
+    UnresolvedField,
+    /// A protected method is invoked from outside its package and subclasses.
+    InaccessibleMethod,
+    /// No method or constructor accepts the given arguments.
+    NoMatchingMethod,
+    /// Several methods accept the given arguments equally well.
+    AmbiguousMethod,
+    /// A returned value is not assignable to the return type.
+    ReturnType,
+    /// instanceof is applied to a primitive type.
+    PrimitiveInstanceof,
+    /// A bitwise operator is applied to integers.
+    Bitwise,
+    /// A cast between unrelated types.
+    InvalidCast,
+    /// A method invocation does not name a method of its class.
+    UnresolvedMethod,
+    /// A local variable is read before it is definitely assigned.
+    Unassigned,
+    /// A statement can never be reached.
+    UnreachableStatement,
+    /// A non-void method can complete normally.
+    MissingReturn,
+}
+
+impl AnalysisCode {
+    /// The stable code of this kind of error, eg. `J0301`.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            AnalysisCode::DuplicateType => "J0101",
+            AnalysisCode::DuplicateConstructor => "J0102",
+            AnalysisCode::DuplicateField => "J0103",
+            AnalysisCode::DuplicateMethod => "J0104",
+            AnalysisCode::MissingImport => "J0105",
+            AnalysisCode::ImportClash => "J0106",
+            AnalysisCode::DuplicateParameter => "J0107",
+            AnalysisCode::ConstructorName => "J0108",
+            AnalysisCode::PrefixResolvesToType => "J0109",
+            AnalysisCode::UnresolvedType => "J0110",
+            AnalysisCode::UnresolvedName => "J0111",
+            AnalysisCode::CyclicHierarchy => "J0201",
+            AnalysisCode::ExtendsFinal => "J0202",
+            AnalysisCode::ClassExtendsInterface => "J0203",
+            AnalysisCode::ClassImplementsClass => "J0204",
+            AnalysisCode::InterfaceExtendsClass => "J0205",
+            AnalysisCode::RepeatedInterface => "J0206",
+            AnalysisCode::OverrideFinal => "J0207",
+            AnalysisCode::OverrideReturnType => "J0208",
+            AnalysisCode::OverrideStatic => "J0209",
+            AnalysisCode::WeakerAccess => "J0210",
+            AnalysisCode::AbstractInConcrete => "J0211",
+            AnalysisCode::Assignment => "J0301",
+            AnalysisCode::NonBooleanCondition => "J0302",
+            AnalysisCode::ThisInStatic => "J0303",
+            AnalysisCode::InstantiateAbstract => "J0304",
+            AnalysisCode::ShadowedVariable => "J0305",
+            AnalysisCode::UnresolvedField => "J0306",
+            AnalysisCode::InaccessibleMethod => "J0307",
+            AnalysisCode::NoMatchingMethod => "J0308",
+            AnalysisCode::AmbiguousMethod => "J0309",
+            AnalysisCode::ReturnType => "J0312",
+            AnalysisCode::PrimitiveInstanceof => "J0314",
+            AnalysisCode::Bitwise => "J0315",
+            AnalysisCode::InvalidCast => "J0316",
+            AnalysisCode::UnresolvedMethod => "J0317",
+            AnalysisCode::Unassigned => "J0318",
+            AnalysisCode::UnreachableStatement => "J0319",
+            AnalysisCode::MissingReturn => "J0320",
+        }
+    }
+}
+
+// Every kind of analysis error, so that each of them can be checked against
+// the catalog.
+pub const ANALYSIS: &'static [AnalysisCode] = &[AnalysisCode::DuplicateType,
+                                              AnalysisCode::DuplicateConstructor,
+                                              AnalysisCode::DuplicateField,
+                                              AnalysisCode::DuplicateMethod,
+                                              AnalysisCode::MissingImport,
+                                              AnalysisCode::ImportClash,
+                                              AnalysisCode::DuplicateParameter,
+                                              AnalysisCode::ConstructorName,
+                                              AnalysisCode::PrefixResolvesToType,
+                                              AnalysisCode::UnresolvedType,
+                                              AnalysisCode::UnresolvedName,
+                                              AnalysisCode::CyclicHierarchy,
+                                              AnalysisCode::ExtendsFinal,
+                                              AnalysisCode::ClassExtendsInterface,
+                                              AnalysisCode::ClassImplementsClass,
+                                              AnalysisCode::InterfaceExtendsClass,
+                                              AnalysisCode::RepeatedInterface,
+                                              AnalysisCode::OverrideFinal,
+                                              AnalysisCode::OverrideReturnType,
+                                              AnalysisCode::OverrideStatic,
+                                              AnalysisCode::WeakerAccess,
+                                              AnalysisCode::AbstractInConcrete,
+                                              AnalysisCode::Assignment,
+                                              AnalysisCode::NonBooleanCondition,
+                                              AnalysisCode::ThisInStatic,
+                                              AnalysisCode::InstantiateAbstract,
+                                              AnalysisCode::ShadowedVariable,
+                                              AnalysisCode::UnresolvedField,
+                                              AnalysisCode::InaccessibleMethod,
+                                              AnalysisCode::NoMatchingMethod,
+                                              AnalysisCode::AmbiguousMethod,
+                                              AnalysisCode::ReturnType,
+                                              AnalysisCode::PrimitiveInstanceof,
+                                              AnalysisCode::Bitwise,
+                                              AnalysisCode::InvalidCast,
+                                              AnalysisCode::UnresolvedMethod,
+                                              AnalysisCode::Unassigned,
+                                              AnalysisCode::UnreachableStatement,
+                                              AnalysisCode::MissingReturn];
+
+pub const CATALOG: &'static [Explanation] = &[
+    Explanation {
+        code: "J0001",
+        title: "abstract method has body",
+        rule: "JLS 8.4.3.1",
+        text: "An abstract method only declares a signature; its implementation is left to \
+               subclasses. It must end in a semicolon rather than a block.",
+        failing: "public abstract class Foo {\n    public Foo() {}\n    public abstract int \
+                  bar() { return 0; }\n}",
+        passing: "public abstract class Foo {\n    public Foo() {}\n    public abstract int \
+                  bar();\n}",
+    },
+    Explanation {
+        code: "J0002",
+        title: "char contains newline",
+        rule: "JLS 3.10.4",
+        text: "A character literal may not contain a line terminator. Use the escape \
+               sequence '\\n' instead.",
+        failing: "char c = '\n';",
+        passing: "char c = '\\n';",
+    },
+    Explanation {
+        code: "J0003",
+        title: "too many characters in char",
+        rule: "JLS 3.10.4",
+        text: "A character literal contains exactly one character or escape sequence. \
+               Several characters make up a string, which uses double quotes.",
+        failing: "char c = 'ab';",
+        passing: "String s = \"ab\";",
+    },
+    Explanation {
+        code: "J0004",
+        title: "too many characters in char (maybe malformed octal?)",
+        rule: "JLS 3.10.6",
+        text: "A character literal contains exactly one character or escape sequence. Octal \
+               escapes are at most three digits and at most \\377, so anything longer is \
+               more than one character.",
+        failing: "char c = '\\3777';",
+        passing: "char c = '\\377';",
+    },
+    Explanation {
+        code: "J0005",
+        title: "class is badly named",
+        rule: "Joos 1W",
+        text: "Every class must be declared in a file with the same name as the class, \
+               followed by `.java`.",
+        failing: "// in Bar.java\npublic class Foo {\n    public Foo() {}\n}",
+        passing: "// in Foo.java\npublic class Foo {\n    public Foo() {}\n}",
+    },
+    Explanation {
+        code: "J0006",
+        title: "code could not be generated",
+        rule: "",
+        text: "The program was accepted by every check, but the code generator could not \
+               translate it. This is a limitation of the compiler rather than an error in \
+               the program; please report it.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0007",
+        title: "concrete method has no body",
+        rule: "JLS 8.4.5",
+        text: "A method which is neither abstract nor native must have a body.",
+        failing: "public class Foo {\n    public Foo() {}\n    public int bar();\n}",
+        passing: "public class Foo {\n    public Foo() {}\n    public int bar() { return 0; \
+                  }\n}",
+    },
+    Explanation {
+        code: "J0008",
+        title: "could not read file",
+        rule: "",
+        text: "A source file given on the command line or found in the standard library \
+               does not exist or could not be read.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0009",
+        title: "could not entirely reduce stack",
+        rule: "",
+        text: "The parser finished with input left on its stack. This indicates a bug in the \
+               parse table; please report it.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0010",
+        title: "environment could not be built",
+        rule: "JLS 6, 7",
+        text: "The declarations in a class or interface could not be collected into an \
+               environment. The message describes which declaration was at fault.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0011",
+        title: "final method is abstract",
+        rule: "JLS 8.4.3.1",
+        text: "An abstract method must be overridden, and a final method can not be, so a \
+               method can not be both.",
+        failing: "public abstract class Foo {\n    public Foo() {}\n    public abstract final \
+                  int bar();\n}",
+        passing: "public abstract class Foo {\n    public Foo() {}\n    public abstract int \
+                  bar();\n}",
+    },
+    Explanation {
+        code: "J0012",
+        title: "final field has no initializer",
+        rule: "JLS 8.3.1.2",
+        text: "A final field can never be assigned after it is declared. Joos has no blank \
+               finals, so every final field must be given its value where it is declared.",
+        failing: "public class Foo {\n    public final int x;\n    public Foo() {}\n}",
+        passing: "public class Foo {\n    public final int x = 3;\n    public Foo() {}\n}",
+    },
+    Explanation {
+        code: "J0013",
+        title: "class hierarchy is invalid",
+        rule: "JLS 8.1, 9.1",
+        text: "The classes and interfaces in the program do not form a valid hierarchy. The \
+               message describes which rule was broken.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0014",
+        title: "single-type-import declarations clash",
+        rule: "JLS 7.5.1",
+        text: "Two single-type-import declarations may not import types with the same simple \
+               name, since the name would be ambiguous.",
+        failing: "import foo.List;\nimport bar.List;",
+        passing: "import foo.List;\nimport bar.*;",
+    },
+    Explanation {
+        code: "J0015",
+        title: "interface is badly named",
+        rule: "Joos 1W",
+        text: "Every interface must be declared in a file with the same name as the \
+               interface, followed by `.java`.",
+        failing: "// in Bar.java\npublic interface Foo {}",
+        passing: "// in Foo.java\npublic interface Foo {}",
+    },
+    Explanation {
+        code: "J0016",
+        title: "integer out of bounds",
+        rule: "JLS 3.10.1",
+        text: "Integer literals must fit in 32 bits: from -2147483648 to 2147483647.",
+        failing: "int x = 2147483648;",
+        passing: "int x = 2147483647;",
+    },
+    Explanation {
+        code: "J0017",
+        title: "invalid cast type",
+        rule: "JLS 15.16",
+        text: "A cast must be to a type, ie. a primitive type, a name or an array type, and \
+               not to an arbitrary expression.",
+        failing: "int x = (1 + 2) 3;",
+        passing: "int x = (int) 3;",
+    },
+    Explanation {
+        code: "J0018",
+        title: "invalid escape character",
+        rule: "JLS 3.10.6",
+        text: "Only \\b, \\t, \\n, \\f, \\r, \\\", \\', \\\\ and octal escapes may follow a \
+               backslash in a character or string literal.",
+        failing: "String s = \"\\q\";",
+        passing: "String s = \"\\\\q\";",
+    },
+    Explanation {
+        code: "J0019",
+        title: "invalid octal value",
+        rule: "JLS 3.10.6",
+        text: "Octal escapes may only use the digits 0 to 7, and may not exceed \\377.",
+        failing: "char c = '\\8';",
+        passing: "char c = '\\7';",
+    },
+    Explanation {
+        code: "J0020",
+        title: "parse tree could not be entirely reduced",
+        rule: "",
+        text: "The parser reached the end of the file without building a complete \
+               compilation unit. This usually follows another syntax error.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0021",
+        title: "invalid child of root token",
+        rule: "JLS 7.3",
+        text: "A compilation unit may only contain a package declaration, import \
+               declarations and a type declaration. This indicates a bug in the grammar; \
+               please report it.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0022",
+        title: "invalid token",
+        rule: "JLS 3.5",
+        text: "The lexer found a character or sequence of characters which does not begin \
+               any Joos token.",
+        failing: "int x = 3 # 4;",
+        passing: "int x = 3 + 4;",
+    },
+    Explanation {
+        code: "J0023",
+        title: "malformed grammar",
+        rule: "",
+        text: "A grammar given to `juicyj grammar` could not be read. Each non-terminal is \
+               declared as `Name:` at the start of a line, followed by its rules, one per \
+               tab-indented line, and the grammar must declare `Start`.",
+        failing: "Start\n\tA",
+        passing: "Start:\n\tA",
+    },
+    Explanation {
+        code: "J0024",
+        title: "missing ast name",
+        rule: "",
+        text: "A compilation unit was accepted without a class or interface name. This \
+               indicates a bug in the weeder; please report it.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0025",
+        title: "multiple classes",
+        rule: "Joos 1W",
+        text: "Every file must declare exactly one class or interface.",
+        failing: "public class Foo {\n    public Foo() {}\n}\nclass Bar {}",
+        passing: "public class Foo {\n    public Foo() {}\n}",
+    },
+    Explanation {
+        code: "J0026",
+        title: "native method has body",
+        rule: "JLS 8.4.3.4",
+        text: "A native method is implemented outside of Joos, so it must end in a semicolon \
+               rather than a block.",
+        failing: "public static native int bar(int x) { return x; }",
+        passing: "public static native int bar(int x);",
+    },
+    Explanation {
+        code: "J0027",
+        title: "non-static method is native",
+        rule: "Joos 1W",
+        text: "Joos only supports native methods which are static.",
+        failing: "public native int bar(int x);",
+        passing: "public static native int bar(int x);",
+    },
+    Explanation {
+        code: "J0028",
+        title: "reduce/reduce conflict",
+        rule: "",
+        text: "A grammar given to `juicyj grammar` is not LR(1): in some state, two rules \
+               could be reduced on the same lookahead. The conflicting items are listed.",
+        failing: "Start:\n\tA\n\tB\nA:\n\tx\nB:\n\tx",
+        passing: "Start:\n\tA\nA:\n\tx",
+    },
+    Explanation {
+        code: "J0029",
+        title: "shift/reduce conflict",
+        rule: "",
+        text: "A grammar given to `juicyj grammar` is not LR(1): in some state, a token could \
+               either be shifted or end a rule. The conflicting items are listed.",
+        failing: "Start:\n\tE\nE:\n\tE + E\n\tx",
+        passing: "Start:\n\tE\nE:\n\tE + x\n\tx",
+    },
+    Explanation {
+        code: "J0030",
+        title: "static method is abstract",
+        rule: "JLS 8.4.3.1",
+        text: "Static methods can not be overridden, so they can not be abstract.",
+        failing: "public static abstract int bar();",
+        passing: "public static int bar() { return 0; }",
+    },
+    Explanation {
+        code: "J0031",
+        title: "static method is final",
+        rule: "Joos 1W",
+        text: "Joos does not allow static methods to be final.",
+        failing: "public static final int bar() { return 0; }",
+        passing: "public static int bar() { return 0; }",
+    },
+    Explanation {
+        code: "J0032",
+        title: "string contains newline",
+        rule: "JLS 3.10.5",
+        text: "A string literal may not contain a line terminator. Use the escape sequence \
+               \\n instead.",
+        failing: "String s = \"foo\nbar\";",
+        passing: "String s = \"foo\\nbar\";",
+    },
+    Explanation {
+        code: "J0033",
+        title: "could not convert string to token",
+        rule: "",
+        text: "The parse table refers to a token the compiler does not know about. The \
+               table is out of date with the lexer; regenerate it with `make grammar`.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0034",
+        title: "type error",
+        rule: "JLS 15",
+        text: "An expression or statement is not well typed. The message describes which \
+               rule was broken.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0035",
+        title: "unexpected token",
+        rule: "JLS 18",
+        text: "The parser found a token which can not appear at this point in the program. \
+               Every token which could have appeared is listed.",
+        failing: "int x = = 3;",
+        passing: "int x = 3;",
+    },
+    Explanation {
+        code: "J0036",
+        title: "unparseable token",
+        rule: "",
+        text: "The parse table has no action for a token. This indicates a bug in the parse \
+               table; please report it.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0037",
+        title: "unexpected ast node",
+        rule: "",
+        text: "A node of the abstract syntax tree did not have the shape expected of it, so \
+               the typed AST could not be built from it. This indicates a bug in the AST \
+               builder; please report it.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0101",
+        title: "class or interface names must be unique",
+        rule: "JLS 7.6",
+        text: "Two types in the same package may not have the same name.",
+        failing: "// in a/Foo.java and b/a/Foo.java\npackage a;\npublic class Foo {\n    \
+                  public Foo() {}\n}",
+        passing: "// in a/Foo.java and a/Bar.java\npackage a;\npublic class Bar {\n    \
+                  public Bar() {}\n}",
+    },
+    Explanation {
+        code: "J0102",
+        title: "constructors must have unique signatures",
+        rule: "JLS 8.8.2",
+        text: "A class may not declare two constructors with the same parameter types.",
+        failing: "public Foo(int x) {}\npublic Foo(int y) {}",
+        passing: "public Foo(int x) {}\npublic Foo(boolean y) {}",
+    },
+    Explanation {
+        code: "J0103",
+        title: "field names must be unique",
+        rule: "JLS 8.3",
+        text: "A class may not declare two fields with the same name.",
+        failing: "public int x;\npublic boolean x;",
+        passing: "public int x;\npublic boolean y;",
+    },
+    Explanation {
+        code: "J0104",
+        title: "methods must have unique signatures",
+        rule: "JLS 8.4.2",
+        text: "A class or interface may not declare two methods with the same name and \
+               parameter types, even if their return types differ.",
+        failing: "public int bar(int x) { return x; }\npublic boolean bar(int y) { return \
+                  true; }",
+        passing: "public int bar(int x) { return x; }\npublic boolean bar(boolean y) { \
+                  return y; }",
+    },
+    Explanation {
+        code: "J0105",
+        title: "could not find imported package",
+        rule: "JLS 7.5",
+        text: "An import declaration names a type or package which does not exist in the \
+               program. Check the spelling, and that every file was passed to the compiler.",
+        failing: "import java.utils.*;",
+        passing: "import java.util.*;",
+    },
+    Explanation {
+        code: "J0106",
+        title: "single-type-import declaration clashes with a declared type",
+        rule: "JLS 7.5.1",
+        text: "A single-type-import declaration may not import a type with the same simple \
+               name as the type declared in the file.",
+        failing: "// in Foo.java\nimport bar.Foo;\npublic class Foo {\n    public Foo() {}\n}",
+        passing: "// in Foo.java\nimport bar.*;\npublic class Foo {\n    public Foo() {}\n}",
+    },
+    Explanation {
+        code: "J0107",
+        title: "multiple parameters with the same name",
+        rule: "JLS 8.4.1",
+        text: "Two parameters of the same method or constructor may not have the same name.",
+        failing: "public int bar(int x, int x) { return x; }",
+        passing: "public int bar(int x, int y) { return x; }",
+    },
+    Explanation {
+        code: "J0108",
+        title: "constructor does not share class name",
+        rule: "JLS 8.8",
+        text: "A constructor has the same name as the class which declares it. A declaration \
+               with a different name and no return type is neither a constructor nor a \
+               method.",
+        failing: "public class Foo {\n    public Bar() {}\n}",
+        passing: "public class Foo {\n    public Foo() {}\n}",
+    },
+    Explanation {
+        code: "J0109",
+        title: "strict prefix of a name resolves to a type",
+        rule: "JLS 6.5.2",
+        text: "When a qualified name refers to a type, no strict prefix of it may itself \
+               resolve to a type, since the meaning of the name would be ambiguous.",
+        failing: "// with classes foo.Bar and foo.Bar.Baz\nfoo.Bar.Baz x = null;",
+        passing: "// with classes foo.Bar and foo.bar.Baz\nfoo.bar.Baz x = null;",
+    },
+    Explanation {
+        code: "J0110",
+        title: "could not find type",
+        rule: "JLS 6.5.5",
+        text: "A type name does not refer to any class or interface in scope. A simple name \
+               must be the enclosing type, a single-type import, a type in the same package, \
+               or a type in an on-demand imported package; a qualified name must be the \
+               canonical name of a type.",
+        failing: "Strnig s = \"foo\";",
+        passing: "String s = \"foo\";",
+    },
+    Explanation {
+        code: "J0111",
+        title: "could not find variable, field or type",
+        rule: "JLS 6.5.2",
+        text: "A simple name in an expression does not refer to a local variable, parameter, \
+               field or type in scope. Check the spelling, and that the variable was declared \
+               before it is used.",
+        failing: "int count = 0;\nreturn cuont;",
+        passing: "int count = 0;\nreturn count;",
+    },
+    Explanation {
+        code: "J0201",
+        title: "cyclic class hierarchy detected",
+        rule: "JLS 8.1.3, 9.1.2",
+        text: "A class or interface may not depend on itself, either directly or through its \
+               superclasses and superinterfaces.",
+        failing: "public class Foo extends Bar {}\npublic class Bar extends Foo {}",
+        passing: "public class Foo extends Bar {}\npublic class Bar {}",
+    },
+    Explanation {
+        code: "J0202",
+        title: "class cannot extend final class",
+        rule: "JLS 8.1.3",
+        text: "A final class may not have subclasses.",
+        failing: "public final class Bar {}\npublic class Foo extends Bar {}",
+        passing: "public class Bar {}\npublic class Foo extends Bar {}",
+    },
+    Explanation {
+        code: "J0203",
+        title: "class cannot extend interface",
+        rule: "JLS 8.1.3",
+        text: "A class may only extend another class. Interfaces are implemented instead.",
+        failing: "public class Foo extends Comparable {}",
+        passing: "public class Foo implements Comparable {}",
+    },
+    Explanation {
+        code: "J0204",
+        title: "class cannot implement class",
+        rule: "JLS 8.1.4",
+        text: "A class may only implement interfaces. Other classes are extended instead.",
+        failing: "public class Foo implements Bar {}",
+        passing: "public class Foo extends Bar {}",
+    },
+    Explanation {
+        code: "J0205",
+        title: "interface cannot extend class",
+        rule: "JLS 9.1.2",
+        text: "An interface may only extend other interfaces.",
+        failing: "public interface Foo extends Object {}",
+        passing: "public interface Foo extends Comparable {}",
+    },
+    Explanation {
+        code: "J0206",
+        title: "supertype must not be repeated",
+        rule: "JLS 8.1.4, 9.1.2",
+        text: "An interface may appear only once in the implements clause of a class or the \
+               extends clause of an interface.",
+        failing: "public class Foo implements Bar, Bar {}",
+        passing: "public class Foo implements Bar {}",
+    },
+    Explanation {
+        code: "J0207",
+        title: "cannot override final method",
+        rule: "JLS 8.4.3.3",
+        text: "A final method may not be overridden or hidden by a subclass.",
+        failing: "// with public final int bar() in Base\npublic class Foo extends Base {\n    \
+                  public int bar() { return 0; }\n}",
+        passing: "// with public int bar() in Base\npublic class Foo extends Base {\n    \
+                  public int bar() { return 0; }\n}",
+    },
+    Explanation {
+        code: "J0208",
+        title: "cannot override method with different return type",
+        rule: "JLS 8.4.6.3",
+        text: "A method which overrides or is inherited alongside another method with the \
+               same signature must have the same return type.",
+        failing: "// with public int bar() in Base\npublic boolean bar() { return true; }",
+        passing: "// with public int bar() in Base\npublic int bar() { return 0; }",
+    },
+    Explanation {
+        code: "J0209",
+        title: "cannot override staticity of method",
+        rule: "JLS 8.4.6.1, 8.4.6.2",
+        text: "A static method may not hide an instance method, and an instance method may \
+               not override a static method.",
+        failing: "// with public int bar() in Base\npublic static int bar() { return 0; }",
+        passing: "// with public int bar() in Base\npublic int bar() { return 0; }",
+    },
+    Explanation {
+        code: "J0210",
+        title: "cannot weaken access of method",
+        rule: "JLS 8.4.6.3",
+        text: "A method which overrides another may not be less accessible than it: a public \
+               method may not be overridden by a protected one.",
+        failing: "// with public int bar() in Base\nprotected int bar() { return 0; }",
+        passing: "// with public int bar() in Base\npublic int bar() { return 0; }",
+    },
+    Explanation {
+        code: "J0211",
+        title: "abstract method found in non-abstract class",
+        rule: "JLS 8.1.1.1",
+        text: "A class which declares or inherits an abstract method, without implementing \
+               it, must itself be declared abstract.",
+        failing: "public class Foo implements Comparable {\n    public Foo() {}\n}",
+        passing: "public abstract class Foo implements Comparable {\n    public Foo() {}\n}",
+    },
+    Explanation {
+        code: "J0301",
+        title: "incompatible types in assignment",
+        rule: "JLS 5.2",
+        text: "A value may only be assigned to a variable, or returned from a method, if its \
+               type can be converted to the type of the variable by assignment conversion.",
+        failing: "int x = true;",
+        passing: "boolean x = true;",
+    },
+    Explanation {
+        code: "J0302",
+        title: "condition is not boolean",
+        rule: "JLS 14.8, 14.10, 14.12",
+        text: "The condition of an if, while or for statement must have type boolean. Joos, \
+               like Java, does not treat integers as truth values.",
+        failing: "if (1) { x = 2; }",
+        passing: "if (1 == 1) { x = 2; }",
+    },
+    Explanation {
+        code: "J0303",
+        title: "'this' used in static method",
+        rule: "JLS 15.8.3",
+        text: "A static method is not invoked on an object, so it can not refer to `this`, \
+               either explicitly or by naming an instance field or method.",
+        failing: "public int x;\npublic static int bar() { return x; }",
+        passing: "public static int x;\npublic static int bar() { return x; }",
+    },
+    Explanation {
+        code: "J0304",
+        title: "instantiated abstract class or interface",
+        rule: "JLS 15.9.1",
+        text: "Only concrete classes can be created with `new`.",
+        failing: "Object x = new Comparable();",
+        passing: "Object x = new Object();",
+    },
+    Explanation {
+        code: "J0305",
+        title: "variable declared twice",
+        rule: "JLS 14.4.2",
+        text: "A local variable may not be declared with the same name as a parameter or \
+               another local variable which is still in scope.",
+        failing: "public int bar(int x) {\n    int x = 3;\n    return x;\n}",
+        passing: "public int bar(int x) {\n    int y = 3;\n    return y;\n}",
+    },
+    Explanation {
+        code: "J0306",
+        title: "could not find field",
+        rule: "JLS 15.11",
+        text: "A field access names a field which does not exist in the class of the \
+               expression, or any of its superclasses.",
+        failing: "String s = \"foo\";\nint x = s.size;",
+        passing: "String s = \"foo\";\nint x = s.length();",
+    },
+    Explanation {
+        code: "J0307",
+        title: "could not access method",
+        rule: "JLS 6.6.2",
+        text: "A protected method may only be invoked from the class which declares it, its \
+               subclasses, or its package.",
+        failing: "// in another package\nObject x = new Object();\nObject y = x.clone();",
+        passing: "Object x = new Object();\nString y = x.toString();",
+    },
+    Explanation {
+        code: "J0308",
+        title: "no method matching parameters was found",
+        rule: "JLS 15.12.2",
+        text: "No method or constructor with the given name accepts arguments of the given \
+               types. Joos methods are matched on the exact types of their arguments.",
+        failing: "String s = \"foo\";\nchar c = s.charAt(true);",
+        passing: "String s = \"foo\";\nchar c = s.charAt(0);",
+    },
+    Explanation {
+        code: "J0309",
+        title: "ambiguous method invocation",
+        rule: "JLS 15.12.2.2",
+        text: "More than one method matches the arguments of an invocation equally well, so \
+               the compiler can not choose between them.",
+        failing: "",
+        passing: "",
+    },
+    Explanation {
+        code: "J0310",
+        title: "unreachable code",
+        rule: "JLS 14.20",
+        text: "Every statement must be reachable. Code after a return, or after a loop which \
+               never ends, can never run; a loop whose condition is the constant false can \
+               never run its body.",
+        failing: "public int bar() {\n    return 1;\n    return 2;\n}",
+        passing: "public int bar() {\n    return 1;\n}",
+    },
+    Explanation {
+        code: "J0311",
+        title: "non-void method has no return",
+        rule: "JLS 8.4.5",
+        text: "A method whose return type is not void must return a value.",
+        failing: "public int bar() {}",
+        passing: "public int bar() { return 0; }",
+    },
+    Explanation {
+        code: "J0312",
+        title: "method has invalid return type",
+        rule: "JLS 14.16",
+        text: "Every value returned by a method must be assignable to its declared return \
+               type, and a constructor can not return a value.",
+        failing: "public int bar() { return true; }",
+        passing: "public int bar() { return 1; }",
+    },
+    Explanation {
+        code: "J0313",
+        title: "field used before it is initialized",
+        rule: "JLS 8.3.2.3",
+        text: "The initializer of a field may only refer to fields declared before it.",
+        failing: "public int x = y;\npublic int y = 3;",
+        passing: "public int y = 3;\npublic int x = y;",
+    },
+    Explanation {
+        code: "J0314",
+        title: "instanceof applied to primitive type",
+        rule: "JLS 15.20.2",
+        text: "Both sides of an instanceof expression must be reference types.",
+        failing: "boolean b = 3 instanceof int;",
+        passing: "boolean b = x instanceof Object;",
+    },
+    Explanation {
+        code: "J0315",
+        title: "bitwise operations are not allowed",
+        rule: "Joos 1W",
+        text: "Joos does not support the bitwise operators & and | on integers. On booleans \
+               they are the eager logical operators.",
+        failing: "int x = 1 & 3;",
+        passing: "boolean b = true & false;",
+    },
+    Explanation {
+        code: "J0316",
+        title: "invalid cast",
+        rule: "JLS 5.5",
+        text: "A cast is only allowed between numeric types, or between reference types where \
+               one could be a subtype of the other.",
+        failing: "String s = (String) 3;",
+        passing: "Object o = \"foo\";\nString s = (String) o;",
+    },
+    Explanation {
+        code: "J0317",
+        title: "could not resolve method",
+        rule: "JLS 15.12.1",
+        text: "A method invocation does not name a method of the class it is invoked on. \
+               Instance methods may be invoked on an expression or implicitly on `this`; \
+               static methods must be invoked on the name of their class.",
+        failing: "String s = \"foo\";\nint x = s.lenght();",
+        passing: "String s = \"foo\";\nint x = s.length();",
+    },
+    Explanation {
+        code: "J0318",
+        title: "variable used before it is definitely assigned",
+        rule: "JLS 16",
+        text: "A local variable declared without an initializer must be assigned along \
+               every path through the method before it is read. Paths include both branches \
+               of an if statement, a loop whose body never runs, and the right side of a && \
+               or || which is never evaluated. Conditions which are constant true or false \
+               only have one path.",
+        failing: "int x;\nif (b) {\n    x = 1;\n}\nreturn x;",
+        passing: "int x;\nif (b) {\n    x = 1;\n} else {\n    x = 2;\n}\nreturn x;",
+    },
+    Explanation {
+        code: "J0319",
+        title: "unreachable statement",
+        rule: "JLS 14.20",
+        text: "Every statement must be reachable. Code after a return, or after a loop which \
+               never ends, can never run; a loop whose condition is the constant false can \
+               never run its body. A loop condition is constant when it is built only from \
+               literals, eg. `while (true)`, and a missing `for` condition is always true.",
+        failing: "public int bar() {\n    while (true) {}\n    return 2;\n}",
+        passing: "public int bar() {\n    while (true) {}\n}",
+    },
+    Explanation {
+        code: "J0320",
+        title: "missing return statement",
+        rule: "JLS 8.4.5, 14.20",
+        text: "The body of a method whose return type is not void must not be able to complete \
+               normally: every path through it must end in a return statement or a loop which \
+               never ends. An `if` statement without an `else` can always be skipped, even \
+               when its condition is constant.",
+        failing: "public int bar(boolean c) {\n    if (c) {\n        return 1;\n    }\n}",
+        passing: "public int bar(boolean c) {\n    if (c) {\n        return 1;\n    }\n    \
+                  return 0;\n}",
+    },
+];
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
+use error::ast::ASTError;
+use error::lexer::LexerError;
+use error::message::ErrorMessage;
+use error::parser::ParserError;
+use error::weeder::WeederError;
+use scanner::SourceMap;
+use scanner::Span;
+
+/// The stage of the compiler which generated a Diagnostic.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Phase {
+    /// Reading source files.
+    Input,
+    /// Lexer, ie. tokenizing each source file.
+    Lexer,
+    /// Parser, ie. building a parse tree from tokens.
+    Parser,
+    /// Weeder, ie. rejecting parse trees which do not follow Joos rules.
+    Weeder,
+    /// AST generation.
+    AST,
+    /// Building the class and interface environments.
+    Environment,
+    /// Checking the class hierarchy.
+    Hierarchy,
+    /// Type checking.
+    Types,
+    /// Code generation.
+    Codegen,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Phase::Input => write!(f, "input"),
+            Phase::Lexer => write!(f, "lexer"),
+            Phase::Parser => write!(f, "parser"),
+            Phase::Weeder => write!(f, "weeder"),
+            Phase::AST => write!(f, "ast"),
+            Phase::Environment => write!(f, "environment"),
+            Phase::Hierarchy => write!(f, "hierarchy"),
+            Phase::Types => write!(f, "types"),
+            Phase::Codegen => write!(f, "codegen"),
+        }
+    }
+}
+
+/// A structured error, generated by any stage of the compiler. Points at the
+/// offending code with a primary span and may carry secondary labels and
+/// free-form notes.
+pub struct Diagnostic {
+    /// stage of the compiler which generated this error
+    pub phase: Phase,
+    /// ErrorMessage
+    pub message: ErrorMessage,
+    /// location of the offending code, if it could be determined
+    pub span: Option<Span>,
+    /// secondary locations, each with an explanation of its relevance
+    pub labels: Vec<(Span, String)>,
+    /// additional context which can not be tied to a location
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Constructs a Diagnostic from the stage which generated it, an
+    /// ErrorMessage and the span of the offending code. The notes of an
+    /// analysis error become the notes of the Diagnostic.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use juicyj::error::Diagnostic;
+    /// use juicyj::error::AnalysisCode;
+    /// use juicyj::error::AnalysisError;
+    /// use juicyj::error::ErrorMessage;
+    /// use juicyj::error::Phase;
+    ///
+    /// let reason = AnalysisError::new(AnalysisCode::NonBooleanCondition,
+    ///                                 "condition is not boolean".to_owned());
+    /// let message = ErrorMessage::TypeError(reason);
+    /// let error = Diagnostic::new(Phase::Types, message, None).with_note("in method foo".to_owned());
+    /// println!("{}", error);
+    /// ```
+    pub fn new(phase: Phase, message: ErrorMessage, span: Option<Span>) -> Diagnostic {
+        let notes = match message {
+            ErrorMessage::EnvironmentError(ref reason) |
+            ErrorMessage::HierarchyError(ref reason) |
+            ErrorMessage::TypeError(ref reason) => reason.notes.clone(),
+            _ => Vec::new(),
+        };
+
+        Diagnostic {
+            phase: phase,
+            message: message,
+            span: span,
+            labels: Vec::new(),
+            notes: notes,
+        }
+    }
+
+    /// Adds a secondary label to a Diagnostic. Labels without a span are
+    /// dropped, since there would be nothing to point at.
+    pub fn with_label(mut self, span: Option<Span>, label: String) -> Diagnostic {
+        if let Some(span) = span {
+            self.labels.push((span, label));
+        }
+        self
+    }
+
+    /// Adds a note to a Diagnostic.
+    pub fn with_note(mut self, note: String) -> Diagnostic {
+        self.notes.push(note);
+        self
+    }
+}
+
+impl From<LexerError> for Diagnostic {
+    fn from(error: LexerError) -> Diagnostic {
+        let diagnostic = Diagnostic::new(Phase::Lexer, error.message, error.span);
+        match error.span {
+            Some(_) => diagnostic,
+            None => {
+                diagnostic.with_note(format!("in {} on line {}", error.filename, error.line_number))
+            }
+        }
+    }
+}
+
+impl From<ParserError> for Diagnostic {
+    fn from(error: ParserError) -> Diagnostic {
+        let diagnostic = Diagnostic::new(Phase::Parser, error.message, error.span);
+        match error.arg {
+            Some(arg) => diagnostic.with_note(arg),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<WeederError> for Diagnostic {
+    fn from(error: WeederError) -> Diagnostic {
+        let diagnostic = Diagnostic::new(Phase::Weeder, error.message, error.span);
+        match error.span {
+            Some(_) => diagnostic,
+            None => diagnostic.with_note(format!("in {}", error.filename)),
+        }
+    }
+}
+
+impl From<ASTError> for Diagnostic {
+    fn from(error: ASTError) -> Diagnostic {
+        Diagnostic::new(Phase::AST, error.message, error.span)
+    }
+}
+
+// Builds the marker line drawn below a source line, eg. `    ^^^`. Tabs are
+// preserved so the markers line up with the source as printed.
+fn underline(span: &Span, line: &str, marker: char) -> String {
+    let mut chars = line.chars();
+    let mut markers = String::new();
+    for _ in 1..span.column {
+        match chars.next() {
+            Some('\t') => markers.push('\t'),
+            Some(_) => markers.push(' '),
+            None => break,
+        }
+    }
+
+    let mut remaining = span.end - span.start;
+    let mut width = 0;
+    for ch in chars {
+        if remaining == 0 {
+            break;
+        }
+        remaining = remaining.saturating_sub(ch.len_utf8());
+        markers.push(marker);
+        width += 1;
+    }
+    if width == 0 {
+        markers.push(marker);
+    }
+
+    markers
+}
+
+// Writes a header line followed by the source lines pointed at by a primary
+// span and any secondary labels, then any notes. Shared by errors and warnings.
+// Spans into files which are not in `sources` are written as a bare location.
+pub fn render(f: &mut fmt::Formatter,
+              sources: &SourceMap,
+              header: &str,
+              span: &Option<Span>,
+              labels: &Vec<(Span, String)>,
+              notes: &Vec<String>)
+              -> fmt::Result {
+    try!(write!(f, "{}", header));
+
+    let mut line_numbers = labels.iter().map(|l| l.0.line).collect::<Vec<_>>();
+    if let Some(ref span) = *span {
+        line_numbers.push(span.line);
+    }
+    let indent = line_numbers.iter().max().unwrap_or(&0).to_string().len();
+    let pad = (0..indent).map(|_| " ").collect::<String>();
+
+    if let Some(ref span) = *span {
+        try!(write!(f, "\n{}--> {}", pad, sources.location(span)));
+        if sources.contents(span.file).is_some() {
+            let line = sources.source_line(span);
+            try!(write!(f,
+                        "\n{} |\n{:>width$} | {}\n{} | {}",
+                        pad,
+                        span.line,
+                        line,
+                        pad,
+                        underline(span, &line, '^'),
+                        width = indent));
+        }
+    }
+
+    for &(ref span, ref label) in labels {
+        try!(write!(f, "\n{}::: {}", pad, sources.location(span)));
+        if sources.contents(span.file).is_some() {
+            let line = sources.source_line(span);
+            try!(write!(f,
+                        "\n{} |\n{:>width$} | {}\n{} | {} {}",
+                        pad,
+                        span.line,
+                        line,
+                        pad,
+                        underline(span, &line, '-'),
+                        label,
+                        width = indent));
+        } else {
+            try!(write!(f, " {}", label));
+        }
+    }
+
+    for note in notes {
+        try!(write!(f, "\n{} = note: {}", pad, note));
+    }
+
+    Ok(())
+}
+
+/// A Diagnostic or Warning paired with the sources its spans point into, so
+/// that it can be displayed with the offending code.
+pub struct Rendered<'a, T: 'a> {
+    item: &'a T,
+    sources: &'a SourceMap,
+}
+
+impl<'a, T> Rendered<'a, T> {
+    /// Pairs an error or warning with the sources of its compilation.
+    pub fn new(item: &'a T, sources: &'a SourceMap) -> Rendered<'a, T> {
+        Rendered {
+            item: item,
+            sources: sources,
+        }
+    }
+
+    /// The error or warning being displayed.
+    pub fn item(&self) -> &'a T {
+        self.item
+    }
+
+    /// The sources its spans point into.
+    pub fn sources(&self) -> &'a SourceMap {
+        self.sources
+    }
+}
+
+impl Diagnostic {
+    /// Pairs a Diagnostic with the sources of its compilation, so that it is
+    /// displayed along with the code it points at.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut compiler = juicyj::Compiler::new();
+    /// compiler.add_source("Foo.java", "public class Foo { int x }");
+    /// for error in compiler.parse().unwrap_err() {
+    ///     println!("{}", error.render(compiler.sources()));
+    /// }
+    /// ```
+    pub fn render<'a>(&'a self, sources: &'a SourceMap) -> Rendered<'a, Diagnostic> {
+        Rendered::new(self, sources)
+    }
+
+    /// Serializes a Diagnostic as a single flat record, naming the file its
+    /// span points into.
+    pub fn to_json(&self, sources: &SourceMap) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("level".to_owned(), "error".to_json());
+        object.insert("phase".to_owned(), self.phase.to_string().to_json());
+        object.insert("code".to_owned(), self.message.name().to_json());
+        object.insert("id".to_owned(), self.message.code().to_json());
+        object.insert("file".to_owned(),
+                      self.span.and_then(|s| sources.name(s.file)).map(|n| n.to_owned()).to_json());
+        object.insert("line".to_owned(), self.span.map(|s| s.line).to_json());
+        object.insert("column".to_owned(), self.span.map(|s| s.column).to_json());
+        object.insert("message".to_owned(), self.message.to_string().to_json());
+        object.insert("labels".to_owned(), labels_to_json(&self.labels, sources));
+        object.insert("notes".to_owned(), self.notes.to_json());
+        Json::Object(object)
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, sources: &SourceMap) -> fmt::Result {
+        let header = format!("error[{}]: {}", self.message.code(), self.message);
+        render(f, sources, &header, &self.span, &self.labels, &self.notes)
+    }
+}
+
+impl<'a> fmt::Display for Rendered<'a, Diagnostic> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.item.write(f, self.sources)
+    }
+}
+
+// Without the sources of its compilation, a Diagnostic can only point at the
+// location of the offending code.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, &SourceMap::new())
+    }
+}
+
+// Diagnostics are meant to be read by people, so are debugged the same way
+// they are displayed.
+impl fmt::Debug for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// Secondary labels are written as a list of {file, span, message} records.
+pub fn labels_to_json(labels: &Vec<(Span, String)>, sources: &SourceMap) -> Json {
+    let labels = labels.iter()
+        .map(|&(ref span, ref label)| {
+                 let mut object = BTreeMap::new();
+                 object.insert("file".to_owned(), sources.name(span.file).map(|n| n.to_owned()).to_json());
+                 object.insert("span".to_owned(), span.to_json());
+                 object.insert("message".to_owned(), label.to_json());
+                 Json::Object(object)
+             })
+        .collect::<Vec<Json>>();
+    Json::Array(labels)
+}
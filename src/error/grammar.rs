@@ -0,0 +1,52 @@
+use std::fmt;
+
+use error::message::ErrorMessage;
+
+/// An error struct generated while building a parse table from a grammar.
+pub struct GrammarError {
+    /// ErrorMessage
+    pub message: ErrorMessage,
+    /// `format!`'ed LR(1) items involved in a conflict, added with
+    /// self.with_items()
+    pub items: Vec<String>,
+}
+
+impl GrammarError {
+    /// Constructs a GrammarError from an ErrorMessage.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use juicyj::error::ErrorMessage;
+    /// use juicyj::error::GrammarError;
+    ///
+    /// let message = ErrorMessage::MalformedGrammar("no start symbol".to_owned());
+    /// let error = GrammarError::new(message);
+    /// println!("{}", error);
+    /// ```
+    pub fn new(message: ErrorMessage) -> GrammarError {
+        GrammarError {
+            message: message,
+            items: Vec::new(),
+        }
+    }
+
+    /// Add the items involved in a conflict to a GrammarError.
+    pub fn with_items<T>(mut self, items: T) -> GrammarError
+        where T: IntoIterator,
+              <T as IntoIterator>::Item: fmt::Display
+    {
+        self.items = items.into_iter().map(|i| format!("{}", i)).collect();
+        self
+    }
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "error[{}]: {}", self.message.code(), self.message));
+        for item in &self.items {
+            try!(write!(f, "\n    {}", item));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt;
 
 use error::message::ErrorMessage;
+use scanner::Span;
 
 /// An error struct generated by the lexer.
 pub struct LexerError {
@@ -14,6 +15,8 @@ pub struct LexerError {
     pub line_number: u32,
     /// ErrorMessage
     pub message: ErrorMessage,
+    /// the offending token, from its first character to the erroring one
+    pub span: Option<Span>,
 }
 
 impl fmt::Display for LexerError {
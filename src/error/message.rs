@@ -1,5 +1,7 @@
 use std::fmt;
 
+use error::analysis::AnalysisError;
+
 /// Contains every error message generated by the compiler throughout each
 /// layer of the juicyj stack.
 pub enum ErrorMessage {
@@ -15,16 +17,23 @@ pub enum ErrorMessage {
     CharTooLongOctal,
     /// Classes must have the same name as the file they are declared in.
     ClassBadName,
+    /// Code could not be generated. Parameter: reason.
+    CodegenError(String),
     /// Concrete classes must have bodies.
     ConcreteNoBody,
     /// Error reading file. Parameter: filename.
     CouldNotReadFile(String),
     /// Error running `reduce` function on parse stack.
     CouldNotReduceStack,
+    /// Class or interface environment could not be built. Parameter: reason.
+    EnvironmentError(AnalysisError),
     /// Final methods can not be abstract.
     FinalAbstract,
     /// Final fields must have initializers.
     FinalNoInit,
+    /// Class hierarchy is invalid, eg. cyclic or conflicting inheritance.
+    /// Parameter: reason.
+    HierarchyError(AnalysisError),
     /// Single-Type import declarations must not clash.
     ImportClashSingleTogether,
     /// Interfaces must have the same name as the file they are declared in.
@@ -43,6 +52,8 @@ pub enum ErrorMessage {
     InvalidRootChild,
     /// Catch-all for un-scannable tokens.
     InvalidToken,
+    /// Grammar file could not be read. Parameter: reason.
+    MalformedGrammar(String),
     /// An AST must have a single canonical name identifier.
     MissingName,
     /// A single file may only contain one class.
@@ -51,6 +62,12 @@ pub enum ErrorMessage {
     NativeBody,
     /// Non-static methods can not be native.
     NonStaticNative,
+    /// Grammar is ambiguous: two reductions apply on the same lookahead.
+    /// Parameters: state, lookahead.
+    ReduceReduceConflict(usize, String),
+    /// Grammar is ambiguous: a shift and a reduction apply on the same
+    /// lookahead. Parameters: state, lookahead.
+    ShiftReduceConflict(usize, String),
     /// Static methods cannot be abstract.
     StaticAbstract,
     /// Static methods cannot be final.
@@ -59,10 +76,113 @@ pub enum ErrorMessage {
     StringNewline,
     /// Failure to parse Token (TokenKind) from String.
     StringNotToken(String),
+    /// Catch-all for type checking failures. Parameter: reason.
+    TypeError(AnalysisError),
+    /// An AST node does not have the shape expected of it.
+    UnexpectedNode,
+    /// Token which does not fit the grammar. Parameters: found token, expected
+    /// tokens.
+    UnexpectedToken(String, Vec<String>),
     /// Catch-all for un-parseable tokens. Parameter: `format!`'ed token.
     UnparseableToken(String),
 }
 
+impl ErrorMessage {
+    /// The name of this kind of error, ie. its variant, eg. `FinalNoInit`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            ErrorMessage::AbstractBody => "AbstractBody",
+            ErrorMessage::CharNewline => "CharNewline",
+            ErrorMessage::CharTooLong => "CharTooLong",
+            ErrorMessage::CharTooLongOctal => "CharTooLongOctal",
+            ErrorMessage::ClassBadName => "ClassBadName",
+            ErrorMessage::CodegenError(_) => "CodegenError",
+            ErrorMessage::ConcreteNoBody => "ConcreteNoBody",
+            ErrorMessage::CouldNotReadFile(_) => "CouldNotReadFile",
+            ErrorMessage::CouldNotReduceStack => "CouldNotReduceStack",
+            ErrorMessage::EnvironmentError(_) => "EnvironmentError",
+            ErrorMessage::FinalAbstract => "FinalAbstract",
+            ErrorMessage::FinalNoInit => "FinalNoInit",
+            ErrorMessage::HierarchyError(_) => "HierarchyError",
+            ErrorMessage::ImportClashSingleTogether => "ImportClashSingleTogether",
+            ErrorMessage::InterfaceBadName => "InterfaceBadName",
+            ErrorMessage::IntOOB => "IntOOB",
+            ErrorMessage::InvalidCast => "InvalidCast",
+            ErrorMessage::InvalidEscape => "InvalidEscape",
+            ErrorMessage::InvalidOctal => "InvalidOctal",
+            ErrorMessage::InvalidParseTree => "InvalidParseTree",
+            ErrorMessage::InvalidRootChild => "InvalidRootChild",
+            ErrorMessage::InvalidToken => "InvalidToken",
+            ErrorMessage::MalformedGrammar(_) => "MalformedGrammar",
+            ErrorMessage::MissingName => "MissingName",
+            ErrorMessage::MultipleClasses => "MultipleClasses",
+            ErrorMessage::NativeBody => "NativeBody",
+            ErrorMessage::NonStaticNative => "NonStaticNative",
+            ErrorMessage::ReduceReduceConflict(_, _) => "ReduceReduceConflict",
+            ErrorMessage::ShiftReduceConflict(_, _) => "ShiftReduceConflict",
+            ErrorMessage::StaticAbstract => "StaticAbstract",
+            ErrorMessage::StaticFinal => "StaticFinal",
+            ErrorMessage::StringNewline => "StringNewline",
+            ErrorMessage::StringNotToken(_) => "StringNotToken",
+            ErrorMessage::TypeError(_) => "TypeError",
+            ErrorMessage::UnexpectedNode => "UnexpectedNode",
+            ErrorMessage::UnexpectedToken(_, _) => "UnexpectedToken",
+            ErrorMessage::UnparseableToken(_) => "UnparseableToken",
+        }
+    }
+
+    /// The stable code of this error, eg. `J0012`, which can be looked up with
+    /// `error::explain`. Analysis errors have their own codes where the reason
+    /// given is of a known kind.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ErrorMessage::AbstractBody => "J0001",
+            ErrorMessage::CharNewline => "J0002",
+            ErrorMessage::CharTooLong => "J0003",
+            ErrorMessage::CharTooLongOctal => "J0004",
+            ErrorMessage::ClassBadName => "J0005",
+            ErrorMessage::CodegenError(_) => "J0006",
+            ErrorMessage::ConcreteNoBody => "J0007",
+            ErrorMessage::CouldNotReadFile(_) => "J0008",
+            ErrorMessage::CouldNotReduceStack => "J0009",
+            ErrorMessage::EnvironmentError(ref reason) => {
+                reason.code.map(|c| c.code()).unwrap_or("J0010")
+            }
+            ErrorMessage::FinalAbstract => "J0011",
+            ErrorMessage::FinalNoInit => "J0012",
+            ErrorMessage::HierarchyError(ref reason) => {
+                reason.code.map(|c| c.code()).unwrap_or("J0013")
+            }
+            ErrorMessage::ImportClashSingleTogether => "J0014",
+            ErrorMessage::InterfaceBadName => "J0015",
+            ErrorMessage::IntOOB => "J0016",
+            ErrorMessage::InvalidCast => "J0017",
+            ErrorMessage::InvalidEscape => "J0018",
+            ErrorMessage::InvalidOctal => "J0019",
+            ErrorMessage::InvalidParseTree => "J0020",
+            ErrorMessage::InvalidRootChild => "J0021",
+            ErrorMessage::InvalidToken => "J0022",
+            ErrorMessage::MalformedGrammar(_) => "J0023",
+            ErrorMessage::MissingName => "J0024",
+            ErrorMessage::MultipleClasses => "J0025",
+            ErrorMessage::NativeBody => "J0026",
+            ErrorMessage::NonStaticNative => "J0027",
+            ErrorMessage::ReduceReduceConflict(_, _) => "J0028",
+            ErrorMessage::ShiftReduceConflict(_, _) => "J0029",
+            ErrorMessage::StaticAbstract => "J0030",
+            ErrorMessage::StaticFinal => "J0031",
+            ErrorMessage::StringNewline => "J0032",
+            ErrorMessage::StringNotToken(_) => "J0033",
+            ErrorMessage::TypeError(ref reason) => {
+                reason.code.map(|c| c.code()).unwrap_or("J0034")
+            }
+            ErrorMessage::UnexpectedToken(_, _) => "J0035",
+            ErrorMessage::UnparseableToken(_) => "J0036",
+            ErrorMessage::UnexpectedNode => "J0037",
+        }
+    }
+}
+
 impl fmt::Display for ErrorMessage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -73,13 +193,16 @@ impl fmt::Display for ErrorMessage {
                 write!(f, "too many characters in char (maybe malformed octal?)")
             }
             ErrorMessage::ClassBadName => write!(f, "class is badly named"),
+            ErrorMessage::CodegenError(ref reason) => write!(f, "{}", reason),
             ErrorMessage::ConcreteNoBody => write!(f, "concrete method has no body"),
             ErrorMessage::CouldNotReadFile(ref filename) => {
                 write!(f, "could not read file {}", filename)
             }
             ErrorMessage::CouldNotReduceStack => write!(f, "could not entirely reduce stack"),
+            ErrorMessage::EnvironmentError(ref reason) => write!(f, "{}", reason),
             ErrorMessage::FinalAbstract => write!(f, "final method is abstract"),
             ErrorMessage::FinalNoInit => write!(f, "final field has no initializer"),
+            ErrorMessage::HierarchyError(ref reason) => write!(f, "{}", reason),
             ErrorMessage::ImportClashSingleTogether => {
                 write!(f, "single-type-import declarations clash")
             }
@@ -91,16 +214,34 @@ impl fmt::Display for ErrorMessage {
             ErrorMessage::InvalidParseTree => write!(f, "parse tree could not be entirely reduced"),
             ErrorMessage::InvalidRootChild => write!(f, "invalid child of root token"),
             ErrorMessage::InvalidToken => write!(f, "invalid token"),
+            ErrorMessage::MalformedGrammar(ref reason) => {
+                write!(f, "malformed grammar: {}", reason)
+            }
             ErrorMessage::MissingName => write!(f, "missing ast name"),
             ErrorMessage::MultipleClasses => write!(f, "multiple classes"),
             ErrorMessage::NativeBody => write!(f, "native method has body"),
             ErrorMessage::NonStaticNative => write!(f, "non-static method is native"),
+            ErrorMessage::ReduceReduceConflict(state, ref symbol) => {
+                write!(f, "reduce/reduce conflict in state {} on {}", state, symbol)
+            }
+            ErrorMessage::ShiftReduceConflict(state, ref symbol) => {
+                write!(f, "shift/reduce conflict in state {} on {}", state, symbol)
+            }
             ErrorMessage::StaticAbstract => write!(f, "static method is abstract"),
             ErrorMessage::StaticFinal => write!(f, "static method is final"),
             ErrorMessage::StringNewline => write!(f, "string contains newline"),
             ErrorMessage::StringNotToken(ref value) => {
                 write!(f, "could not convert string '{}' to token", value)
             }
+            ErrorMessage::TypeError(ref reason) => write!(f, "{}", reason),
+            ErrorMessage::UnexpectedNode => write!(f, "unexpected ast node"),
+            ErrorMessage::UnexpectedToken(ref found, ref expected) => {
+                match expected.len() {
+                    0 => write!(f, "unexpected {}", found),
+                    1 => write!(f, "expected {}, found {}", expected[0], found),
+                    _ => write!(f, "expected one of {}, found {}", expected.join(", "), found),
+                }
+            }
             ErrorMessage::UnparseableToken(ref token) => write!(f, "unparseable token [{}]", token),
         }
     }
@@ -1,15 +1,36 @@
 //! An error module for juicyj. Contains individual error implementations for
-//! every layer of the compiler as well as a global enum for error messages.
+//! every layer of the compiler as well as a global enum for error messages,
+//! and the warnings reported for suspicious but legal code.
 mod message;
 
+mod analysis;
 mod ast;
+mod codes;
+mod diagnostic;
+mod grammar;
 mod lexer;
 mod parser;
+mod warning;
 mod weeder;
 
 pub use self::message::ErrorMessage;
 
+pub use self::codes::AnalysisCode;
+pub use self::codes::Explanation;
+pub use self::codes::explain;
+
+pub use self::analysis::AnalysisError;
 pub use self::ast::ASTError;
+pub use self::diagnostic::Diagnostic;
+pub use self::diagnostic::Phase;
+pub use self::diagnostic::Rendered;
+pub use self::grammar::GrammarError;
 pub use self::lexer::LexerError;
 pub use self::parser::ParserError;
+pub use self::warning::LINTS;
+pub use self::warning::Lint;
+pub use self::warning::Warning;
 pub use self::weeder::WeederError;
+
+#[cfg(test)]
+mod test;
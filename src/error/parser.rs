@@ -1,6 +1,7 @@
 use std::fmt;
 
 use error::message::ErrorMessage;
+use scanner::Span;
 
 /// An error struct generated by the parser, including the DFA.
 pub struct ParserError {
@@ -10,6 +11,8 @@ pub struct ParserError {
     pub message: ErrorMessage,
     /// `format1`'ed `ParseNode`s for context, added with self.with_nodes()
     pub nodes: Option<String>,
+    /// location of the offending token, added with self.with_span()
+    pub span: Option<Span>,
 }
 
 impl ParserError {
@@ -20,9 +23,16 @@ impl ParserError {
             arg: arg,
             message: message,
             nodes: None,
+            span: None,
         }
     }
 
+    /// Add the location of the offending token to a ParserError.
+    pub fn with_span(mut self, span: Option<Span>) -> ParserError {
+        self.span = span;
+        self
+    }
+
     /// Add node information to a ParserError. Should be called as late as
     /// possible before printing the error to ensure we have context.
     pub fn with_nodes<T>(mut self, nodes: T) -> ParserError
@@ -40,6 +50,10 @@ impl ParserError {
 
 impl fmt::Display for ParserError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref span) = self.span {
+            try!(write!(f, " --> {}:{}\n", span.line, span.column));
+        }
+
         match self.arg {
             Some(ref r) => {
                 match self.nodes {
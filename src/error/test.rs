@@ -0,0 +1,51 @@
+use super::AnalysisCode;
+use super::AnalysisError;
+use super::Diagnostic;
+use super::ErrorMessage;
+use super::Phase;
+use super::codes;
+
+#[test]
+fn test_codes_are_unique() {
+    for (i, explanation) in codes::CATALOG.iter().enumerate() {
+        for other in &codes::CATALOG[i + 1..] {
+            assert!(explanation.code != other.code, "{} is duplicated", other.code);
+        }
+    }
+}
+
+#[test]
+fn test_analysis_codes_are_explained() {
+    for code in codes::ANALYSIS {
+        assert!(codes::explain(code.code()).is_some(), "{:?} is not explained", code);
+    }
+}
+
+#[test]
+fn test_analysis_codes() {
+    let reason = AnalysisError::new(AnalysisCode::CyclicHierarchy,
+                                    "cyclic class hierarchy detected".to_owned());
+    assert_eq!(ErrorMessage::HierarchyError(reason).code(), "J0201");
+
+    let reason = AnalysisError::new(AnalysisCode::Unassigned,
+                                    "variable x might not have been initialized".to_owned());
+    assert_eq!(ErrorMessage::TypeError(reason).code(), "J0318");
+
+    let reason = AnalysisError::other("something unexpected".to_owned());
+    let message = ErrorMessage::TypeError(reason);
+    assert_eq!(message.code(), "J0034");
+    assert!(codes::explain(message.code()).is_some());
+
+    let reason = AnalysisError::other("something unexpected".to_owned());
+    assert_eq!(ErrorMessage::EnvironmentError(reason).code(), "J0010");
+}
+
+#[test]
+fn test_analysis_notes() {
+    let reason = AnalysisError::new(AnalysisCode::UnresolvedField,
+                                    "could not find field lenght in class Foo".to_owned())
+        .with_note("did you mean `length`?".to_owned());
+    let error = Diagnostic::new(Phase::Types, ErrorMessage::TypeError(reason), None);
+    assert_eq!(format!("{}", error.message), "could not find field lenght in class Foo");
+    assert_eq!(error.notes, vec!["did you mean `length`?".to_owned()]);
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
+use error::diagnostic::labels_to_json;
+use error::diagnostic::render;
+use error::diagnostic::Rendered;
+use scanner::SourceMap;
+use scanner::Span;
+
+/// A kind of suspicious but legal code. Each one can be enabled or disabled
+/// on its own.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Lint {
+    /// A local variable which is never used.
+    UnusedVariable,
+    /// A method or constructor parameter which is never used.
+    UnusedParameter,
+    /// A single-type import whose type is never used.
+    UnusedImport,
+    /// A local variable with the same name as a field of its class.
+    ShadowedField,
+    /// Two Strings compared by reference, with `==` or `!=`.
+    StringComparison,
+    /// An `if` or `while` statement whose body does nothing.
+    EmptyBody,
+}
+
+/// Every Lint, in the order they are listed to users.
+pub const LINTS: [Lint; 6] = [Lint::UnusedVariable,
+                              Lint::UnusedParameter,
+                              Lint::UnusedImport,
+                              Lint::ShadowedField,
+                              Lint::StringComparison,
+                              Lint::EmptyBody];
+
+impl Lint {
+    /// The name used to enable or disable this Lint, eg. `unused-import`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Lint::UnusedVariable => "unused-variable",
+            Lint::UnusedParameter => "unused-parameter",
+            Lint::UnusedImport => "unused-import",
+            Lint::ShadowedField => "shadowed-field",
+            Lint::StringComparison => "string-comparison",
+            Lint::EmptyBody => "empty-body",
+        }
+    }
+
+    /// Finds a Lint by its name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use juicyj::error::Lint;
+    ///
+    /// assert_eq!(Lint::from_name("unused-import"), Some(Lint::UnusedImport));
+    /// assert_eq!(Lint::from_name("unused-everything"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Lint> {
+        LINTS.iter().find(|l| l.name() == name).cloned()
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Suspicious but legal code, found during analysis. Warnings never stop a
+/// compilation; they are rendered the same way as a Diagnostic.
+#[derive(Clone)]
+pub struct Warning {
+    /// the kind of suspicious code found
+    pub lint: Lint,
+    /// a description of this occurrence
+    pub message: String,
+    /// location of the suspicious code, if it could be determined
+    pub span: Option<Span>,
+    /// secondary locations, each with an explanation of its relevance
+    pub labels: Vec<(Span, String)>,
+}
+
+impl Warning {
+    /// Constructs a Warning from its Lint, a message and the span of the
+    /// suspicious code.
+    pub fn new(lint: Lint, message: String, span: Option<Span>) -> Warning {
+        Warning {
+            lint: lint,
+            message: message,
+            span: span,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Adds a secondary label to a Warning. Labels without a span are
+    /// dropped, since there would be nothing to point at.
+    pub fn with_label(mut self, span: Option<Span>, label: String) -> Warning {
+        if let Some(span) = span {
+            self.labels.push((span, label));
+        }
+        self
+    }
+}
+
+impl Warning {
+    /// Pairs a Warning with the sources of its compilation, so that it is
+    /// displayed along with the code it points at.
+    pub fn render<'a>(&'a self, sources: &'a SourceMap) -> Rendered<'a, Warning> {
+        Rendered::new(self, sources)
+    }
+
+    /// Serializes a Warning as a single flat record, naming the file its span
+    /// points into.
+    pub fn to_json(&self, sources: &SourceMap) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("level".to_owned(), "warning".to_json());
+        object.insert("lint".to_owned(), self.lint.name().to_json());
+        object.insert("file".to_owned(),
+                      self.span.and_then(|s| sources.name(s.file)).map(|n| n.to_owned()).to_json());
+        object.insert("line".to_owned(), self.span.map(|s| s.line).to_json());
+        object.insert("column".to_owned(), self.span.map(|s| s.column).to_json());
+        object.insert("message".to_owned(), self.message.to_json());
+        object.insert("labels".to_owned(), labels_to_json(&self.labels, sources));
+        Json::Object(object)
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, sources: &SourceMap) -> fmt::Result {
+        let header = format!("warning[{}]: {}", self.lint, self.message);
+        render(f, sources, &header, &self.span, &self.labels, &Vec::new())
+    }
+}
+
+impl<'a> fmt::Display for Rendered<'a, Warning> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.item().write(f, self.sources())
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, &SourceMap::new())
+    }
+}
+
+impl fmt::Debug for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
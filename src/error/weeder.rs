@@ -1,6 +1,7 @@
 use std::fmt;
 
 use error::message::ErrorMessage;
+use scanner::Span;
 
 /// An error struct generated by the weeder.
 pub struct WeederError {
@@ -10,6 +11,8 @@ pub struct WeederError {
     pub message: ErrorMessage,
     /// `format!`'ed representation of nearby `ParseNode`s
     pub node: String,
+    /// location of the offending node, added with self.with_span()
+    pub span: Option<Span>,
 }
 
 impl WeederError {
@@ -33,16 +36,36 @@ impl WeederError {
             filename: filename,
             message: message,
             node: format!("{}", node),
+            span: None,
         }
     }
+
+    /// Add the location of the offending node to a WeederError.
+    pub fn with_span(mut self, span: Option<Span>) -> WeederError {
+        self.span = span;
+        self
+    }
 }
 
 impl fmt::Display for WeederError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,
-               "error: {}\n --> {}\n\n{}",
-               self.message,
-               self.filename,
-               self.node)
+        match self.span {
+            Some(ref span) => {
+                write!(f,
+                       "error: {}\n --> {}:{}:{}\n\n{}",
+                       self.message,
+                       self.filename,
+                       span.line,
+                       span.column,
+                       self.node)
+            }
+            None => {
+                write!(f,
+                       "error: {}\n --> {}\n\n{}",
+                       self.message,
+                       self.filename,
+                       self.node)
+            }
+        }
     }
 }
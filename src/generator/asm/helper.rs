@@ -1,6 +1,11 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::body;
+use generator::frame::Frame;
+use generator::frame::primitive;
+use generator::vtable::Entry;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
@@ -34,17 +39,42 @@ fn build_method(name: &ASTNode, params: &ASTNode) -> Result<String, String> {
     Ok(method.join(""))
 }
 
+// Where a method is found at runtime, or None if it is not an instance method
+// and so must be called directly.
+fn dispatch(name: &ASTNode, method: &String, vtables: &VTables) -> Result<Option<Entry>, String> {
+    let mut class = name.clone();
+    class.flatten();
+    class.children.pop();
+    class.children.pop();
+    if class.children.is_empty() {
+        return Ok(None);
+    }
+
+    let class = match class.to_label() {
+        Ok(l) => l,
+        Err(e) => return Err(e),
+    };
+    let prefix = format!("__{}.", class);
+    if !method.starts_with(&prefix) {
+        return Ok(None);
+    }
+
+    Ok(vtables.lookup(&class, &method[prefix.len()..]))
+}
+
 pub fn call(this: &Reg,
             method: &ASTNode,
             params: &ASTNode,
             class_label: &String,
-            label: &String,
+            frame: &Frame,
             fields: &HashMap<String, Vec<(String, String)>>,
+            vtables: &VTables,
+            annotations: &Annotations,
             mut text: &mut Vec<String>,
             mut externs: &mut Vec<String>,
-            mut bss: &mut Vec<(String, String)>,
             mut data: &mut Vec<String>)
-            -> Result<Option<String>, String> {
+            -> Result<(), String> {
+    let name = method;
     let method = match build_method(&method, &params) {
         Ok(m) => m,
         Err(e) => return Err(e),
@@ -64,7 +94,15 @@ pub fn call(this: &Reg,
     }
 
     text.push(format!("  ; {}({})", method, param_labels.join(", ")));
-    externs.push(format!("{} {}", Instr::EXTERN, method));
+
+    // instance methods are looked up in the dispatch tables of the receiver
+    let entry = match dispatch(name, &method, vtables) {
+        Ok(e) => e,
+        Err(e) => return Err(e),
+    };
+    if entry.is_none() {
+        externs.push(format!("{} {}", Instr::EXTERN, method));
+    }
 
     // push this and this param
     text.push(format!("{} {}", Instr::PUSH, Reg::EBX));
@@ -78,27 +116,43 @@ pub fn call(this: &Reg,
             continue;
         }
 
-        match body::go(&param,
-                       class_label,
-                       label,
-                       fields,
-                       &mut text,
-                       &mut externs,
-                       &mut bss,
-                       &mut data) {
-            Ok(_) => (),
+        let kind = match body::go(&param,
+                                  class_label,
+                                  frame,
+                                  fields,
+                                  vtables,
+                                  annotations,
+                                  &mut text,
+                                  &mut externs,
+                                  &mut data) {
+            Ok(k) => annotations.label(&param).or(k),
             Err(e) => return Err(e),
+        };
+
+        // primitives are passed by value, anything else by pointer
+        match kind {
+            Some(ref k) if primitive(k) => text.push(format!("{} {}", Instr::PUSH, Reg::EAX)),
+            _ => text.push(format!("{} {}", Instr::PUSH, Reg::ESI)),
         }
-        text.push(format!("{} {}", Instr::PUSH, Reg::ESI));
         text.push("".to_owned());
     }
 
     // call method
-    text.push(format!("{} {}", Instr::PUSH, Reg::EBP));
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::EBP, Reg::ESP));
-    text.push(format!("{} {}", Instr::CALL, method));
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::ESP, Reg::EBP));
-    text.push(format!("{} {}", Instr::POP, Reg::EBP));
+    let receiver = format!("[{}+4*{}]", Reg::ESP, param_labels.len());
+    match entry {
+        Some(Entry::Virtual(idx)) => {
+            text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, receiver));
+            text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::EAX));
+            text.push(format!("{} [{}+4*{}]", Instr::CALL, Reg::EAX, idx));
+        }
+        Some(Entry::Interface(selector)) => {
+            text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, receiver));
+            text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::EAX));
+            text.push(format!("{} {}, [{}-4]", Instr::MOV, Reg::EAX, Reg::EAX));
+            text.push(format!("{} [{}+4*{}]", Instr::CALL, Reg::EAX, selector));
+        }
+        None => text.push(format!("{} {}", Instr::CALL, method)),
+    }
     text.push("".to_owned());
 
     // pop stack by number of params
@@ -117,6 +171,23 @@ pub fn call(this: &Reg,
     text.push(format!("{} {}", Instr::POP, Reg::EBX)); // real this
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is return_type of method
-    Ok(None)
+    Ok(())
+}
+
+/// Sets up the stack frame of a method, reserving space for its local
+/// variables.
+pub fn enter(frame: &Frame, text: &mut Vec<String>) {
+    text.push(format!("{} {}", Instr::PUSH, Reg::EBP));
+    text.push(format!("{} {}, {}", Instr::MOV, Reg::EBP, Reg::ESP));
+    if frame.size() != 0 {
+        text.push(format!("{} {}, {}", Instr::SUB, Reg::ESP, frame.size()));
+    }
+    text.push("".to_owned());
+}
+
+/// Tears down the stack frame of a method and returns from it.
+pub fn leave(text: &mut Vec<String>) {
+    text.push(format!("{} {}, {}", Instr::MOV, Reg::ESP, Reg::EBP));
+    text.push(format!("{} {}", Instr::POP, Reg::EBP));
+    text.push(format!("{}", Instr::RET));
 }
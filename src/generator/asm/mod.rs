@@ -11,7 +11,10 @@ pub enum Instr {
     // general
     CALL,
     INT,
+    LEA,
     MOV,
+    MOVSX,
+    MOVZX,
     POP,
     PUSH,
     RET,
@@ -49,7 +52,10 @@ impl fmt::Display for Instr {
 
             Instr::CALL => write!(f, "  {}", "call"),
             Instr::INT => write!(f, "  {}", "int"),
+            Instr::LEA => write!(f, "  {}", "lea"),
             Instr::MOV => write!(f, "  {}", "mov"),
+            Instr::MOVSX => write!(f, "  {}", "movsx"),
+            Instr::MOVZX => write!(f, "  {}", "movzx"),
             Instr::POP => write!(f, "  {}", "pop"),
             Instr::PUSH => write!(f, "  {}", "push"),
             Instr::RET => write!(f, "  {}", "ret"),
@@ -80,6 +86,7 @@ impl fmt::Display for Instr {
 pub enum Reg {
     // general
     EAX, // Accumulator
+    AX,
     AL,
     EBX, // Base
     // BL,
@@ -102,6 +109,7 @@ impl fmt::Display for Reg {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Reg::EAX => write!(f, "{}", "eax"),
+            Reg::AX => write!(f, "{}", "ax"),
             Reg::AL => write!(f, "{}", "al"),
             Reg::EBX => write!(f, "{}", "ebx"),
             // Reg::BL => write!(f, "{}", "bl"),
@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     text.push(format!("  ; get array[idx]"));
@@ -20,11 +24,12 @@ pub fn go(node: &ASTNode,
     // get index
     match statement::go(&node.children[2],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -42,11 +47,12 @@ pub fn go(node: &ASTNode,
     // get address of base (addr: eax, addr in class: esi)
     match statement::go(&node.children[0],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -57,6 +63,5 @@ pub fn go(node: &ASTNode,
     text.push(format!("{} {}, {}", Instr::ADD, Reg::ESI, Reg::EAX));
     text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
 
-    // TODO<codegen>: kind is ArrayType minus Dim
-    Ok(None)
+    Ok(annotations.label(node))
 }
@@ -1,30 +1,43 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
+    // arrays built during code generation, eg. for string literals, are not
+    // annotated
+    let kind = match annotations.label(node) {
+        Some(k) => k,
+        None => format!("{}[]", node.children[0].source_text()),
+    };
+
     match node.children[1].token.lexeme {
         Some(ref l) if l == "DimExpr" => {
             // resolve array length
             match statement::go(&node.children[1].children[1],
                                 class_label,
-                                label,
+                                frame,
                                 fields,
+                                vtables,
+                                annotations,
                                 &mut text,
                                 &mut externs,
-                                &mut bss,
                                 &mut data) {
                 Ok(_) => (),
                 Err(e) => return Err(e),
@@ -36,10 +49,10 @@ pub fn go(node: &ASTNode,
 
             text.push(format!("  ; new array [size in EAX]"));
 
-            // allocate 4 + 4*l bytes
+            // allocate 8 + 4*l bytes
             text.push(format!("{} {}, {}", Instr::MOV, Reg::ECX, "4"));
             text.push(format!("{} {}, {}", Instr::MUL, Reg::EAX, Reg::ECX));
-            text.push(format!("{} {}, {}", Instr::ADD, Reg::EAX, Reg::ECX));
+            text.push(format!("{} {}, {}", Instr::ADD, Reg::EAX, "8"));
 
             text.push(format!("{} {}", Instr::PUSH, Reg::EBX));
             externs.push(format!("{} {}", Instr::EXTERN, "__malloc"));
@@ -47,15 +60,19 @@ pub fn go(node: &ASTNode,
             text.push(format!("{} {}", Instr::POP, Reg::EBX));
             text.push("".to_owned());
 
+            // the type descriptor is kept before the array
+            let descriptor = vtable::descriptor(&kind);
+            externs.push(format!("{} {}", Instr::EXTERN, descriptor));
+            text.push(format!("{} dword [{}], {}", Instr::MOV, Reg::EAX, descriptor));
+
             // set size in array memory
             text.push(format!("  ; array.length = x"));
-            text.push(format!("{} {}, {}", Instr::MOV, Reg::ESI, Reg::EAX));
+            text.push(format!("{} {}, [{}+4]", Instr::LEA, Reg::ESI, Reg::EAX));
             text.push(format!("{} {}", Instr::POP, Reg::EAX));
             text.push(format!("{} [{}], {}", Instr::MOV, Reg::ESI, Reg::EAX));
             text.push("".to_owned());
 
-            // TODO<codegen>: kind is ArrayType of subexpr
-            Ok(None)
+            Ok(annotations.label(node))
         }
         Some(ref l) if l == "Dim" => Err(format!("found Dim in ArrayCreation {:?}", node)),
         _ => {
@@ -1,30 +1,50 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
-    let kind = match statement::go(&node.children[0],
-                                   class_label,
-                                   label,
-                                   fields,
-                                   &mut text,
-                                   &mut externs,
-                                   &mut bss,
-                                   &mut data) {
-        Ok(k) => (k),
-        Err(e) => return Err(e),
+    let mut lhs = node.children[0].clone();
+    lhs.flatten();
+
+    // a local or parameter is assigned by replacing the contents of its slot,
+    // rather than the value it points to
+    let slot = frame.get(&lhs, annotations);
+    let kind = match slot {
+        Some(slot) => {
+            text.push(format!("  ; {} =", lhs.source_text()));
+            text.push(format!("{} {}, [{}]", Instr::LEA, Reg::ESI, slot.address()));
+            annotations.label(&lhs).or(Some(slot.kind.clone()))
+        }
+        None => {
+            match statement::go(&node.children[0],
+                                class_label,
+                                frame,
+                                fields,
+                                vtables,
+                                annotations,
+                                &mut text,
+                                &mut externs,
+                                &mut data) {
+                Ok(k) => (k),
+                Err(e) => return Err(e),
+            }
+        }
     };
 
     // store lhs address
@@ -34,19 +54,29 @@ pub fn go(node: &ASTNode,
     // get rhs
     match statement::go(&node.children[2],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
 
-    // store rhs value in lhs address
-    text.push(format!("{} {}", Instr::POP, Reg::ESI));
-    text.push(format!("{} [{}], {}", Instr::MOV, Reg::ESI, Reg::EAX));
+    match slot {
+        Some(slot) if !slot.primitive => {
+            // store the pointer to rhs in the slot
+            text.push(format!("{} {}", Instr::POP, Reg::ECX));
+            text.push(format!("{} [{}], {}", Instr::MOV, Reg::ECX, Reg::ESI));
+        }
+        _ => {
+            // store rhs value in lhs address
+            text.push(format!("{} {}", Instr::POP, Reg::ESI));
+            text.push(format!("{} [{}], {}", Instr::MOV, Reg::ESI, Reg::EAX));
+        }
+    }
     text.push("".to_owned());
 
     return Ok(kind);
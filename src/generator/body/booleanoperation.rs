@@ -4,8 +4,11 @@ use std::collections::HashMap;
 
 use self::rand::Rng;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
@@ -13,11 +16,12 @@ use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     match node.token.kind {
@@ -38,11 +42,12 @@ pub fn go(node: &ASTNode,
     // get lhs
     match statement::go(&node.children[0],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -66,11 +71,12 @@ pub fn go(node: &ASTNode,
     // get rhs
     match statement::go(&node.children[1],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -90,6 +96,5 @@ pub fn go(node: &ASTNode,
 
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is boolean
-    Ok(None)
+    Ok(annotations.label(node))
 }
@@ -1,9 +1,11 @@
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
 pub fn go(node: &ASTNode,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>)
           -> Result<Option<String>, String> {
@@ -28,6 +30,5 @@ pub fn go(node: &ASTNode,
     text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is boolean
-    Ok(None)
+    Ok(annotations.label(node))
 }
@@ -1,32 +1,94 @@
+extern crate rand;
+
 use std::collections::HashMap;
 
+use self::rand::Rng;
+
+use analysis::Annotations;
+use generator::asm::Instr;
+use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::frame::primitive;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
-    match statement::go(&node.children[node.children.len() - 1],
+    let operand = &node.children[node.children.len() - 1];
+    match statement::go(operand,
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
 
-    // TODO<codegen>: actual casting
+    let kind = match annotations.label(node) {
+        Some(k) => k,
+        None => return Err(format!("could not find type of cast {:?}", node)),
+    };
+
+    text.push(format!("  ; cast to {}", kind));
+
+    // narrowing truncates the value, then extends it back to 32 bits
+    if primitive(&kind) {
+        match kind.as_ref() {
+            "byte" => text.push(format!("{} {}, {}", Instr::MOVSX, Reg::EAX, Reg::AL)),
+            "short" => text.push(format!("{} {}, {}", Instr::MOVSX, Reg::EAX, Reg::AX)),
+            "char" => text.push(format!("{} {}, {}", Instr::MOVZX, Reg::EAX, Reg::AX)),
+            _ => (),
+        }
+        text.push("".to_owned());
+
+        return Ok(Some(kind));
+    }
+
+    // upcasts always succeed
+    if let Some(from) = annotations.label(operand) {
+        if vtables.assignable(&from, &kind) {
+            text.push("".to_owned());
+            return Ok(Some(kind));
+        }
+    }
+
+    let index = match vtables.subtype(&kind) {
+        Some(i) => i,
+        None => return Err(format!("could not find type descriptor for {}", kind)),
+    };
+
+    let endlabel = format!("cast{}",
+                           rand::thread_rng()
+                               .gen_ascii_chars()
+                               .take(32)
+                               .collect::<String>());
+
+    // null, which has no type descriptor, can be cast to anything
+    text.push(format!("{} {}, [{}-4]", Instr::MOV, Reg::ECX, Reg::ESI));
+    text.push(format!("{} {}, {}", Instr::CMP, Reg::ECX, "0"));
+    text.push(format!("{} .{}", Instr::JE, endlabel));
+
+    // otherwise, the object must be an instance of the type
+    text.push(format!("{} {}, [{}+4*{}]", Instr::MOV, Reg::ECX, Reg::ECX, index));
+    text.push(format!("{} {}, {}", Instr::CMP, Reg::ECX, "0"));
+    text.push(format!("{} {}", Instr::JE, "__exception"));
+    externs.push(format!("{} {}", Instr::EXTERN, "__exception"));
+    text.push(format!(".{}:", endlabel));
+    text.push("".to_owned());
 
-    // TODO<codegen>: kind is lhs (node.children[1] and node.children[2])
-    Ok(None)
+    Ok(Some(kind))
 }
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::helper::call;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -17,11 +20,12 @@ lazy_static! {
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     let params = match node.children.len() {
@@ -29,14 +33,18 @@ pub fn go(node: &ASTNode,
         _ => EMPTYPARAMS.clone(),
     };
 
-    call(&Reg::EBX, // Note: this should not matter, will be overridden
-         &node.children[0],
-         &params,
-         class_label,
-         label,
-         fields,
-         &mut text,
-         &mut externs,
-         &mut bss,
-         &mut data)
+    match call(&Reg::EBX, // Note: this should not matter, will be overridden
+               &node.children[0],
+               &params,
+               class_label,
+               frame,
+               fields,
+               vtables,
+               annotations,
+               &mut text,
+               &mut externs,
+               &mut data) {
+        Ok(_) => Ok(annotations.label(node)),
+        Err(e) => Err(e),
+    }
 }
@@ -1,19 +1,23 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
+use generator::frame::Frame;
 use scanner::ASTNode;
 use scanner::TokenKind;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::vtable::VTables;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     match node.token.kind {
@@ -29,11 +33,12 @@ pub fn go(node: &ASTNode,
     // get lhs
     match statement::go(&node.children[0],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -46,11 +51,12 @@ pub fn go(node: &ASTNode,
     // get rhs
     match statement::go(&node.children[1],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -84,6 +90,5 @@ pub fn go(node: &ASTNode,
 
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is bool
-    Ok(None)
+    Ok(annotations.label(node))
 }
@@ -1,27 +1,32 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     let kind = match statement::go(&node.children[0],
                                    class_label,
-                                   label,
+                                   frame,
                                    fields,
+                                   vtables,
+                                   annotations,
                                    &mut text,
                                    &mut externs,
-                                   &mut bss,
                                    &mut data) {
         Ok(k) => k,
         Err(e) => return Err(e),
@@ -34,11 +39,7 @@ pub fn go(node: &ASTNode,
         //                    node.children[0]));
     }
 
-    let mut kind = kind.unwrap();
-    // TODO<codegen>: resolve parameter types in Env
-    if kind == "String" {
-        kind = "java.lang.String".to_owned();
-    }
+    let kind = kind.unwrap();
 
     if let Some(myfields) = fields.get(&kind) {
         let field = match node.children[2].to_label() {
@@ -64,16 +65,13 @@ pub fn go(node: &ASTNode,
         text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
         text.push("".to_owned());
 
-        return Ok(Some(myfields[fidx.unwrap()].1.clone()));
+        return Ok(annotations.label(node).or(Some(myfields[fidx.unwrap()].1.clone())));
     }
 
     // ArrayTypes
-    // TODO<codegen>: do this properly
-    if kind == "" && node.children[2].clone().token.lexeme.unwrap() == "length" {
+    if kind.ends_with("[]") && node.children[2].clone().token.lexeme.unwrap() == "length" {
         // the length of a field is stored at its base address
-
-        // TODO<codegen>: kind is int
-        return Ok(None);
+        return Ok(annotations.label(node));
     }
 
     Err(format!("could not find field {:?} in {:?}", kind, fields))
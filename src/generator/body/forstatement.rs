@@ -4,8 +4,11 @@ use std::collections::HashMap;
 
 use self::rand::Rng;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
@@ -13,11 +16,12 @@ use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     text.push(format!("  ; for"));
@@ -33,11 +37,12 @@ pub fn go(node: &ASTNode,
 
         match statement::go(&node.children[idxinit],
                             class_label,
-                            label,
+                            frame,
                             fields,
+                            vtables,
+                            annotations,
                             &mut text,
                             &mut externs,
-                            &mut bss,
                             &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -62,11 +67,12 @@ pub fn go(node: &ASTNode,
 
         match statement::go(&node.children[idxcond],
                             class_label,
-                            label,
+                            frame,
                             fields,
+                            vtables,
+                            annotations,
                             &mut text,
                             &mut externs,
-                            &mut bss,
                             &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -79,11 +85,12 @@ pub fn go(node: &ASTNode,
 
     match statement::go(&node.children[idxbody],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -92,11 +99,12 @@ pub fn go(node: &ASTNode,
     if node.children[idxupdt].token.kind != TokenKind::RParen {
         match statement::go(&node.children[idxupdt],
                             class_label,
-                            label,
+                            frame,
                             fields,
+                            vtables,
+                            annotations,
                             &mut text,
                             &mut externs,
-                            &mut bss,
                             &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -1,5 +1,69 @@
+extern crate rand;
+
+use std::collections::HashMap;
+
+use self::rand::Rng;
+
+use analysis::Annotations;
+use generator::asm::Instr;
+use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
-pub fn go(node: &ASTNode) -> Result<Option<String>, String> {
-    Err(format!("NotImplemented Instanceof for node {:?}", node))
+use super::statement;
+
+pub fn go(node: &ASTNode,
+          class_label: &String,
+          frame: &Frame,
+          fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
+          mut text: &mut Vec<String>,
+          mut externs: &mut Vec<String>,
+          mut data: &mut Vec<String>)
+          -> Result<Option<String>, String> {
+    let kind = match annotations.label(&node.children[1]) {
+        Some(k) => k,
+        None => return Err(format!("could not find type of instanceof {:?}", node)),
+    };
+    let index = match vtables.subtype(&kind) {
+        Some(i) => i,
+        None => return Err(format!("could not find type descriptor for {}", kind)),
+    };
+
+    // get object
+    match statement::go(&node.children[0],
+                        class_label,
+                        frame,
+                        fields,
+                        vtables,
+                        annotations,
+                        &mut text,
+                        &mut externs,
+                        &mut data) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    text.push(format!("  ; instanceof {}", kind));
+
+    let endlabel = format!("instanceof{}",
+                           rand::thread_rng()
+                               .gen_ascii_chars()
+                               .take(32)
+                               .collect::<String>());
+
+    // null, which has no type descriptor, is not an instance of anything
+    text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, "0"));
+    text.push(format!("{} {}, [{}-4]", Instr::MOV, Reg::ECX, Reg::ESI));
+    text.push(format!("{} {}, {}", Instr::CMP, Reg::ECX, "0"));
+    text.push(format!("{} .{}", Instr::JE, endlabel));
+
+    // look up the type in the object's type descriptor
+    text.push(format!("{} {}, [{}+4*{}]", Instr::MOV, Reg::EAX, Reg::ECX, index));
+    text.push(format!(".{}:", endlabel));
+    text.push("".to_owned());
+
+    Ok(annotations.label(node))
 }
@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     let kind = match node.children[0].to_param() {
@@ -26,44 +30,32 @@ pub fn go(node: &ASTNode,
 
     text.push(format!("  ; {} {} = x", kind, name));
 
-    let variable = match node.children[1].children[0].to_label() {
-        Ok(l) => format!("{}.{}", label, l),
-        Err(e) => return Err(e),
-    };
-    let vkind = match node.children[0].to_label() {
-        Ok(k) => k,
-        Err(e) => return Err(e),
+    let slot = match frame.get(&node.children[1].children[0], annotations) {
+        Some(s) => s,
+        None => return Err(format!("could not find a slot for {:?} in {}", name, frame.label)),
     };
-    bss.push((variable.clone(), vkind.clone()));
-
-    // allocate 4 bytes for lhs
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, "4"));
-
-    text.push(format!("{} {}", Instr::PUSH, Reg::EBX));
-    externs.push(format!("{} {}", Instr::EXTERN, "__malloc"));
-    text.push(format!("{} {}", Instr::CALL, "__malloc"));
-    text.push(format!("{} {}", Instr::POP, Reg::EBX));
-
-    text.push(format!("{} [{}], {}", Instr::MOV, variable, Reg::EAX));
-    text.push("".to_owned());
+    let vkind = annotations.label(&node.children[1].children[0]).unwrap_or(slot.kind.clone());
 
     // resolve rhs and store in lhs
     match statement::go(&node.children[1].children[1],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
 
-    text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EDI, variable));
-    text.push(format!("{} [{}], {}", Instr::MOV, Reg::EDI, Reg::ESI));
+    if slot.primitive {
+        text.push(format!("{} [{}], {}", Instr::MOV, slot.address(), Reg::EAX));
+    } else {
+        text.push(format!("{} [{}], {}", Instr::MOV, slot.address(), Reg::ESI));
+    }
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is either lhs kind or null
-    Ok(None)
+    Ok(Some(vkind))
 }
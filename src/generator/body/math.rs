@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
@@ -9,11 +12,12 @@ use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     match node.token.kind {
@@ -28,11 +32,12 @@ pub fn go(node: &ASTNode,
     // get lhs
     match statement::go(&node.children[0],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -45,11 +50,12 @@ pub fn go(node: &ASTNode,
     // get rhs
     match statement::go(&node.children[1],
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -83,6 +89,5 @@ pub fn go(node: &ASTNode,
 
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is lhs-ish
-    Ok(None)
+    Ok(annotations.label(node))
 }
@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::helper::call;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     if node.children[0]
@@ -33,11 +37,12 @@ pub fn go(node: &ASTNode,
     // get instance address
     match statement::go(&instance,
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(_) => {
@@ -50,11 +55,12 @@ pub fn go(node: &ASTNode,
 
             match statement::go(&qinstance,
                                 class_label,
-                                label,
+                                frame,
                                 fields,
+                                vtables,
+                                annotations,
                                 &mut text,
                                 &mut externs,
-                                &mut bss,
                                 &mut data) {
                 Ok(_) => (),
                 Err(e) => return Err(e),
@@ -62,14 +68,18 @@ pub fn go(node: &ASTNode,
         }
     }
 
-    call(&Reg::ESI,
-         &node.children[0].children[1],
-         &node.children[2],
-         class_label,
-         label,
-         fields,
-         &mut text,
-         &mut externs,
-         &mut bss,
-         &mut data)
+    match call(&Reg::ESI,
+               &node.children[0].children[1],
+               &node.children[2],
+               class_label,
+               frame,
+               fields,
+               vtables,
+               annotations,
+               &mut text,
+               &mut externs,
+               &mut data) {
+        Ok(_) => Ok(annotations.label(node)),
+        Err(e) => Err(e),
+    }
 }
@@ -1,43 +1,52 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     if node.clone().token.lexeme.unwrap_or("".to_owned()) == "FieldAccess" {
         return Err(format!("could not resolve node"));
     }
 
+    let kind = annotations.label(node);
+
     let mut node = node.clone();
     node.flatten();
 
-    let (field, variable) = match node.to_label() {
-        Ok(l) => (l.clone(), format!("{}.{}", label, l)),
+    let field = match node.to_label() {
+        Ok(l) => l,
         Err(e) => return Err(e),
     };
 
-    let vidx = bss.iter().position(|v| v.0 == variable);
-    if vidx.is_some() {
-        // local
-        text.push(format!("  ; {}", variable));
+    if let Some(slot) = frame.get(&node, annotations) {
+        // local or parameter
+        text.push(format!("  ; {}", field));
 
-        text.push(format!("{} {}, [{}]", Instr::MOV, Reg::ESI, variable));
-        text.push(format!("{} {}, [{}]", Instr::MOV, Reg::ESI, Reg::ESI));
+        // a primitive is stored in its slot, anything else is pointed to
+        if slot.primitive {
+            text.push(format!("{} {}, [{}]", Instr::LEA, Reg::ESI, slot.address()));
+        } else {
+            text.push(format!("{} {}, [{}]", Instr::MOV, Reg::ESI, slot.address()));
+        }
         text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
         text.push("".to_owned());
 
-        return Ok(Some(bss[vidx.unwrap()].1.clone()));
+        return Ok(kind.or(Some(slot.kind.clone())));
     }
 
     if let Some(myfields) = fields.get(class_label) {
@@ -54,7 +63,7 @@ pub fn go(node: &ASTNode,
             text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
             text.push("".to_owned());
 
-            return Ok(Some(myfields[fidx.unwrap()].1.clone()));
+            return Ok(kind.or(Some(myfields[fidx.unwrap()].1.clone())));
         }
     }
 
@@ -76,10 +85,11 @@ pub fn go(node: &ASTNode,
     node.token.lexeme = Some("FieldAccess".to_owned());
     statement::go(&node,
                   class_label,
-                  label,
+                  frame,
                   fields,
+                  vtables,
+                  annotations,
                   &mut text,
                   &mut externs,
-                  &mut bss,
                   &mut data)
 }
@@ -1,26 +1,32 @@
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use scanner::ASTNode;
 
-pub fn go(mut text: &mut Vec<String>,
+pub fn go(node: &ASTNode,
+          annotations: &Annotations,
+          mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>)
           -> Result<Option<String>, String> {
     text.push(format!("  ; null"));
 
-    // allocate 4 bytes for num
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, "4"));
+    // allocate 4 bytes for num and 4 bytes for its type descriptor
+    text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, "8"));
 
     text.push(format!("{} {}", Instr::PUSH, Reg::EBX));
     externs.push(format!("{} {}", Instr::EXTERN, "__malloc"));
     text.push(format!("{} {}", Instr::CALL, "__malloc"));
     text.push(format!("{} {}", Instr::POP, Reg::EBX));
 
+    // null has no type descriptor
+    text.push(format!("{} dword [{}], {}", Instr::MOV, Reg::EAX, "0"));
+
     // store value in memory
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::ESI, Reg::EAX));
+    text.push(format!("{} {}, [{}+4]", Instr::LEA, Reg::ESI, Reg::EAX));
     text.push(format!("{} dword [{}], {}", Instr::MOV, Reg::ESI, "0xDEADBEEF"));
 
     text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is null
-    Ok(None)
+    Ok(annotations.label(node))
 }
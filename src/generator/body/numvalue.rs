@@ -1,8 +1,10 @@
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
 use scanner::ASTNode;
 
 pub fn go(node: &ASTNode,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>)
           -> Result<Option<String>, String> {
@@ -29,6 +31,5 @@ pub fn go(node: &ASTNode,
     text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EAX, Reg::ESI));
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is int/short
-    Ok(None)
+    Ok(annotations.label(node))
 }
@@ -1,41 +1,46 @@
 use std::collections::HashMap;
 
-use generator::asm::Instr;
+use analysis::Annotations;
+use generator::asm::helper::leave;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 
 use super::statement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
-    match node.children.len() {
+    let kind = match node.children.len() {
         3 => {
             match statement::go(&node.children[1],
                                 class_label,
-                                label,
+                                frame,
                                 fields,
+                                vtables,
+                                annotations,
                                 &mut text,
                                 &mut externs,
-                                &mut bss,
                                 &mut data) {
-                Ok(_) => (),
+                Ok(k) => k,
                 Err(e) => return Err(e),
             }
         }
         _ => {
             // TODO<codegen>: ret a specific value, maybe 0?
+            None
         }
-    }
+    };
 
-    text.push(format!("{}", Instr::RET));
+    leave(&mut text);
     text.push("".to_owned());
 
-    // TODO<codegen>: kind is null or kind of child
-    Ok(None)
+    Ok(kind)
 }
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::TokenKind;
 
@@ -31,11 +34,12 @@ use super::whilestatement;
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     match node.token.kind {
@@ -44,62 +48,68 @@ pub fn go(node: &ASTNode,
                 Some(ref l) if l == "Argument" => {
                     go(&node.children[1],
                        class_label,
-                       label,
+                       frame,
                        fields,
+                       vtables,
+                       annotations,
                        &mut text,
                        &mut externs,
-                       &mut bss,
                        &mut data)
                 }
                 Some(ref l) if l == "ArrayAccess" => {
                     arrayaccess::go(&node,
                                     class_label,
-                                    label,
+                                    frame,
                                     fields,
+                                    vtables,
+                                    annotations,
                                     &mut text,
                                     &mut externs,
-                                    &mut bss,
                                     &mut data)
                 }
                 Some(ref l) if l == "ArrayCreationExpression" => {
                     arraycreationexpression::go(&node,
                                                 class_label,
-                                                label,
+                                                frame,
                                                 fields,
+                                                vtables,
+                                                annotations,
                                                 &mut text,
                                                 &mut externs,
-                                                &mut bss,
                                                 &mut data)
                 }
                 Some(ref l) if l == "Assignment" => {
                     assignment::go(&node,
                                    class_label,
-                                   label,
+                                   frame,
                                    fields,
+                                   vtables,
+                                   annotations,
                                    &mut text,
                                    &mut externs,
-                                   &mut bss,
                                    &mut data)
                 }
                 Some(ref l) if l == "Block" && node.children.len() == 3 => {
                     go(&node.children[1],
                        class_label,
-                       label,
+                       frame,
                        fields,
+                       vtables,
+                       annotations,
                        &mut text,
                        &mut externs,
-                       &mut bss,
                        &mut data)
                 }
                 Some(ref l) if l == "BlockStatements" => {
                     for child in &node.children {
                         match go(&child,
                                  class_label,
-                                 label,
+                                 frame,
                                  fields,
+                                 vtables,
+                                 annotations,
                                  &mut text,
                                  &mut externs,
-                                 &mut bss,
                                  &mut data) {
                             Ok(_) => (),
                             Err(e) => return Err(e),
@@ -110,111 +120,122 @@ pub fn go(node: &ASTNode,
                 Some(ref l) if l == "CastExpression" => {
                     castexpression::go(&node,
                                        class_label,
-                                       label,
+                                       frame,
                                        fields,
+                                       vtables,
+                                       annotations,
                                        &mut text,
                                        &mut externs,
-                                       &mut bss,
                                        &mut data)
                 }
                 Some(ref l) if l == "ClassInstanceCreationExpression" => {
                     classinstancecreationexpression::go(&node,
                                                         class_label,
-                                                        label,
+                                                        frame,
                                                         fields,
+                                                        vtables,
+                                                        annotations,
                                                         &mut text,
                                                         &mut externs,
-                                                        &mut bss,
                                                         &mut data)
                 }
                 Some(ref l) if l == "FieldAccess" => {
                     fieldaccess::go(&node,
                                     class_label,
-                                    label,
+                                    frame,
                                     fields,
+                                    vtables,
+                                    annotations,
                                     &mut text,
                                     &mut externs,
-                                    &mut bss,
                                     &mut data)
                 }
                 Some(ref l) if l == "ForStatement" || l == "ForStatementNoShortIf" => {
                     forstatement::go(&node,
                                      class_label,
-                                     label,
+                                     frame,
                                      fields,
+                                     vtables,
+                                     annotations,
                                      &mut text,
                                      &mut externs,
-                                     &mut bss,
                                      &mut data)
                 }
                 Some(ref l) if l == "IfElseStatement" || l == "IfElseStatementNoShortIf" => {
                     ifelsestatement::go(&node,
                                         class_label,
-                                        label,
+                                        frame,
                                         fields,
+                                        vtables,
+                                        annotations,
                                         &mut text,
                                         &mut externs,
-                                        &mut bss,
                                         &mut data)
                 }
                 Some(ref l) if l == "IfStatement" => {
                     ifstatement::go(&node,
                                     class_label,
-                                    label,
+                                    frame,
                                     fields,
+                                    vtables,
+                                    annotations,
                                     &mut text,
                                     &mut externs,
-                                    &mut bss,
                                     &mut data)
                 }
                 Some(ref l) if l == "LocalVariableDeclaration" => {
                     localvariabledeclaration::go(&node,
                                                  class_label,
-                                                 label,
+                                                 frame,
                                                  fields,
+                                                 vtables,
+                                                 annotations,
                                                  &mut text,
                                                  &mut externs,
-                                                 &mut bss,
                                                  &mut data)
                 }
                 Some(ref l) if l == "MethodInvocation" => {
                     methodinvocation::go(&node,
                                          class_label,
-                                         label,
+                                         frame,
                                          fields,
+                                         vtables,
+                                         annotations,
                                          &mut text,
                                          &mut externs,
-                                         &mut bss,
                                          &mut data)
                 }
                 Some(ref l) if l == "Name" => {
                     name::go(&node,
                              class_label,
-                             label,
+                             frame,
                              fields,
+                             vtables,
+                             annotations,
                              &mut text,
                              &mut externs,
-                             &mut bss,
                              &mut data)
                 }
                 Some(ref l) if l == "ReturnStatement" => {
                     returnstatement::go(&node,
                                         class_label,
-                                        label,
+                                        frame,
                                         fields,
+                                        vtables,
+                                        annotations,
                                         &mut text,
                                         &mut externs,
-                                        &mut bss,
                                         &mut data)
                 }
                 Some(ref l) if l == "WhileStatement" || l == "WhileStatementNoShortIf" => {
                     whilestatement::go(&node,
                                        class_label,
-                                       label,
+                                       frame,
                                        fields,
+                                       vtables,
+                                       annotations,
                                        &mut text,
                                        &mut externs,
-                                       &mut bss,
                                        &mut data)
                 }
 
@@ -226,14 +247,15 @@ pub fn go(node: &ASTNode,
         TokenKind::BitXor => {
             booleanoperation::go(&node,
                                  class_label,
-                                 label,
+                                 frame,
                                  fields,
+                                 vtables,
+                                 annotations,
                                  &mut text,
                                  &mut externs,
-                                 &mut bss,
                                  &mut data)
         }
-        TokenKind::CharValue => charvalue::go(&node, &mut text, &mut externs),
+        TokenKind::CharValue => charvalue::go(&node, annotations, &mut text, &mut externs),
         TokenKind::Equality |
         TokenKind::NotEqual |
         TokenKind::LessThan |
@@ -242,56 +264,73 @@ pub fn go(node: &ASTNode,
         TokenKind::GreaterThanOrEqual => {
             comparison::go(&node,
                            class_label,
-                           label,
+                           frame,
                            fields,
+                           vtables,
+                           annotations,
                            &mut text,
                            &mut externs,
-                           &mut bss,
                            &mut data)
         }
-        TokenKind::False | TokenKind::True => booleanvalue::go(&node, &mut text, &mut externs),
+        TokenKind::False | TokenKind::True => {
+            booleanvalue::go(&node, annotations, &mut text, &mut externs)
+        }
         TokenKind::Identifier => {
             name::go(&node,
                      class_label,
-                     label,
+                     frame,
                      fields,
+                     vtables,
+                     annotations,
                      &mut text,
                      &mut externs,
-                     &mut bss,
                      &mut data)
         }
-        TokenKind::Instanceof => instanceof::go(&node),
+        TokenKind::Instanceof => {
+            instanceof::go(&node,
+                           class_label,
+                           frame,
+                           fields,
+                           vtables,
+                           annotations,
+                           &mut text,
+                           &mut externs,
+                           &mut data)
+        }
         TokenKind::FSlash | TokenKind::Minus | TokenKind::Percent | TokenKind::Plus |
         TokenKind::Star => {
             math::go(&node,
                      class_label,
-                     label,
+                     frame,
                      fields,
+                     vtables,
+                     annotations,
                      &mut text,
                      &mut externs,
-                     &mut bss,
                      &mut data)
         }
         TokenKind::Not => {
             not::go(&node,
                     class_label,
-                    label,
+                    frame,
                     fields,
+                    vtables,
+                    annotations,
                     &mut text,
                     &mut externs,
-                    &mut bss,
                     &mut data)
         }
-        TokenKind::Null => nullvalue::go(&mut text, &mut externs),
-        TokenKind::NumValue => numvalue::go(&node, &mut text, &mut externs),
+        TokenKind::Null => nullvalue::go(&node, annotations, &mut text, &mut externs),
+        TokenKind::NumValue => numvalue::go(&node, annotations, &mut text, &mut externs),
         TokenKind::StrValue => {
             strvalue::go(&node,
                          class_label,
-                         label,
+                         frame,
                          fields,
+                         vtables,
+                         annotations,
                          &mut text,
                          &mut externs,
-                         &mut bss,
                          &mut data)
         }
         TokenKind::This => this::go(class_label, &mut text),
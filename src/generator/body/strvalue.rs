@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use generator::asm::Instr;
 use generator::asm::Reg;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -14,7 +17,7 @@ lazy_static! {
             token: Token::new(TokenKind::NonTerminal, Some("ArrayCreationExpression")),
             children: vec![
                 ASTNode {
-                    token: Token::new(TokenKind::NonTerminal, Some("TODO")),
+                    token: Token::new(TokenKind::Char, None),
                     children: Vec::new(),
                 },
                 ASTNode {
@@ -77,11 +80,12 @@ lazy_static! {
 
 pub fn go(node: &ASTNode,
           class_label: &String,
-          label: &String,
+          frame: &Frame,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<String>, String> {
     if node.token.lexeme.is_none() {
@@ -99,6 +103,7 @@ pub fn go(node: &ASTNode,
                   token: Token {
                       kind: TokenKind::NumValue,
                       lexeme: Some(chars.len().to_string()),
+                      span: None,
                   },
                   children: Vec::new(),
               });
@@ -106,11 +111,12 @@ pub fn go(node: &ASTNode,
     // create chars array
     match statement::go(&charinstantiation,
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -123,11 +129,12 @@ pub fn go(node: &ASTNode,
     let instantiation = STRINGINSTANTIATION.clone();
     match statement::go(&instantiation,
                         class_label,
-                        label,
+                        frame,
                         fields,
+                        vtables,
+                        annotations,
                         &mut text,
                         &mut externs,
-                        &mut bss,
                         &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -145,6 +152,5 @@ pub fn go(node: &ASTNode,
                           ch));
     }
 
-    // TODO<codegen>: kind is string
-    Ok(None)
+    Ok(annotations.label(node))
 }
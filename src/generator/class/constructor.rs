@@ -1,10 +1,14 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use analysis::MethodEnvironment;
 use generator::asm::helper::call;
+use generator::asm::helper::leave;
 use generator::asm::Instr;
 use generator::asm::Reg;
 use generator::body;
+use generator::vtable;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -24,9 +28,10 @@ pub fn go(method: &MethodEnvironment,
           class_label: &String,
           init_fields: &Vec<(String, ASTNode)>,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<(), String> {
     let label = match method::get_label(method, &class_label, &mut text, &mut externs) {
@@ -34,22 +39,19 @@ pub fn go(method: &MethodEnvironment,
         Err(e) => return Err(e),
     };
 
-    match method::get_args(&method.parameters,
-                           &label,
-                           &mut text,
-                           &mut externs,
-                           &mut bss) {
-        Ok(_) => (),
+    let frame = match method::get_frame(method, &label, annotations, &mut text) {
+        Ok(f) => f,
         Err(e) => return Err(e),
-    }
+    };
 
-    // allocate 32 bytes for this and 32 bytes for each field
+    // allocate 32 bytes for the type descriptor, 32 bytes for the vtable and
+    // 32 bytes for each field
     let myfields = fields.get(class_label);
     if myfields.is_none() {
         return Err(format!("could not find own fields for {:?}", class_label));
     }
 
-    let space = 4 * (myfields.unwrap().len() + 1);
+    let space = 4 * (myfields.unwrap().len() + 2);
     text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, space));
 
     text.push(format!("{} {}", Instr::PUSH, Reg::EBX));
@@ -57,8 +59,13 @@ pub fn go(method: &MethodEnvironment,
     text.push(format!("{} {}", Instr::CALL, "__malloc"));
     text.push(format!("{} {}", Instr::POP, Reg::EBX));
 
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::EBX, Reg::EAX));
-    text.push(format!("{} dword [{}], {}", Instr::MOV, Reg::EBX, "0xBAADCAFE"));
+    // the type descriptor is kept before the object
+    text.push(format!("{} dword [{}], {}",
+                      Instr::MOV,
+                      Reg::EAX,
+                      vtable::descriptor(class_label)));
+    text.push(format!("{} {}, [{}+4]", Instr::LEA, Reg::EBX, Reg::EAX));
+    text.push(format!("{} dword [{}], {}", Instr::MOV, Reg::EBX, vtable::label(class_label)));
     text.push("".to_owned());
 
     // call parent constructor
@@ -77,11 +84,12 @@ pub fn go(method: &MethodEnvironment,
                    &p,
                    &EMPTYPARAMS.clone(),
                    &class_label,
-                   &label,
+                   &frame,
                    &fields,
+                   &vtables,
+                   annotations,
                    &mut text,
                    &mut externs,
-                   &mut bss,
                    &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -114,11 +122,12 @@ pub fn go(method: &MethodEnvironment,
                    &init,
                    &EMPTYPARAMS.clone(),
                    &class_label,
-                   &label,
+                   &frame,
                    &fields,
+                   &vtables,
+                   annotations,
                    &mut text,
                    &mut externs,
-                   &mut bss,
                    &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -151,11 +160,12 @@ pub fn go(method: &MethodEnvironment,
     if let Some(b) = method.body.clone() {
         match body::go(&b,
                        &class_label,
-                       &label,
+                       &frame,
                        &fields,
+                       &vtables,
+                       annotations,
                        &mut text,
                        &mut externs,
-                       &mut bss,
                        &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -165,7 +175,7 @@ pub fn go(method: &MethodEnvironment,
     // return this
     text.push(format!("{} {}, {}", Instr::MOV, Reg::ESI, Reg::EBX));
     text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, Reg::EBX)); // TODO<codegen>: verify
-    text.push(format!("{}", Instr::RET));
+    leave(&mut text);
     text.push("".to_owned());
 
     Ok(())
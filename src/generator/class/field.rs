@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use analysis::FieldEnvironment;
 use analysis::MethodEnvironment;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -31,9 +33,10 @@ lazy_static! {
 
 pub fn go(field: &FieldEnvironment,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<Option<ASTNode>, String> {
     let mut init = field.name.clone();
@@ -59,9 +62,10 @@ pub fn go(field: &FieldEnvironment,
     match method::go(&init_method,
                      &init_label,
                      &fields,
+                     &vtables,
+                     annotations,
                      &mut text,
                      &mut externs,
-                     &mut bss,
                      &mut data) {
         Ok(_) => (),
         Err(e) => return Err(e),
@@ -1,10 +1,14 @@
 use std::collections::HashMap;
 
+use analysis::Annotations;
 use analysis::MethodEnvironment;
-use analysis::VariableEnvironment;
+use generator::asm::helper::enter;
+use generator::asm::helper::leave;
 use generator::asm::Instr;
 use generator::asm::Reg;
 use generator::body;
+use generator::frame::Frame;
+use generator::vtable::VTables;
 use scanner::ASTNode;
 use scanner::Token;
 use scanner::TokenKind;
@@ -36,11 +40,7 @@ fn build_entrypoint(class_label: &String,
     text.push(format!("{} dword {}, {}", Instr::MOV, Reg::EBX, "0xC0DEBABE"));
     text.push(format!("{} {}", Instr::PUSH, Reg::EBX)); // fake this param
 
-    text.push(format!("{} {}", Instr::PUSH, Reg::EBP));
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::EBP, Reg::ESP));
     text.push(format!("{} {}", Instr::CALL, constructor_label));
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::ESP, Reg::EBP));
-    text.push(format!("{} {}", Instr::POP, Reg::EBP));
 
     text.push(format!("{} {}", Instr::POP, Reg::EBX)); // fake this param
     text.push("".to_owned());
@@ -48,11 +48,7 @@ fn build_entrypoint(class_label: &String,
     // call this method
     text.push(format!("{} {}", Instr::PUSH, Reg::EAX)); // real this param
 
-    text.push(format!("{} {}", Instr::PUSH, Reg::EBP));
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::EBP, Reg::ESP));
     text.push(format!("{} {}", Instr::CALL, label));
-    text.push(format!("{} {}, {}", Instr::MOV, Reg::ESP, Reg::EBP));
-    text.push(format!("{} {}", Instr::POP, Reg::EBP));
 
     // text.push(format!("{} {}", Instr::POP, Reg::EBX)); // real this param
     text.push("".to_owned());
@@ -64,48 +60,25 @@ fn build_entrypoint(class_label: &String,
     text.push("".to_owned());
 }
 
-pub fn get_args(parameters: &Vec<VariableEnvironment>,
-                label: &String,
-                mut text: &mut Vec<String>,
-                mut externs: &mut Vec<String>,
-                mut bss: &mut Vec<(String, String)>)
-                -> Result<(), String> {
-    text.push(format!("  ; get args"));
-    for (idx, param) in parameters.iter().enumerate().rev() {
-        let variable = match param.name.to_label() {
-            Ok(l) => format!("{}.{}", label, l),
-            Err(e) => return Err(e),
-        };
-        let pkind = match param.kind.to_label() {
-            Ok(k) => k,
-            Err(e) => return Err(e),
-        };
-        bss.push((variable.clone(), pkind.clone()));
-
-        externs.push(format!("{} {}", Instr::EXTERN, "__malloc"));
-
-        // allocate space for variable
-        text.push(format!("{} {}, {}", Instr::MOV, Reg::EAX, "4"));
-        text.push(format!("{} {}", Instr::CALL, "__malloc"));
+/// Lays out the stack frame of a method and enters it, loading `this` into
+/// `ebx`.
+pub fn get_frame(method: &MethodEnvironment,
+                 label: &String,
+                 annotations: &Annotations,
+                 mut text: &mut Vec<String>)
+                 -> Result<Frame, String> {
+    let frame = match Frame::new(label, &method.parameters, method.body.as_ref(), annotations) {
+        Ok(f) => f,
+        Err(e) => return Err(e),
+    };
 
-        // put address of variable in new space
-        // 0:"esp", 4:"null", 8:"argx", ... n:"arg0", n+4:"this param", n+8:"this"
-        text.push(format!("{} {}, [{}+4*{}]", Instr::MOV, Reg::EBX, Reg::ESP, idx + 2));
-        text.push(format!("{} [{}], {}", Instr::MOV, Reg::EAX, Reg::EBX));
-
-        text.push(format!("{} [{}], {}", Instr::MOV, variable, Reg::EAX));
-        text.push("".to_owned());
-    }
+    enter(&frame, &mut text);
 
     text.push(format!("  ; get this"));
-    text.push(format!("{} {}, [{}+4*{}]",
-                      Instr::MOV,
-                      Reg::EBX,
-                      Reg::ESP,
-                      parameters.len() + 2));
+    text.push(format!("{} {}, [{}]", Instr::MOV, Reg::EBX, frame.this()));
     text.push("".to_owned());
 
-    Ok(())
+    Ok(frame)
 }
 
 pub fn get_label(method: &MethodEnvironment,
@@ -127,9 +100,10 @@ pub fn get_label(method: &MethodEnvironment,
 pub fn go(method: &MethodEnvironment,
           class_label: &String,
           fields: &HashMap<String, Vec<(String, String)>>,
+          vtables: &VTables,
+          annotations: &Annotations,
           mut text: &mut Vec<String>,
           mut externs: &mut Vec<String>,
-          mut bss: &mut Vec<(String, String)>,
           mut data: &mut Vec<String>)
           -> Result<(), String> {
     let label = match get_label(method, &class_label, &mut text, &mut externs) {
@@ -137,30 +111,31 @@ pub fn go(method: &MethodEnvironment,
         Err(e) => return Err(e),
     };
 
-    match get_args(&method.parameters,
-                   &label,
-                   &mut text,
-                   &mut externs,
-                   &mut bss) {
-        Ok(_) => (),
+    let frame = match get_frame(method, &label, annotations, &mut text) {
+        Ok(f) => f,
         Err(e) => return Err(e),
-    }
+    };
 
     // generate body
     if let Some(b) = method.body.clone() {
         match body::go(&b,
                        &class_label,
-                       &label,
+                       &frame,
                        &fields,
+                       &vtables,
+                       annotations,
                        &mut text,
                        &mut externs,
-                       &mut bss,
                        &mut data) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
     }
 
+    // a void method may complete without a return statement
+    leave(&mut text);
+    text.push("".to_owned());
+
     if method.modifiers.contains(&*STATIC) && method.return_type == *INTEGER &&
        method.name == *TEST {
         build_entrypoint(&class_label, &label, text, externs);
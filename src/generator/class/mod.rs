@@ -6,7 +6,6 @@ pub mod method;
 
 pub fn code(text: &Vec<String>,
             externs: &Vec<String>,
-            bss: &Vec<(String, String)>,
             data: &Vec<String>)
             -> String {
     let mut generated = Vec::new();
@@ -36,18 +35,6 @@ pub fn code(text: &Vec<String>,
 
     generated.push(text.join("\n"));
 
-    if !bss.is_empty() {
-        let mut bss: Vec<String> = bss.iter()
-            .map(|v| format!("{}: resb {}", v.0, "16"))
-            .collect();
-        bss.sort();
-        bss.dedup();
-
-        bss.insert(0, format!("{} .{}", Instr::SECTION, "bss"));
-
-        generated.push(bss.join("\n"));
-    }
-
     // TODO<codegen>: dedup data by value
     if !data.is_empty() {
         let mut data = data.clone();
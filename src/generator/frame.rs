@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use analysis::Annotations;
+use analysis::Declaration;
+use analysis::VariableEnvironment;
+use scanner::ASTNode;
+use scanner::Span;
+use scanner::TokenKind;
+
+/// Whether values of the named type are stored directly, rather than as a
+/// pointer to an object, array or boxed value.
+pub fn primitive(kind: &str) -> bool {
+    match kind {
+        "boolean" | "byte" | "char" | "int" | "short" => true,
+        _ => false,
+    }
+}
+
+/// Where a parameter or local variable lives, relative to `ebp`.
+#[derive(Clone,Debug)]
+pub struct Slot {
+    pub offset: i32,
+    /// the declared type of the variable, eg. `int` or `java.lang.String[]`
+    pub kind: String,
+    /// whether the slot holds the value itself, rather than a pointer to it
+    pub primitive: bool,
+}
+
+impl Slot {
+    fn new(variable: &VariableEnvironment,
+           offset: i32,
+           annotations: &Annotations)
+           -> Result<Slot, String> {
+        let kind = match annotations.label(&variable.name) {
+            Some(k) => k,
+            None => {
+                match variable.kind.to_label() {
+                    Ok(k) if variable.dim => format!("{}[]", k),
+                    Ok(k) => k,
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+
+        Ok(Slot {
+               offset: offset,
+               primitive: primitive(&kind),
+               kind: kind,
+           })
+    }
+
+    /// The address of the slot, eg. `ebp+8` or `ebp-4`.
+    pub fn address(&self) -> String {
+        if self.offset < 0 {
+            format!("ebp{}", self.offset)
+        } else {
+            format!("ebp+{}", self.offset)
+        }
+    }
+}
+
+/// The stack frame of a single activation of a method or constructor.
+///
+/// The caller pushes its `this`, the receiver and then each argument in reverse
+/// order before calling the method, which saves `ebp` and reserves space below
+/// it for every local variable:
+///
+/// ```text
+/// ebp+12+4n  caller's this
+/// ebp+8+4n   this
+/// ebp+8+4i   argument i
+/// ebp+4      return address
+/// ebp        caller's ebp
+/// ebp-4-4j   local variable j
+/// ```
+///
+/// Each local variable declared in the body has its own slot, even if another
+/// variable of the same name is declared in a disjoint scope, since the two
+/// may have different types. Slots are found by the span of the declaration a
+/// name refers to.
+pub struct Frame {
+    /// the label of the method this frame belongs to
+    pub label: String,
+    parameters: usize,
+    locals: usize,
+    slots: HashMap<Span, Slot>,
+}
+
+impl Frame {
+    pub fn new(label: &String,
+               parameters: &Vec<VariableEnvironment>,
+               body: Option<&ASTNode>,
+               annotations: &Annotations)
+               -> Result<Frame, String> {
+        let mut frame = Frame {
+            label: label.clone(),
+            parameters: parameters.len(),
+            locals: 0,
+            slots: HashMap::new(),
+        };
+
+        for (idx, parameter) in parameters.iter().enumerate() {
+            match frame.insert(parameter, 8 + 4 * idx as i32, annotations) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut locals = Vec::new();
+        if let Some(body) = body {
+            declared(body, &mut locals);
+        }
+        for local in &locals {
+            frame.locals += 1;
+            let offset = -4 * frame.locals as i32;
+            match frame.insert(local, offset, annotations) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(frame)
+    }
+
+    fn insert(&mut self,
+              variable: &VariableEnvironment,
+              offset: i32,
+              annotations: &Annotations)
+              -> Result<(), String> {
+        let span = match variable.span() {
+            Some(s) => s,
+            None => {
+                let name = variable.name.source_text();
+                return Err(format!("{} has no span to key its slot", name));
+            }
+        };
+        let slot = match Slot::new(variable, offset, annotations) {
+            Ok(s) => s,
+            Err(e) => return Err(e),
+        };
+
+        self.slots.insert(span, slot);
+        Ok(())
+    }
+
+    /// The slot of the parameter or local variable a name refers to, as found
+    /// by type checking, or None if it refers to anything else.
+    pub fn get(&self, name: &ASTNode, annotations: &Annotations) -> Option<&Slot> {
+        // a Name keeps the span of the whole name, even once it has been
+        // stripped down to the identifier it starts with
+        let name = match name.children.len() {
+            1 => &name.children[0],
+            _ => name,
+        };
+        let declaration = match annotations.get(name) {
+            Some(annotation) => annotation.declaration.as_ref(),
+            None => None,
+        };
+
+        match declaration {
+            Some(&Declaration::Local(ref local)) => {
+                match local.span() {
+                    Some(span) => self.slots.get(&span),
+                    None => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The number of bytes reserved below `ebp` for local variables.
+    pub fn size(&self) -> usize {
+        4 * self.locals
+    }
+
+    /// The address of the receiver, ie. `this`.
+    pub fn this(&self) -> String {
+        format!("ebp+{}", 8 + 4 * self.parameters)
+    }
+}
+
+fn declared(node: &ASTNode, locals: &mut Vec<VariableEnvironment>) {
+    if node.token.kind == TokenKind::NonTerminal &&
+       node.token.lexeme == Some("LocalVariableDeclaration".to_owned()) {
+        locals.push(VariableEnvironment::new(node.clone()));
+    }
+    for child in &node.children {
+        declared(child, locals);
+    }
+}
@@ -1,19 +1,24 @@
 //! The code generation module for juicyj. Roughly corresponds to assignment 5
 //! of the original CS444 project.
-extern crate walkdir;
 
 mod asm;
 mod body;
 mod class;
+mod frame;
+mod vtable;
 
-use std;
 use std::collections::HashMap;
-use std::fs;
-use std::io::Write;
 
+use analysis::Annotations;
 use analysis::ClassOrInterfaceEnvironment;
 use analysis::Environment;
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+use generator::asm::Instr;
+use generator::vtable::VTables;
 use scanner::ASTNode;
+use scanner::Span;
 use scanner::Token;
 use scanner::TokenKind;
 
@@ -26,22 +31,32 @@ lazy_static! {
 trait Generatable {
     fn generate(&self,
                 label: &String,
-                fields: &HashMap<String, Vec<(String, String)>>)
-                -> Result<String, String>;
+                fields: &HashMap<String, Vec<(String, String)>>,
+                vtables: &VTables,
+                annotations: &Annotations)
+                -> Result<String, Vec<Diagnostic>>;
 }
 
 impl Generatable for ClassOrInterfaceEnvironment {
     fn generate(&self,
                 label: &String,
-                fields: &HashMap<String, Vec<(String, String)>>)
-                -> Result<String, String> {
-        let mut bss = Vec::new();
+                fields: &HashMap<String, Vec<(String, String)>>,
+                vtables: &VTables,
+                annotations: &Annotations)
+                -> Result<String, Vec<Diagnostic>> {
+        let mut errors = Vec::new();
+
         let mut data = Vec::new();
         let mut externs = Vec::new();
         let mut text = Vec::new();
 
         // externs.push(format!("extern {}", "__NATIVEjava.io.OutputStream.nativeWrite"));
 
+        for (table, entry) in vtables.data(label) {
+            externs.push(format!("{} {}", Instr::GLOBAL, table));
+            data.push(entry);
+        }
+
         let mut init_fields = Vec::new();
         for field in &self.fields {
             let mut name = self.name.clone();
@@ -54,13 +69,17 @@ impl Generatable for ClassOrInterfaceEnvironment {
 
             let flabel = match field.name.to_label() {
                 Ok(l) => l,
-                Err(e) => return Err(e),
+                Err(e) => {
+                    errors.push(error(e, field.span()));
+                    continue;
+                }
             };
             match class::field::go(&field,
                                    &fields,
+                                   &vtables,
+                                   annotations,
                                    &mut text,
                                    &mut externs,
-                                   &mut bss,
                                    &mut data) {
                 Ok(Some(n)) => {
                     let mut name = field.name.clone();
@@ -69,7 +88,7 @@ impl Generatable for ClassOrInterfaceEnvironment {
                     init_fields.push((flabel.clone(), name.clone()))
                 }
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => errors.push(error(e, field.span())),
             }
         }
 
@@ -78,12 +97,13 @@ impl Generatable for ClassOrInterfaceEnvironment {
                                          &label,
                                          &init_fields,
                                          &fields,
+                                         &vtables,
+                                         annotations,
                                          &mut text,
                                          &mut externs,
-                                         &mut bss,
                                          &mut data) {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => errors.push(error(e, constructor.span())),
             }
         }
 
@@ -91,74 +111,68 @@ impl Generatable for ClassOrInterfaceEnvironment {
             match class::method::go(&method,
                                     &label,
                                     &fields,
+                                    &vtables,
+                                    annotations,
                                     &mut text,
                                     &mut externs,
-                                    &mut bss,
                                     &mut data) {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => errors.push(error(e, method.span())),
             }
         }
 
-        Ok(class::code(&text, &externs, &bss, &data))
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(class::code(&text, &externs, &data))
     }
 }
 
-/// Runs an Environment through code generation and exits with code 42 on a
-/// failure. If no failure exists, the compiled files will be located in the
-/// `output/` subdirectory in the current working directory.
-pub fn generate_or_exit(env: &Environment) {
-    match fs::create_dir_all("output") {
-        Ok(_) => (),
-        Err(e) => {
-            println!("{}", e);
-            std::process::exit(42);
-        }
-    };
+fn error(message: String, span: Option<Span>) -> Diagnostic {
+    Diagnostic::new(Phase::Codegen, ErrorMessage::CodegenError(message), span)
+}
 
-    for path in walkdir::WalkDir::new("output") {
-        match path.unwrap().path().to_str() {
-            Some(filename) if filename != "output" => {
-                if fs::remove_file(filename).is_err() {
-                    println!("Could not remove file 'output/{}'", filename);
-                    std::process::exit(42);
-                }
-            }
-            _ => (),
-        }
-    }
+/// Runs an Environment through code generation, reporting every error found.
+/// Returns the assembly for each class, along with the name of the file it
+/// should be written to.
+pub fn generate(env: &Environment) -> Result<Vec<(String, String)>, Vec<Diagnostic>> {
+    let mut errors = Vec::new();
 
     let mut fields = HashMap::new();
     for kind in &env.kinds {
         let label = match kind.name.to_label() {
             Ok(l) => l,
             Err(e) => {
-                println!("{:?}", e);
-                std::process::exit(42);
+                errors.push(error(e, kind.span()));
+                continue;
             }
         };
 
-        fields.insert(label,
-                      kind.fields
-                          .iter()
-                          .map(|fld| match fld.name.to_label() {
-                                   Ok(n) => {
-                                       match fld.kind.to_label() {
-                                           Ok(k) => (n, k),
-                                           Err(e) => {
-            println!("{:?}", e);
-            std::process::exit(42);
-        }
-                                       }
-                                   }
-                                   Err(e) => {
-            println!("{:?}", e);
-            std::process::exit(42);
+        let mut labels = Vec::new();
+        for fld in &kind.fields {
+            let kind = match env.annotations.label(&fld.name) {
+                Some(k) => Ok(k),
+                None => fld.kind.to_label(),
+            };
+            match (fld.name.to_label(), kind) {
+                (Ok(n), Ok(k)) => labels.push((n, k)),
+                (Err(e), _) | (_, Err(e)) => errors.push(error(e, fld.span())),
+            }
         }
-                               })
-                          .collect::<Vec<(String, String)>>());
+        fields.insert(label, labels);
     }
 
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let vtables = match VTables::new(&env.kinds) {
+        Ok(v) => v,
+        Err(e) => return Err(vec![error(e, None)]),
+    };
+
+    let mut files = Vec::new();
     for kind in &env.kinds {
         let name = kind.name
             .children
@@ -173,36 +187,23 @@ pub fn generate_or_exit(env: &Environment) {
             .collect::<Vec<String>>()
             .join("");
 
-        let mut f = match fs::File::create(format!("output/{}.s", name)) {
-            Ok(f) => f,
-            Err(e) => {
-                println!("{}", e);
-                std::process::exit(42);
-            }
-        };
-
         let label = match kind.name.to_label() {
             Ok(l) => l,
             Err(e) => {
-                println!("{:?}", e);
-                std::process::exit(42);
-            }
-        };
-
-        let source = match kind.generate(&label, &fields) {
-            Ok(s) => s,
-            Err(e) => {
-                println!("{}", e);
-                std::process::exit(42);
+                errors.push(error(e, kind.span()));
+                continue;
             }
         };
 
-        match f.write_all(source.as_bytes()) {
-            Ok(_) => (),
-            Err(e) => {
-                println!("{}", e);
-                std::process::exit(42);
-            }
+        match kind.generate(&label, &fields, &vtables, &env.annotations) {
+            Ok(source) => files.push((format!("{}.s", name), source)),
+            Err(e) => errors.extend(e),
         }
     }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(files)
 }
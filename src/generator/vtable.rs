@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+
+use analysis::ClassOrInterface;
+use analysis::ClassOrInterfaceEnvironment;
+use analysis::MethodEnvironment;
+use scanner::ASTNode;
+use scanner::Token;
+use scanner::TokenKind;
+
+lazy_static! {
+    static ref STATIC: ASTNode = {
+        ASTNode { token: Token::new(TokenKind::Static, None), children: Vec::new() }
+    };
+}
+
+const OBJECT: &'static str = "java.lang.Object";
+// every array is also a subtype of these, along with java.lang.Object
+const ARRAY_SUPERTYPES: [&'static str; 2] = ["java.lang.Cloneable", "java.io.Serializable"];
+const PRIMITIVES: [&'static str; 5] = ["boolean", "byte", "char", "int", "short"];
+
+/// The label of the vtable of a class.
+pub fn label(class_label: &String) -> String {
+    format!("__VTABLE{}", class_label)
+}
+
+/// The label of the interface table of a class.
+pub fn itable_label(class_label: &String) -> String {
+    format!("__ITABLE{}", class_label)
+}
+
+/// The label of the type descriptor of a class, interface or array type, eg.
+/// `__TYPEFoo` for `Foo` or `__ARRAYint` for `int[]`.
+pub fn descriptor(kind: &str) -> String {
+    if kind.ends_with("[]") {
+        format!("__ARRAY{}", &kind[..kind.len() - 2])
+    } else {
+        format!("__TYPE{}", kind)
+    }
+}
+
+/// The label of a method without its class, eg. `fact_INT_` for
+/// `__Foo.fact_INT_`. Overriding methods share the same key.
+pub fn key(method: &MethodEnvironment, class_label: &String) -> Result<String, String> {
+    let prefix = format!("__{}.", class_label);
+    match method.to_label(class_label.clone()) {
+        Ok(ref l) if l.starts_with(&prefix) => Ok(l[prefix.len()..].to_owned()),
+        Ok(l) => Err(format!("could not build vtable key for native method {}", l)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Where an instance method is found at runtime, given the static type of its
+/// receiver.
+#[derive(Clone,Debug,PartialEq)]
+pub enum Entry {
+    /// the index of the method in the vtable of a class
+    Virtual(usize),
+    /// the selector of the method in the interface table of a class
+    Interface(usize),
+}
+
+/// The dispatch tables and type descriptors of every class.
+///
+/// A class's vtable begins with its superclass's, so an overriding method
+/// keeps the index of the method it overrides; instance methods it declares
+/// or inherits from an interface are appended in order.
+///
+/// Every instance method of an interface is instead given a global selector,
+/// and each class has an interface table indexed by selector, holding its
+/// implementation of each method, or 0 if it has none. The word before a
+/// vtable points to the interface table of its class:
+///
+/// ```text
+///   dd __ITABLEFoo
+/// __VTABLEFoo: dd __Foo.equals_java.lang.Object_, ...
+/// __ITABLEFoo: dd 0, __Foo.f_INT_, ...
+/// ```
+///
+/// Every class, interface and array type is also given a global index. The
+/// type descriptor of each type which can be instantiated is a row of a
+/// subtype matrix, holding 1 at the index of each of its supertypes and 0
+/// elsewhere. Objects, arrays and `null` point to their descriptor from the
+/// word before them, which is 0 for `null`.
+pub struct VTables {
+    vtables: HashMap<String, Vec<String>>,
+    interfaces: Vec<String>,
+    selectors: Vec<String>,
+    types: Vec<String>,
+    supertypes: HashMap<String, Vec<String>>,
+}
+
+impl VTables {
+    pub fn new(kinds: &Vec<ClassOrInterfaceEnvironment>) -> Result<VTables, String> {
+        let mut tables = VTables {
+            vtables: HashMap::new(),
+            interfaces: Vec::new(),
+            selectors: Vec::new(),
+            types: Vec::new(),
+            supertypes: HashMap::new(),
+        };
+
+        for kind in kinds {
+            let label = match kind.name.to_label() {
+                Ok(l) => l,
+                Err(e) => return Err(e),
+            };
+            let mut supertypes = Vec::new();
+            match ancestors(kind, kinds, &mut supertypes) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+
+            let mut arrays = supertypes.iter().map(|s| format!("{}[]", s)).collect::<Vec<_>>();
+            arrays.push(OBJECT.to_owned());
+            arrays.extend(ARRAY_SUPERTYPES.iter().map(|s| s.to_string()));
+
+            tables.types.push(label.clone());
+            tables.supertypes.insert(label.clone(), supertypes);
+            tables.supertypes.insert(format!("{}[]", label), arrays);
+        }
+        for kind in kinds {
+            match kind.name.to_label() {
+                Ok(l) => tables.types.push(format!("{}[]", l)),
+                Err(e) => return Err(e),
+            }
+        }
+        for primitive in &PRIMITIVES {
+            let array = format!("{}[]", primitive);
+            let mut arrays = vec![array.clone(), OBJECT.to_owned()];
+            arrays.extend(ARRAY_SUPERTYPES.iter().map(|s| s.to_string()));
+
+            tables.types.push(array.clone());
+            tables.supertypes.insert(array, arrays);
+        }
+
+        for kind in kinds {
+            if kind.kind == ClassOrInterface::CLASS {
+                match tables.build(kind, kinds, &mut Vec::new()) {
+                    Ok(_) => (),
+                    Err(e) => return Err(e),
+                }
+                continue;
+            }
+
+            let label = match kind.name.to_label() {
+                Ok(l) => l,
+                Err(e) => return Err(e),
+            };
+            for method in &kind.methods {
+                if method.modifiers.contains(&*STATIC) {
+                    continue;
+                }
+
+                match key(method, &label) {
+                    Ok(ref k) if tables.selectors.contains(k) => (),
+                    Ok(k) => tables.selectors.push(k),
+                    Err(e) => return Err(e),
+                }
+            }
+            tables.interfaces.push(label);
+        }
+
+        Ok(tables)
+    }
+
+    fn build(&mut self,
+             kind: &ClassOrInterfaceEnvironment,
+             kinds: &Vec<ClassOrInterfaceEnvironment>,
+             visited: &mut Vec<String>)
+             -> Result<Vec<String>, String> {
+        let label = match kind.name.to_label() {
+            Ok(l) => l,
+            Err(e) => return Err(e),
+        };
+        if let Some(vtable) = self.vtables.get(&label) {
+            return Ok(vtable.clone());
+        }
+        if visited.contains(&label) {
+            return Err(format!("cyclic class hierarchy detected at {}", label));
+        }
+        visited.push(label.clone());
+
+        let mut vtable = match kind.superclass(kinds) {
+            Ok(Some(parent)) => {
+                match self.build(&parent, kinds, visited) {
+                    Ok(v) => v,
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(None) => Vec::new(),
+            Err(e) => return Err(e.message),
+        };
+
+        for method in &kind.methods {
+            if method.modifiers.contains(&*STATIC) {
+                continue;
+            }
+
+            match key(method, &label) {
+                Ok(ref k) if vtable.contains(k) => (),
+                Ok(k) => vtable.push(k),
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.vtables.insert(label, vtable.clone());
+        Ok(vtable)
+    }
+
+    /// Where the method with the given key is found when called on a receiver
+    /// of the given type, or None if it is not an instance method.
+    pub fn lookup(&self, class_label: &String, key: &str) -> Option<Entry> {
+        if self.interfaces.contains(class_label) {
+            return self.selectors.iter().position(|s| s == key).map(Entry::Interface);
+        }
+
+        self.vtables
+            .get(class_label)
+            .and_then(|vtable| vtable.iter().position(|m| m == key))
+            .map(Entry::Virtual)
+    }
+
+    /// The index of a type in every type descriptor, whose entry is 1 only for
+    /// its subtypes.
+    pub fn subtype(&self, kind: &str) -> Option<usize> {
+        self.types.iter().position(|t| t == kind)
+    }
+
+    /// Whether every instance of the first type is also an instance of the
+    /// second, ie. whether a cast between them needs no runtime check.
+    pub fn assignable(&self, kind: &str, to: &str) -> bool {
+        match self.supertypes.get(kind) {
+            Some(supertypes) => supertypes.iter().any(|s| s == to),
+            None => false,
+        }
+    }
+
+    // The type descriptor of a type, as a row of the subtype matrix.
+    fn row(&self, kind: &str) -> String {
+        let supertypes = match self.supertypes.get(kind) {
+            Some(s) => s.clone(),
+            None => Vec::new(),
+        };
+
+        self.types
+            .iter()
+            .map(|t| if supertypes.contains(t) { "1" } else { "0" })
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+
+    /// The data section entries for the dispatch tables and type descriptors
+    /// defined along with a class or interface, along with their labels. The
+    /// descriptors of primitive arrays are defined with `java.lang.Object`.
+    pub fn data(&self, class_label: &String) -> Vec<(String, String)> {
+        let mut data = Vec::new();
+
+        let array = format!("{}[]", class_label);
+        data.push((descriptor(&array), format!("{}: dd {}", descriptor(&array), self.row(&array))));
+        if class_label == OBJECT {
+            for primitive in &PRIMITIVES {
+                let array = format!("{}[]", primitive);
+                data.push((descriptor(&array),
+                           format!("{}: dd {}", descriptor(&array), self.row(&array))));
+            }
+        }
+
+        let vtable = match self.vtables.get(class_label) {
+            Some(v) => v,
+            None => return data,
+        };
+
+        data.push((descriptor(class_label),
+                   format!("{}: dd {}", descriptor(class_label), self.row(class_label))));
+
+        let methods = vtable.iter()
+            .map(|m| format!("__{}.{}", class_label, m))
+            .collect::<Vec<String>>();
+        let selectors = self.selectors
+            .iter()
+            .map(|s| if vtable.contains(s) {
+                     format!("__{}.{}", class_label, s)
+                 } else {
+                     "0".to_owned()
+                 })
+            .collect::<Vec<String>>();
+
+        // the data section is sorted, so the word before the vtable must be
+        // part of the same entry
+        data.push((label(class_label),
+                   format!("  dd {}\n{}: dd {}",
+                           itable_label(class_label),
+                           label(class_label),
+                           words(&methods))));
+        data.push((itable_label(class_label),
+                   format!("{}: dd {}", itable_label(class_label), words(&selectors))));
+
+        data
+    }
+}
+
+// Collects the labels of a class or interface and all of its supertypes.
+fn ancestors(kind: &ClassOrInterfaceEnvironment,
+             kinds: &Vec<ClassOrInterfaceEnvironment>,
+             labels: &mut Vec<String>)
+             -> Result<(), String> {
+    let label = match kind.name.to_label() {
+        Ok(l) => l,
+        Err(e) => return Err(e),
+    };
+    if labels.contains(&label) {
+        return Ok(());
+    }
+    labels.push(label);
+
+    let parents = match kind.supertypes(kinds) {
+        Ok(p) => p,
+        Err(e) => return Err(e.message),
+    };
+    for parent in &parents {
+        match ancestors(parent, kinds, labels) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+fn words(entries: &Vec<String>) -> String {
+    if entries.is_empty() {
+        return "0".to_owned();
+    }
+
+    entries.join(", ")
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use error::ErrorMessage;
+use error::GrammarError;
+use scanner::TokenKind;
+
+const START: &'static str = "Start";
+
+/// A context-free grammar, as read from a human-readable grammar file. Every
+/// list is kept sorted, so that regenerating the parse table from an unchanged
+/// grammar gives an identical table.
+pub struct Grammar {
+    /// symbols which appear only on the right-hand side of rules
+    pub terminals: Vec<String>,
+    /// symbols which are expanded by rules
+    pub non_terminals: Vec<String>,
+    /// the non-terminal every parse tree is rooted at
+    pub start: String,
+    /// (lhs, rhs) pairs; an empty rhs is an epsilon rule
+    pub rules: Vec<(String, Vec<String>)>,
+}
+
+impl Grammar {
+    /// Reads a grammar. Each non-terminal is declared as `Name:` at the start
+    /// of a line, followed by its rules, one per tab-indented line. `\epsilon`
+    /// denotes the empty rule. Every terminal must name a TokenKind.
+    pub fn new(src: &str) -> Result<Grammar, GrammarError> {
+        let mut non_terminals = BTreeSet::new();
+        let mut rules = BTreeSet::new();
+        // symbol -> line it is first used on
+        let mut symbols = BTreeMap::new();
+
+        let mut current: Option<String> = None;
+        for (number, line) in src.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if !line.starts_with('\t') {
+                let name = line.trim_right();
+                if !name.ends_with(':') || name.len() == 1 ||
+                   !name[..name.len() - 1].chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    let message = format!("line {} is neither a rule nor a declaration: {}",
+                                          number + 1,
+                                          line);
+                    return Err(GrammarError::new(ErrorMessage::MalformedGrammar(message)));
+                }
+
+                let name = name[..name.len() - 1].to_owned();
+                non_terminals.insert(name.clone());
+                current = Some(name);
+                continue;
+            }
+
+            let lhs = match current {
+                Some(ref c) => c.clone(),
+                None => {
+                    let message = format!("rule on line {} has no non-terminal", number + 1);
+                    return Err(GrammarError::new(ErrorMessage::MalformedGrammar(message)));
+                }
+            };
+
+            let rule = line.trim();
+            if rule == "\\epsilon" {
+                rules.insert(lhs);
+                continue;
+            }
+
+            let rhs = rule.split_whitespace().collect::<Vec<_>>();
+            for symbol in &rhs {
+                symbols.entry((*symbol).to_owned()).or_insert(number + 1);
+            }
+            rules.insert(format!("{} {}", lhs, rhs.join(" ")));
+        }
+
+        if !non_terminals.contains(START) {
+            let message = format!("missing start symbol {}", START);
+            return Err(GrammarError::new(ErrorMessage::MalformedGrammar(message)));
+        }
+
+        let mut terminals = Vec::new();
+        for (symbol, number) in symbols {
+            if non_terminals.contains(&symbol) {
+                continue;
+            }
+
+            match symbol.parse() {
+                Ok(TokenKind::NonTerminal) | Err(_) => {
+                    let message = format!("terminal {} on line {} is not a token", symbol, number);
+                    return Err(GrammarError::new(ErrorMessage::MalformedGrammar(message)));
+                }
+                Ok(_) => terminals.push(symbol),
+            }
+        }
+
+        Ok(Grammar {
+               terminals: terminals,
+               non_terminals: non_terminals.into_iter().collect(),
+               start: START.to_owned(),
+               rules: rules.into_iter()
+                   .map(|r| {
+                            let mut symbols = r.split(' ').map(|s| s.to_owned());
+                            let lhs = symbols.next().unwrap();
+                            (lhs, symbols.collect())
+                        })
+                   .collect(),
+           })
+    }
+}
+
+// Writes the grammar in the format which heads every parse table: the
+// terminals, the non-terminals, the start symbol and the rules, each list
+// preceded by its length.
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(writeln!(f, "{}", self.terminals.len()));
+        for terminal in &self.terminals {
+            try!(writeln!(f, "{}", terminal));
+        }
+
+        try!(writeln!(f, "{}", self.non_terminals.len()));
+        for non_terminal in &self.non_terminals {
+            try!(writeln!(f, "{}", non_terminal));
+        }
+
+        try!(writeln!(f, "{}", self.start));
+
+        try!(writeln!(f, "{}", self.rules.len()));
+        for &(ref lhs, ref rhs) in &self.rules {
+            let mut rule = lhs.clone();
+            for symbol in rhs {
+                rule.push(' ');
+                rule.push_str(symbol);
+            }
+            try!(writeln!(f, "{}", rule));
+        }
+
+        Ok(())
+    }
+}
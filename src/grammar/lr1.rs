@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt;
+
+use error::ErrorMessage;
+use error::GrammarError;
+use grammar::cfg::Grammar;
+
+// A dotted rule with a lookahead: (rule, position of the dot, lookahead).
+type Item = (usize, usize, usize);
+
+#[derive(Clone,Copy,PartialEq)]
+enum Action {
+    Reduce(usize),
+    Shift(usize),
+}
+
+/// A canonical LR(1) parse table, built with the algorithms described in
+/// chapter 3 of Appel's Modern Compiler Implementation.
+pub struct Table {
+    symbols: Vec<String>,
+    states: usize,
+    actions: BTreeMap<(usize, usize), Action>,
+}
+
+// Symbols are numbered with the terminals first, in the order the grammar
+// lists them, followed by the non-terminals.
+struct Generator<'a> {
+    grammar: &'a Grammar,
+    symbols: Vec<String>,
+    rules: Vec<(usize, Vec<usize>)>,
+    rules_by_lhs: HashMap<usize, Vec<usize>>,
+    nullable: Vec<bool>,
+    first: Vec<BTreeSet<usize>>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(grammar: &'a Grammar) -> Generator<'a> {
+        let symbols = grammar.terminals
+            .iter()
+            .chain(grammar.non_terminals.iter())
+            .cloned()
+            .collect::<Vec<String>>();
+        let index = symbols.iter()
+            .enumerate()
+            .map(|(i, s)| (s.clone(), i))
+            .collect::<HashMap<String, usize>>();
+
+        let mut rules = Vec::new();
+        let mut rules_by_lhs = HashMap::new();
+        for (i, &(ref lhs, ref rhs)) in grammar.rules.iter().enumerate() {
+            rules.push((index[lhs], rhs.iter().map(|s| index[s]).collect()));
+            rules_by_lhs.entry(index[lhs]).or_insert(Vec::new()).push(i);
+        }
+
+        let mut generator = Generator {
+            grammar: grammar,
+            symbols: symbols,
+            rules: rules,
+            rules_by_lhs: rules_by_lhs,
+            nullable: Vec::new(),
+            first: Vec::new(),
+        };
+        generator.compute_first_nullable();
+        generator
+    }
+
+    fn is_terminal(&self, symbol: usize) -> bool {
+        symbol < self.grammar.terminals.len()
+    }
+
+    fn compute_first_nullable(&mut self) {
+        self.nullable = vec![false; self.symbols.len()];
+        self.first = (0..self.symbols.len())
+            .map(|s| {
+                     let mut first = BTreeSet::new();
+                     if self.is_terminal(s) {
+                         first.insert(s);
+                     }
+                     first
+                 })
+            .collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(lhs, ref rhs) in &self.rules {
+                for symbol in rhs {
+                    let first = self.first[*symbol].clone();
+                    for terminal in first {
+                        changed |= self.first[lhs].insert(terminal);
+                    }
+                    if !self.nullable[*symbol] {
+                        break;
+                    }
+                }
+
+                if !self.nullable[lhs] && rhs.iter().all(|s| self.nullable[*s]) {
+                    self.nullable[lhs] = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    // The terminals which may begin `symbols` followed by `lookahead`.
+    fn first_of(&self, symbols: &[usize], lookahead: usize) -> BTreeSet<usize> {
+        let mut first = BTreeSet::new();
+        for symbol in symbols {
+            first.extend(self.first[*symbol].iter().cloned());
+            if !self.nullable[*symbol] {
+                return first;
+            }
+        }
+        first.insert(lookahead);
+        first
+    }
+
+    fn next_symbol(&self, item: &Item) -> Option<usize> {
+        self.rules[item.0].1.get(item.1).cloned()
+    }
+
+    fn closure(&self, kernel: BTreeSet<Item>) -> Vec<Item> {
+        let mut items = kernel.clone();
+        let mut queue = kernel.into_iter().collect::<VecDeque<Item>>();
+        while let Some(item) = queue.pop_front() {
+            let next = match self.next_symbol(&item) {
+                Some(n) if !self.is_terminal(n) => n,
+                _ => continue,
+            };
+
+            let rest = &self.rules[item.0].1[item.1 + 1..];
+            for lookahead in self.first_of(rest, item.2) {
+                for rule in self.rules_by_lhs.get(&next).unwrap_or(&Vec::new()) {
+                    let new = (*rule, 0, lookahead);
+                    if items.insert(new) {
+                        queue.push_back(new);
+                    }
+                }
+            }
+        }
+
+        items.into_iter().collect()
+    }
+
+    fn describe(&self, item: &Item) -> String {
+        let &(ref lhs, ref rhs) = &self.grammar.rules[item.0];
+        let mut description = format!("{} ->", lhs);
+        for (i, symbol) in rhs.iter().enumerate() {
+            if i == item.1 {
+                description.push_str(" .");
+            }
+            description.push(' ');
+            description.push_str(symbol);
+        }
+        if item.1 == rhs.len() {
+            description.push_str(" .");
+        }
+        format!("{}, {}", description, self.symbols[item.2])
+    }
+
+    fn conflict(&self, state: usize, items: &[Item], symbol: usize) -> GrammarError {
+        let involved = items.iter()
+            .filter(|i| match self.next_symbol(i) {
+                        Some(next) => next == symbol,
+                        None => i.2 == symbol,
+                    })
+            .collect::<Vec<_>>();
+
+        let reductions = involved.iter().filter(|i| self.next_symbol(i).is_none()).count();
+        let message = if reductions == involved.len() {
+            ErrorMessage::ReduceReduceConflict(state, self.symbols[symbol].clone())
+        } else {
+            ErrorMessage::ShiftReduceConflict(state, self.symbols[symbol].clone())
+        };
+        GrammarError::new(message).with_items(involved.iter().map(|i| self.describe(i)))
+    }
+
+    fn generate(&self) -> Result<Table, Vec<GrammarError>> {
+        let start = self.symbols.iter().position(|s| *s == self.grammar.start).unwrap();
+        // The start rule is never reduced, since nothing follows EOF, so its
+        // lookahead is arbitrary.
+        let kernel = self.rules_by_lhs
+            .get(&start)
+            .unwrap_or(&Vec::new())
+            .iter()
+            .map(|r| (*r, 0, 0))
+            .collect();
+
+        let mut states = vec![self.closure(kernel)];
+        let mut ids = HashMap::new();
+        ids.insert(states[0].clone(), 0);
+
+        let mut actions = BTreeMap::new();
+        let mut current = 0;
+        while current < states.len() {
+            let mut kernels = BTreeMap::new();
+            for item in &states[current] {
+                if let Some(next) = self.next_symbol(item) {
+                    kernels.entry(next).or_insert(BTreeSet::new()).insert((item.0,
+                                                                          item.1 + 1,
+                                                                          item.2));
+                }
+            }
+
+            for (symbol, kernel) in kernels {
+                let items = self.closure(kernel);
+                let id = match ids.get(&items) {
+                    Some(id) => *id,
+                    None => states.len(),
+                };
+                if id == states.len() {
+                    ids.insert(items.clone(), id);
+                    states.push(items);
+                }
+                actions.insert((current, symbol), Action::Shift(id));
+            }
+
+            current += 1;
+        }
+
+        let mut errors = Vec::new();
+        let mut conflicts = BTreeSet::new();
+        for (state, items) in states.iter().enumerate() {
+            for item in items {
+                if self.next_symbol(item).is_some() {
+                    continue;
+                }
+
+                let key = (state, item.2);
+                let action = Action::Reduce(item.0);
+                match actions.get(&key) {
+                    Some(old) if *old != action => {
+                        if conflicts.insert(key) {
+                            errors.push(self.conflict(state, items, item.2));
+                        }
+                        continue;
+                    }
+                    _ => (),
+                }
+                actions.insert(key, action);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Table {
+               symbols: self.symbols.clone(),
+               states: states.len(),
+               actions: actions,
+           })
+    }
+}
+
+impl Table {
+    /// Builds the LR(1) parse table for a grammar, reporting every conflict.
+    pub fn new(grammar: &Grammar) -> Result<Table, Vec<GrammarError>> {
+        Generator::new(grammar).generate()
+    }
+}
+
+// Writes the table in the format read by the parser's DFA: the number of
+// states, the number of actions, and then one `state symbol action value`
+// line per action.
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(writeln!(f, "{}", self.states));
+        try!(writeln!(f, "{}", self.actions.len()));
+        for (&(state, symbol), action) in &self.actions {
+            let (function, value) = match *action {
+                Action::Reduce(rule) => ("reduce", rule),
+                Action::Shift(state) => ("shift", state),
+            };
+            try!(writeln!(f, "{} {} {} {}", state, self.symbols[symbol], function, value));
+        }
+        Ok(())
+    }
+}
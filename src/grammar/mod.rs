@@ -0,0 +1,34 @@
+//! The grammar module for juicyj. Builds the LR(1) parse table used by the
+//! parser from the human-readable grammar in `grammar/joos.grammar`.
+mod cfg;
+mod lr1;
+
+use error::GrammarError;
+
+pub use self::cfg::Grammar;
+pub use self::lr1::Table;
+
+/// Reads a grammar and builds its parse table, in the format embedded in the
+/// parser as `grammar/joos.lr1`. Every shift/reduce and reduce/reduce conflict
+/// is reported, along with the items involved.
+///
+/// # Examples
+///
+/// ```rust
+/// let grammar = "Start:\n\tBOF S EOF\n\nS:\n\tIDENTIFIER\n\t\\epsilon\n";
+/// match juicyj::grammar::generate(grammar) {
+///     Ok(table) => print!("{}", table),
+///     Err(errors) => println!("found {} conflicts", errors.len()),
+/// }
+/// ```
+pub fn generate(src: &str) -> Result<String, Vec<GrammarError>> {
+    let grammar = match Grammar::new(src) {
+        Ok(g) => g,
+        Err(e) => return Err(vec![e]),
+    };
+
+    match Table::new(&grammar) {
+        Ok(table) => Ok(format!("{}{}", grammar, table)),
+        Err(e) => Err(e),
+    }
+}
@@ -5,8 +5,15 @@
 extern crate lazy_static;
 #[macro_use]
 extern crate log;
+extern crate rustc_serialize;
+extern crate walkdir;
 
 pub mod analysis;
+pub mod compiler;
 pub mod error;
 pub mod generator;
+pub mod grammar;
 pub mod scanner;
+pub mod source;
+
+pub use compiler::Compiler;
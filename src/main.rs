@@ -4,8 +4,14 @@ extern crate getopts;
 extern crate log;
 
 extern crate juicyj;
+extern crate rustc_serialize;
 
+use std::collections::BTreeMap;
 use std::env;
+use std::io::Write;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
 
 fn main() {
     env_logger::init().unwrap();
@@ -16,7 +22,31 @@ fn main() {
     let mut opts = getopts::Options::new();
     opts.optflag("h", "help", "print this help menu");
     opts.optflag("s", "stdlib", "include stdlib in compilation");
+    opts.optopt("",
+                "stdlib-dir",
+                "read the stdlib from DIR instead of stdlib/java",
+                "DIR");
     opts.optflag("V", "version", "print the version");
+    opts.optopt("", "explain", "print a longer explanation of an error code", "CODE");
+    opts.optopt("",
+                "error-format",
+                "print errors as human-readable text (the default) or as json, one per line",
+                "human|json");
+    opts.optmulti("W",
+                  "warn",
+                  "report warnings of a given kind, or of every kind with `all`; every kind is \
+                   reported by default",
+                  "LINT");
+    opts.optmulti("A",
+                  "allow",
+                  "stop reporting warnings of a given kind, or of every kind with `all`; -W \
+                   takes precedence over -A",
+                  "LINT");
+    opts.optopt("",
+                "emit",
+                "print the output of a single stage and stop, where STAGE is one of tokens, \
+                 parse-tree, ast, ast-json, env, env-json or asm",
+                "STAGE");
     let matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
         Err(_) => {
@@ -25,6 +55,16 @@ fn main() {
         }
     };
 
+    let json = match matches.opt_str("error-format") {
+        None => false,
+        Some(ref format) if format == "human" => false,
+        Some(ref format) if format == "json" => true,
+        Some(_) => {
+            print_usage(&program, opts);
+            std::process::exit(1);
+        }
+    };
+
     if matches.opt_present("h") {
         print_usage(&program, opts);
         return;
@@ -35,40 +75,275 @@ fn main() {
         return;
     }
 
+    if let Some(code) = matches.opt_str("explain") {
+        match juicyj::error::explain(&code) {
+            Some(explanation) => print_explanation(explanation),
+            None => {
+                writeln!(&mut std::io::stderr(), "error: no such error code {}", code).unwrap();
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     if matches.free.len() == 0 {
         print_usage(&program, opts);
         return;
     }
 
-    let mut asts = Vec::new();
-    for i in 0..matches.free.len() {
-        let file = matches.free[i].clone();
-        let src = juicyj::scanner::read_src_file(&file);
-        asts.push(juicyj::scanner::scan_or_exit(&file, &src));
+    if matches.free[0] == "grammar" {
+        if matches.free.len() != 2 {
+            print_usage(&program, opts);
+            return;
+        }
+
+        let src = match juicyj::scanner::read_src_file(&matches.free[1]) {
+            Ok(src) => src,
+            Err(e) => exit(1, vec![e], &juicyj::scanner::SourceMap::new(), json),
+        };
+        match juicyj::grammar::generate(&src) {
+            Ok(table) => print!("{}", table),
+            Err(errors) => {
+                for e in errors {
+                    writeln!(&mut std::io::stderr(), "{}", e).unwrap();
+                }
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let mut compiler = juicyj::Compiler::new();
+    for file in &matches.free {
+        match compiler.add_file(file) {
+            Ok(_) => (),
+            Err(e) => exit(1, vec![e], compiler.sources(), json),
+        }
+    }
+
+    for (name, allow) in matches.opt_strs("A")
+        .into_iter()
+        .map(|n| (n, true))
+        .chain(matches.opt_strs("W").into_iter().map(|n| (n, false))) {
+        let lints = if name == "all" {
+            juicyj::error::LINTS.to_vec()
+        } else {
+            match juicyj::error::Lint::from_name(&name) {
+                Some(lint) => vec![lint],
+                None => {
+                    writeln!(&mut std::io::stderr(), "error: no such warning {}", name).unwrap();
+                    std::process::exit(1);
+                }
+            }
+        };
+        for lint in lints {
+            if allow {
+                compiler.allow(lint);
+            } else {
+                compiler.warn(lint);
+            }
+        }
     }
 
-    if matches.opt_present("s") {
-        let stdlib_io = std::fs::read_dir("stdlib/java/io").unwrap();
-        let stdlib_lang = std::fs::read_dir("stdlib/java/lang").unwrap();
-        let stdlib_util = std::fs::read_dir("stdlib/java/util").unwrap();
+    if matches.opt_present("s") || matches.opt_present("stdlib-dir") {
+        let stdlib = match matches.opt_str("stdlib-dir") {
+            Some(dir) => juicyj::source::Directory::new(&dir),
+            None => juicyj::source::stdlib(),
+        };
+        match compiler.add_sources(&stdlib) {
+            Ok(_) => (),
+            Err(e) => exit(1, vec![e], compiler.sources(), json),
+        }
+    }
+
+    if let Some(stage) = matches.opt_str("emit") {
+        let result = emit(&mut compiler, &stage);
+        print_warnings(&compiler, &matches.free, json);
+        match result {
+            Ok(true) => return,
+            Ok(false) => {
+                print_usage(&program, opts);
+                std::process::exit(1);
+            }
+            Err(errors) => exit(42, errors, compiler.sources(), json),
+        }
+    }
+
+    let result = compiler.generate();
+    print_warnings(&compiler, &matches.free, json);
+    let files = match result {
+        Ok(files) => files,
+        Err(errors) => exit(42, errors, compiler.sources(), json),
+    };
+
+    match write_output(&files) {
+        Ok(_) => (),
+        Err(e) => {
+            println!("{}", e);
+            std::process::exit(42);
+        }
+    }
+}
 
-        for path in stdlib_io.chain(stdlib_lang).chain(stdlib_util) {
-            match path.unwrap().path().to_str() {
-                Some(filename) => {
-                    let src: String = juicyj::scanner::read_src_file(&filename.to_string());
-                    asts.push(juicyj::scanner::scan_or_exit(&filename, &src));
+// Prints the output of a single stage of the compiler. Returns false if the
+// stage does not exist.
+fn emit(compiler: &mut juicyj::Compiler,
+        stage: &str)
+        -> Result<bool, Vec<juicyj::error::Diagnostic>> {
+    match stage {
+        "tokens" | "parse-tree" => {
+            let mut errors = Vec::new();
+            let sources = compiler.sources();
+            for file in 0..sources.len() {
+                if stage == "tokens" {
+                    match juicyj::scanner::lex(sources, file) {
+                        Ok(tokens) => {
+                            for token in tokens {
+                                match token.span {
+                                    Some(span) => println!("{} {}", sources.location(&span), token),
+                                    None => println!("{}", token),
+                                }
+                            }
+                        }
+                        Err(e) => errors.extend(e),
+                    }
+                } else {
+                    match juicyj::scanner::parse(sources, file) {
+                        Ok(tree) => println!("{}", tree),
+                        Err(e) => errors.extend(e),
+                    }
                 }
-                _ => (),
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+        "ast" => {
+            try!(compiler.parse());
+            for ast in compiler.asts() {
+                println!("{}", ast);
+            }
+        }
+        "ast-json" => {
+            try!(compiler.parse());
+            println!("{}", with_files(compiler, "asts", compiler.asts().to_json()));
+        }
+        "env" => {
+            try!(compiler.analyze());
+            if let Some(env) = compiler.environment() {
+                print!("{}", env);
+            }
+        }
+        "env-json" => {
+            try!(compiler.analyze());
+            if let Some(env) = compiler.environment() {
+                println!("{}", with_files(compiler, "environment", env.to_json()));
+            }
+        }
+        "asm" => {
+            for (name, source) in try!(compiler.generate()) {
+                println!("; {}", name);
+                print!("{}", source);
+                if !source.ends_with('\n') {
+                    println!("");
+                }
+            }
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+// Pairs serialized output with the names of the files its spans point into,
+// indexed by their `file` id.
+fn with_files(compiler: &juicyj::Compiler, key: &str, json: Json) -> Json {
+    let mut object = BTreeMap::new();
+    object.insert("files".to_owned(), compiler.sources().to_json());
+    object.insert(key.to_owned(), json);
+    Json::Object(object)
+}
+
+// Prints every warning found in the files named on the command line to
+// stderr, in the same format as errors, so that they never mix with output
+// such as `--emit=ast-json`. Warnings in the stdlib are not the user's to fix.
+fn print_warnings(compiler: &juicyj::Compiler, files: &Vec<String>, json: bool) {
+    for warning in compiler.warnings() {
+        if let Some(span) = warning.span {
+            let filename = compiler.sources().name(span.file).unwrap_or("");
+            let named = files.iter()
+                .any(|f| f == filename || (f == "-" && filename == juicyj::source::STDIN));
+            if !named {
+                continue;
             }
         }
+
+        let mut stderr = std::io::stderr();
+        if json {
+            writeln!(&mut stderr, "{}", warning.to_json(compiler.sources())).unwrap();
+        } else {
+            writeln!(&mut stderr, "{}", warning.render(compiler.sources())).unwrap();
+        }
+    }
+}
+
+// Prints every error, as text or as one JSON record per line, and exits: with
+// code 42 if the program is not valid Joos, or with code 1 if it could not be
+// compiled for any other reason.
+fn exit(code: i32,
+        errors: Vec<juicyj::error::Diagnostic>,
+        sources: &juicyj::scanner::SourceMap,
+        json: bool)
+        -> ! {
+    for e in errors {
+        if json {
+            println!("{}", e.to_json(sources));
+        } else {
+            println!("{}", e.render(sources));
+        }
+    }
+    std::process::exit(code);
+}
+
+// Replaces the contents of the `output/` subdirectory with the compiled files.
+fn write_output(files: &Vec<(String, String)>) -> std::io::Result<()> {
+    try!(std::fs::create_dir_all("output"));
+    for entry in try!(std::fs::read_dir("output")) {
+        let path = try!(entry).path();
+        if path.is_file() {
+            try!(std::fs::remove_file(path));
+        }
     }
 
-    let env = juicyj::analysis::analyze_or_exit(&asts);
-    juicyj::generator::generate_or_exit(&env);
+    for &(ref name, ref source) in files {
+        let mut f = try!(std::fs::File::create(std::path::Path::new("output").join(name)));
+        try!(f.write_all(source.as_bytes()));
+    }
+
+    Ok(())
+}
+
+fn print_explanation(explanation: &juicyj::error::Explanation) {
+    println!("{}: {}\n", explanation.code, explanation.title);
+    println!("{}", explanation.text);
+    if !explanation.rule.is_empty() {
+        println!("\nSee {}.", explanation.rule);
+    }
+
+    let indent = |example: &str| {
+        example.lines().map(|l| format!("    {}", l)).collect::<Vec<String>>().join("\n")
+    };
+    if !explanation.failing.is_empty() {
+        println!("\nErroneous code example:\n\n{}", indent(explanation.failing));
+    }
+    if !explanation.passing.is_empty() {
+        println!("\nCorrected code example:\n\n{}", indent(explanation.passing));
+    }
 }
 
 fn print_usage(program: &str, opts: getopts::Options) {
-    let brief = format!("Usage: {} [options] FILE...", program);
+    let brief = format!("Usage: {0} [options] FILE...\n       {0} grammar GRAMMAR\n\n\
+                         A FILE of - reads a source from stdin.",
+                        program);
     print!("{}", opts.usage(&brief));
 }
 
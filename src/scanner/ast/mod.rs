@@ -1,7 +1,13 @@
 mod node;
+pub mod typed;
+pub mod visit;
 
+use std::collections::BTreeMap;
 use std::fmt;
 
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
 use error::ASTError;
 use error::ErrorMessage;
 use scanner::common::Token;
@@ -11,6 +17,7 @@ use scanner::parser::ParseTree;
 pub use self::node::ASTNodeImport;
 pub use self::node::ASTNode;
 use self::node::ASTNodePackage;
+use self::typed::TypeDeclaration;
 
 /// An abstract syntax tree generated at the end of the scanning step.
 pub struct AST {
@@ -22,6 +29,9 @@ pub struct AST {
     pub imports: Vec<ASTNodeImport>,
     /// pointer to the root node of the AST for this file
     pub root: Option<ASTNode>,
+    /// the class or interface declared in this file, as a typed tree built
+    /// from `root`
+    pub declaration: Option<TypeDeclaration>,
     /// canonical name of the definied class or interface in this tree --
     /// effectively "package + name"
     pub canonical: ASTNode,
@@ -31,7 +41,8 @@ impl AST {
     /// Transforms a ParseTree to an AST by through three operations: capturing
     /// the package declaration as an ASTNodePackage, capturing the import
     /// statements as ASTNodeImports, and performing a recursive build on the
-    /// remaining tree into ASTNodes.
+    /// remaining tree into ASTNodes. The typed declaration is then built from
+    /// those ASTNodes.
     pub fn new(filename: &str, tree: &ParseTree) -> Result<AST, ASTError> {
         let token_name = Token::new(TokenKind::NonTerminal, Some("Name"));
         let node_star = ASTNode {
@@ -106,7 +117,10 @@ impl AST {
                         Err(e) => return Err(e),
                     };
                 }
-                _ => return Err(ASTError::new(ErrorMessage::InvalidRootChild, child)),
+                _ => {
+                    return Err(ASTError::new(ErrorMessage::InvalidRootChild, child)
+                                   .with_span(child.token.span))
+                }
             }
         }
 
@@ -128,7 +142,10 @@ impl AST {
                               children: Vec::new(),
                           });
             }
-            _ => return Err(ASTError::new(ErrorMessage::MissingName, &root.unwrap())),
+            _ => {
+                let root = root.unwrap();
+                return Err(ASTError::new(ErrorMessage::MissingName, &root).with_span(root.span()));
+            }
         };
 
         for first_import in &imports {
@@ -145,18 +162,30 @@ impl AST {
 
                         if first_import_name == second_import_name {
                             return Err(ASTError::new(ErrorMessage::ImportClashSingleTogether,
-                                                     &first_import_name));
+                                                     &first_import_name)
+                                               .with_span(second_import.import.span()));
                         }
                     }
                 }
             }
         }
 
+        let declaration = match root {
+            Some(ref r) => {
+                match TypeDeclaration::new(r) {
+                    Ok(d) => Some(d),
+                    Err(e) => return Err(e),
+                }
+            }
+            None => None,
+        };
+
         Ok(AST {
                filename: filename.to_owned(),
                imports: imports,
                package: package,
                root: root,
+               declaration: declaration,
                canonical: canonical,
            })
     }
@@ -176,3 +205,17 @@ impl fmt::Display for AST {
         write!(f, "{}", self.root.clone().unwrap())
     }
 }
+
+// The package, imports and canonical name are written as dotted names; the
+// tree itself is written node by node.
+impl ToJson for AST {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("filename".to_owned(), self.filename.to_json());
+        object.insert("package".to_owned(), self.package.to_json());
+        object.insert("imports".to_owned(), self.imports.to_json());
+        object.insert("canonical".to_owned(), self.canonical.source_text().to_json());
+        object.insert("root".to_owned(), self.root.to_json());
+        Json::Object(object)
+    }
+}
@@ -1,9 +1,15 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
 use error::ASTError;
 use error::ErrorMessage;
+use scanner::common::Span;
 use scanner::common::Token;
 use scanner::common::TokenKind;
+use scanner::common::span;
 use scanner::parser::ParseNode;
 
 #[derive(Clone,Debug)]
@@ -51,12 +57,14 @@ impl ASTNode {
                                 TokenKind::NonTerminal => {
                                     if children[1].token.lexeme != Some("Name".to_owned()) {
                                         return Err(ASTError::new(ErrorMessage::InvalidCast,
-                                                                 &children[1]));
+                                                                 &children[1])
+                                                           .with_span(children[1].span()));
                                     }
                                 }
                                 _ => {
                                     return Err(ASTError::new(ErrorMessage::InvalidCast,
-                                                             &children[1]));
+                                                             &children[1])
+                                                       .with_span(children[1].span()));
                                 }
                             }
 
@@ -64,7 +72,8 @@ impl ASTNode {
                             node.children[1].collect_child_lexeme("PrimaryNoNewArray", &mut nodes);
                             for n in &nodes {
                                 if n.children.len() != 1 {
-                                    return Err(ASTError::new(ErrorMessage::InvalidCast, n));
+                                    return Err(ASTError::new(ErrorMessage::InvalidCast, n)
+                                                   .with_span(n.token.span));
                                 }
                             }
                         }
@@ -154,6 +163,7 @@ impl ASTNode {
                                           token: Token {
                                               kind: TokenKind::NumValue,
                                               lexeme: Some("0".to_owned()),
+                                              span: node.children[0].token.span,
                                           },
                                           children: Vec::new(),
                                       });
@@ -213,7 +223,8 @@ impl ASTNode {
                                     match node.token.lexeme {
                                         Some(ref l) if l.parse().unwrap_or(0) >
                                                        2u64.pow(31) - 1 => {
-                                            return Err(ASTError::new(ErrorMessage::IntOOB, &node));
+                                            return Err(ASTError::new(ErrorMessage::IntOOB, &node)
+                                                           .with_span(node.span()));
                                         }
                                         _ => (),
                                     }
@@ -275,6 +286,12 @@ impl ASTNode {
         }
     }
 
+    /// The region of source code covered by this node and all of its
+    /// children. Nodes synthesized during analysis may not have one.
+    pub fn span(&self) -> Option<Span> {
+        self.children.iter().fold(self.token.span, |acc, child| span::merge(acc, child.span()))
+    }
+
     /// Attempt to flatten the current node and children into a simpler tree.
     /// Useful for flattening any NonTerminal which can contain itself.
     pub fn flatten(&mut self) -> &ASTNode {
@@ -343,6 +360,41 @@ impl ASTNode {
         Ok(())
     }
 
+    /// Writes this node as the source text it was built from, eg.
+    /// `java.lang.Object` or `int[]`.
+    pub fn source_text(&self) -> String {
+        if self.children.is_empty() {
+            return match (&self.token.lexeme, &self.token.kind) {
+                (&Some(ref l), _) => l.clone(),
+                (&None, &TokenKind::Dot) => ".".to_owned(),
+                (&None, &TokenKind::Star) => "*".to_owned(),
+                (&None, kind) => format!("{:?}", kind).to_lowercase(),
+            };
+        }
+
+        let text = self.children.iter().map(|c| c.source_text()).collect::<Vec<String>>().join("");
+        match self.token.lexeme {
+            Some(ref l) if l == "ArrayType" => format!("{}[]", text),
+            _ => text,
+        }
+    }
+
+    /// Whether any Name within this node starts with the identifier `name`,
+    /// ie. whether it may refer to a variable or type of that name.
+    pub fn mentions(&self, name: &str) -> bool {
+        if self.token.kind == TokenKind::NonTerminal &&
+           self.token.lexeme == Some("Name".to_owned()) {
+            if let Some(first) = self.children.first() {
+                if first.token.kind == TokenKind::Identifier &&
+                   first.token.lexeme == Some(name.to_owned()) {
+                    return true;
+                }
+            }
+        }
+
+        self.children.iter().any(|c| c.mentions(name))
+    }
+
     /// Creates a label to be used in code generation. Should be called on
     /// method names ("Name" or "Identifier") and to get the names of
     /// parameters.
@@ -523,3 +575,27 @@ impl fmt::Display for ASTNodePackage {
         write!(f, "{} ", &self.package)
     }
 }
+
+// Nodes are written as their token, with a list of children.
+impl ToJson for ASTNode {
+    fn to_json(&self) -> Json {
+        let mut object = match self.token.to_json() {
+            Json::Object(o) => o,
+            _ => BTreeMap::new(),
+        };
+        object.insert("children".to_owned(), self.children.to_json());
+        Json::Object(object)
+    }
+}
+
+impl ToJson for ASTNodeImport {
+    fn to_json(&self) -> Json {
+        self.import.source_text().to_json()
+    }
+}
+
+impl ToJson for ASTNodePackage {
+    fn to_json(&self) -> Json {
+        self.package.source_text().to_json()
+    }
+}
@@ -0,0 +1,715 @@
+//! A typed view of a file's AST, with an enum for each kind of expression and
+//! statement and a struct for each declaration, built from its ASTNodes as
+//! written.
+//!
+//! It does not replace ASTNodes. Only the checks which read a body as it was
+//! written use it: definite assignment (`verify::variable`) and reachability
+//! (`verify::reachability`), along with anything built on `visit::Visitor`.
+//! Name resolution, type checking and code generation still match on the
+//! lexemes of ASTNodes, since resolution rewrites those nodes in place, eg. by
+//! qualifying method names, and code generation reads the rewritten tree.
+//! Moving them over first needs somewhere other than the tree to keep what
+//! resolution finds, as `Annotations` does for types and declarations.
+
+use error::ASTError;
+use error::ErrorMessage;
+use scanner::ast::ASTNode;
+use scanner::common::Span;
+use scanner::common::Token;
+use scanner::common::TokenKind;
+
+/// An expression, as written. Names and types are kept as the ASTNodes used
+/// throughout analysis: names are flattened, eg. `a.b.c` is a single Name, and
+/// array types are written as an `ArrayType` of their element type.
+#[derive(Clone,Debug)]
+pub enum Expression {
+    /// a literal, eg. `1`, `'a'`, `"a"`, `true` or `null`
+    Literal(Token),
+    /// `this`
+    This(Token),
+    /// a simple or qualified name, eg. `x` or `a.b.c`, which may refer to a
+    /// variable, a field or a type
+    Name(ASTNode),
+    /// `object.field`, where the object is not a Name, eg. `this.x`
+    FieldAccess {
+        object: Box<Expression>,
+        field: Token,
+        span: Option<Span>,
+    },
+    /// `method(arguments)` or `object.method(arguments)`. A method called by
+    /// name has no object, eg. `a.b.c()` calls the Name `a.b.c`.
+    MethodInvocation {
+        object: Option<Box<Expression>>,
+        method: ASTNode,
+        arguments: Vec<Expression>,
+        span: Option<Span>,
+    },
+    /// `array[index]`
+    ArrayAccess {
+        array: Box<Expression>,
+        index: Box<Expression>,
+        span: Option<Span>,
+    },
+    /// `new element[size]`; the size is only missing from invalid programs
+    ArrayCreation {
+        element: ASTNode,
+        size: Option<Box<Expression>>,
+        span: Option<Span>,
+    },
+    /// `new kind(arguments)`
+    ClassInstanceCreation {
+        kind: ASTNode,
+        arguments: Vec<Expression>,
+        span: Option<Span>,
+    },
+    /// `(kind) operand`
+    Cast {
+        kind: ASTNode,
+        operand: Box<Expression>,
+        span: Option<Span>,
+    },
+    /// `!operand`. Negation is written as a subtraction from zero.
+    Unary {
+        operator: Token,
+        operand: Box<Expression>,
+        span: Option<Span>,
+    },
+    /// `lhs operator rhs`, eg. `a + b` or `a && b`
+    Binary {
+        operator: Token,
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+        span: Option<Span>,
+    },
+    /// `operand instanceof kind`
+    InstanceOf {
+        operand: Box<Expression>,
+        kind: ASTNode,
+        span: Option<Span>,
+    },
+    /// `lhs = rhs`
+    Assignment {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+        span: Option<Span>,
+    },
+}
+
+/// A statement within a method or constructor body.
+#[derive(Clone,Debug)]
+pub enum Statement {
+    /// `{ statements }`, along with where its closing brace is
+    Block {
+        statements: Vec<Statement>,
+        span: Option<Span>,
+        end: Option<Span>,
+    },
+    /// `kind name;` or `kind name = initializer;`
+    LocalVariableDeclaration {
+        kind: ASTNode,
+        name: Token,
+        initializer: Option<Expression>,
+        span: Option<Span>,
+    },
+    /// `if (condition) then` or `if (condition) then else otherwise`
+    If {
+        condition: Expression,
+        then: Box<Statement>,
+        otherwise: Option<Box<Statement>>,
+        span: Option<Span>,
+    },
+    /// `while (condition) body`
+    While {
+        condition: Expression,
+        body: Box<Statement>,
+        span: Option<Span>,
+    },
+    /// `for (init; condition; update) body`, where each of init, condition
+    /// and update may be missing
+    For {
+        init: Option<Box<Statement>>,
+        condition: Option<Expression>,
+        update: Option<Expression>,
+        body: Box<Statement>,
+        span: Option<Span>,
+    },
+    /// `return;` or `return value;`
+    Return {
+        value: Option<Expression>,
+        span: Option<Span>,
+    },
+    /// an assignment, method invocation or class instance creation
+    Expression(Expression),
+    /// `;`
+    Empty(Option<Span>),
+}
+
+/// A parameter of a method or constructor.
+#[derive(Clone,Debug)]
+pub struct Parameter {
+    pub kind: ASTNode,
+    pub name: Token,
+}
+
+/// A field, which has an initializer if it was declared with one.
+#[derive(Clone,Debug)]
+pub struct Field {
+    pub modifiers: Vec<Token>,
+    pub kind: ASTNode,
+    pub name: Token,
+    pub initializer: Option<Expression>,
+    pub span: Option<Span>,
+}
+
+/// A method, which has a body unless it is abstract or native.
+#[derive(Clone,Debug)]
+pub struct Method {
+    pub modifiers: Vec<Token>,
+    /// the declared return type, which is a `Void` node for `void`
+    pub return_type: ASTNode,
+    pub name: Token,
+    pub parameters: Vec<Parameter>,
+    pub body: Option<Statement>,
+    pub span: Option<Span>,
+}
+
+/// A constructor.
+#[derive(Clone,Debug)]
+pub struct Constructor {
+    pub modifiers: Vec<Token>,
+    pub name: Token,
+    pub parameters: Vec<Parameter>,
+    pub body: Statement,
+    pub span: Option<Span>,
+}
+
+/// A member of a class or interface.
+#[derive(Clone,Debug)]
+pub enum Member {
+    Field(Field),
+    Method(Method),
+    Constructor(Constructor),
+}
+
+/// Whether a TypeDeclaration declares a class or an interface.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum TypeKind {
+    Class,
+    Interface,
+}
+
+/// The class or interface declared in a file.
+#[derive(Clone,Debug)]
+pub struct TypeDeclaration {
+    pub kind: TypeKind,
+    pub modifiers: Vec<Token>,
+    pub name: Token,
+    /// the superclass of a class, or the superinterfaces of an interface
+    pub extends: Vec<ASTNode>,
+    pub implements: Vec<ASTNode>,
+    pub members: Vec<Member>,
+    pub span: Option<Span>,
+}
+
+fn lexeme(node: &ASTNode) -> &str {
+    match node.token.lexeme {
+        Some(ref l) if node.token.kind == TokenKind::NonTerminal => l,
+        _ => "",
+    }
+}
+
+fn unexpected(node: &ASTNode) -> ASTError {
+    ASTError::new(ErrorMessage::UnexpectedNode, node).with_span(node.span())
+}
+
+fn child(node: &ASTNode, idx: usize) -> Result<&ASTNode, ASTError> {
+    match node.children.get(idx) {
+        Some(c) => Ok(c),
+        None => Err(unexpected(node)),
+    }
+}
+
+fn identifier(node: &ASTNode) -> Result<Token, ASTError> {
+    match node.token.kind {
+        TokenKind::Identifier => Ok(node.token.clone()),
+        _ => Err(unexpected(node)),
+    }
+}
+
+// A Name, flattened; a lone Identifier is wrapped in one.
+fn name(node: &ASTNode) -> ASTNode {
+    let mut name = match node.token.kind {
+        TokenKind::Identifier => {
+            ASTNode {
+                token: Token::new(TokenKind::NonTerminal, Some("Name")),
+                children: vec![node.clone()],
+            }
+        }
+        _ => node.clone(),
+    };
+    name.flatten();
+    name
+}
+
+// A type, in the form used by the environment: Names are flattened and an
+// ArrayType only contains its element type, without its Dim.
+fn kind(node: &ASTNode) -> ASTNode {
+    let mut kind = node.clone();
+    kind.flatten();
+    if lexeme(&kind) == "ArrayType" {
+        kind.children.truncate(1);
+        kind.children[0].flatten();
+    }
+    kind
+}
+
+fn array_of(element: &ASTNode) -> ASTNode {
+    ASTNode {
+        token: Token::new(TokenKind::NonTerminal, Some("ArrayType")),
+        children: vec![kind(element)],
+    }
+}
+
+// The elements of a list such as an ArgumentList, without their commas. A
+// list of one element may not be wrapped in the list at all.
+fn list(node: &ASTNode, list: &str) -> Vec<ASTNode> {
+    let mut node = node.clone();
+    if lexeme(&node) != list {
+        return vec![node];
+    }
+
+    node.flatten();
+    node.children.into_iter().filter(|c| c.token.kind != TokenKind::Comma).collect()
+}
+
+fn modifiers(node: &ASTNode) -> Vec<Token> {
+    let mut node = node.clone();
+    node.flatten();
+    node.children.iter().map(|c| c.token.clone()).collect()
+}
+
+fn arguments(node: Option<&ASTNode>) -> Result<Vec<Expression>, ASTError> {
+    let node = match node {
+        Some(n) if lexeme(n) == "ArgumentList" => n,
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut arguments = Vec::new();
+    for argument in list(node, "ArgumentList") {
+        arguments.push(try!(Expression::new(&argument)));
+    }
+    Ok(arguments)
+}
+
+fn boxed(node: &ASTNode) -> Result<Box<Expression>, ASTError> {
+    Expression::new(node).map(Box::new)
+}
+
+impl Expression {
+    /// Builds an Expression from an ASTNode, as it was written. Nodes which
+    /// have been rewritten by type checking, eg. with fully qualified method
+    /// names, are not supported.
+    pub fn new(node: &ASTNode) -> Result<Expression, ASTError> {
+        let span = node.span();
+        match node.token.kind {
+            TokenKind::CharValue | TokenKind::False | TokenKind::Null | TokenKind::NumValue |
+            TokenKind::StrValue | TokenKind::True => {
+                return Ok(Expression::Literal(node.token.clone()));
+            }
+            TokenKind::This => return Ok(Expression::This(node.token.clone())),
+            TokenKind::Identifier => return Ok(Expression::Name(name(node))),
+            TokenKind::Not if node.children.len() == 1 => {
+                return Ok(Expression::Unary {
+                              operator: node.token.clone(),
+                              operand: try!(boxed(&node.children[0])),
+                              span: span,
+                          });
+            }
+            TokenKind::Instanceof if node.children.len() == 2 => {
+                return Ok(Expression::InstanceOf {
+                              operand: try!(boxed(&node.children[0])),
+                              kind: kind(&node.children[1]),
+                              span: span,
+                          });
+            }
+            TokenKind::NonTerminal | TokenKind::Assignment => (),
+            _ if node.children.len() == 2 => {
+                return Ok(Expression::Binary {
+                              operator: node.token.clone(),
+                              lhs: try!(boxed(&node.children[0])),
+                              rhs: try!(boxed(&node.children[1])),
+                              span: span,
+                          });
+            }
+            _ => return Err(unexpected(node)),
+        }
+
+        match lexeme(node) {
+            "Name" => Ok(Expression::Name(name(node))),
+            // ( Expression )
+            "PrimaryNoNewArray" => Expression::new(try!(child(node, 1))),
+            // Lhs = Rhs
+            "Assignment" => {
+                Ok(Expression::Assignment {
+                       lhs: try!(boxed(try!(child(node, 0)))),
+                       rhs: try!(boxed(try!(child(node, 2)))),
+                       span: span,
+                   })
+            }
+            // Primary . Identifier
+            "FieldAccess" => {
+                Ok(Expression::FieldAccess {
+                       object: try!(boxed(try!(child(node, 0)))),
+                       field: try!(identifier(try!(child(node, 2)))),
+                       span: span,
+                   })
+            }
+            // Name ( Arguments ) or Primary . Identifier ( Arguments )
+            "MethodInvocation" => {
+                let first = try!(child(node, 0));
+                let called_by_name = node.children.len() > 1 &&
+                                     node.children[1].token.kind == TokenKind::LParen;
+                let (object, method, arguments_idx) = match called_by_name {
+                    true => (None, name(first), 2),
+                    false => (Some(try!(boxed(first))), try!(child(node, 2)).clone(), 4),
+                };
+                Ok(Expression::MethodInvocation {
+                       object: object,
+                       method: method,
+                       arguments: try!(arguments(node.children.get(arguments_idx))),
+                       span: span,
+                   })
+            }
+            // Array [ Expression ]
+            "ArrayAccess" => {
+                Ok(Expression::ArrayAccess {
+                       array: try!(boxed(try!(child(node, 0)))),
+                       index: try!(boxed(try!(child(node, 2)))),
+                       span: span,
+                   })
+            }
+            // Type DimExpr, or Type Dim in invalid programs
+            "ArrayCreationExpression" => {
+                let dim = try!(child(node, 1));
+                let size = match lexeme(dim) {
+                    "DimExpr" => Some(try!(boxed(try!(child(dim, 1))))),
+                    _ => None,
+                };
+                Ok(Expression::ArrayCreation {
+                       element: kind(try!(child(node, 0))),
+                       size: size,
+                       span: span,
+                   })
+            }
+            // Type ArgumentList?
+            "ClassInstanceCreationExpression" => {
+                Ok(Expression::ClassInstanceCreation {
+                       kind: kind(try!(child(node, 0))),
+                       arguments: try!(arguments(node.children.get(1))),
+                       span: span,
+                   })
+            }
+            // ( Type ) Expression or ( Type Dim ) Expression
+            "CastExpression" => {
+                let target = match node.children.len() {
+                    4 => kind(&node.children[1]),
+                    5 => array_of(&node.children[1]),
+                    _ => return Err(unexpected(node)),
+                };
+                Ok(Expression::Cast {
+                       kind: target,
+                       operand: try!(boxed(node.children.last().unwrap())),
+                       span: span,
+                   })
+            }
+            _ => Err(unexpected(node)),
+        }
+    }
+
+    /// The region of source code covered by this expression.
+    pub fn span(&self) -> Option<Span> {
+        match *self {
+            Expression::Literal(ref token) |
+            Expression::This(ref token) => token.span,
+            Expression::Name(ref name) => name.span(),
+            Expression::FieldAccess { span, .. } |
+            Expression::MethodInvocation { span, .. } |
+            Expression::ArrayAccess { span, .. } |
+            Expression::ArrayCreation { span, .. } |
+            Expression::ClassInstanceCreation { span, .. } |
+            Expression::Cast { span, .. } |
+            Expression::Unary { span, .. } |
+            Expression::Binary { span, .. } |
+            Expression::InstanceOf { span, .. } |
+            Expression::Assignment { span, .. } => span,
+        }
+    }
+}
+
+// The statements of a Block, without its braces.
+fn statements(block: &ASTNode) -> Result<Vec<Statement>, ASTError> {
+    let mut statements = Vec::new();
+    if block.children.len() == 3 {
+        for statement in list(&block.children[1], "BlockStatements") {
+            statements.push(try!(Statement::new(&statement)));
+        }
+    }
+    Ok(statements)
+}
+
+fn boxed_statement(node: &ASTNode) -> Result<Box<Statement>, ASTError> {
+    Statement::new(node).map(Box::new)
+}
+
+impl Statement {
+    /// Builds a Statement from an ASTNode, as it was written, eg. the body of
+    /// a method.
+    pub fn new(node: &ASTNode) -> Result<Statement, ASTError> {
+        let span = node.span();
+        if node.token.kind == TokenKind::Semicolon {
+            return Ok(Statement::Empty(span));
+        }
+
+        match lexeme(node) {
+            "Block" => {
+                Ok(Statement::Block {
+                       statements: try!(statements(node)),
+                       span: span,
+                       end: node.children.last().and_then(|c| c.span()),
+                   })
+            }
+            // Type Declarator, where the declarator may be an Assignment
+            "LocalVariableDeclaration" => {
+                let declarator = try!(child(node, 1));
+                let (name, initializer) = match declarator.token.kind {
+                    TokenKind::Assignment => {
+                        (try!(identifier(try!(child(declarator, 0)))),
+                         Some(try!(Expression::new(try!(child(declarator, 1))))))
+                    }
+                    _ => (try!(identifier(declarator)), None),
+                };
+                Ok(Statement::LocalVariableDeclaration {
+                       kind: kind(&node.children[0]),
+                       name: name,
+                       initializer: initializer,
+                       span: span,
+                   })
+            }
+            // if ( Expression ) Statement [else Statement]
+            "IfStatement" |
+            "IfElseStatement" |
+            "IfElseStatementNoShortIf" => {
+                let otherwise = match node.children.len() {
+                    7 => Some(try!(boxed_statement(&node.children[6]))),
+                    _ => None,
+                };
+                Ok(Statement::If {
+                       condition: try!(Expression::new(try!(child(node, 2)))),
+                       then: try!(boxed_statement(try!(child(node, 4)))),
+                       otherwise: otherwise,
+                       span: span,
+                   })
+            }
+            // while ( Expression ) Statement
+            "WhileStatement" |
+            "WhileStatementNoShortIf" => {
+                Ok(Statement::While {
+                       condition: try!(Expression::new(try!(child(node, 2)))),
+                       body: try!(boxed_statement(try!(child(node, 4)))),
+                       span: span,
+                   })
+            }
+            // for ( Init? ; Expression? ; Update? ) Statement
+            "ForStatement" |
+            "ForStatementNoShortIf" => {
+                let semicolons = node.children
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, c)| c.token.kind == TokenKind::Semicolon)
+                    .map(|(idx, _)| idx)
+                    .collect::<Vec<usize>>();
+                if semicolons.len() != 2 {
+                    return Err(unexpected(node));
+                }
+                let (first, second) = (semicolons[0], semicolons[1]);
+
+                let init = match first {
+                    3 => Some(try!(boxed_statement(&node.children[2]))),
+                    _ => None,
+                };
+                let condition = match second == first + 2 {
+                    true => Some(try!(Expression::new(&node.children[first + 1]))),
+                    false => None,
+                };
+                let update = match try!(child(node, second + 1)).token.kind {
+                    TokenKind::RParen => None,
+                    _ => Some(try!(Expression::new(&node.children[second + 1]))),
+                };
+                Ok(Statement::For {
+                       init: init,
+                       condition: condition,
+                       update: update,
+                       body: try!(boxed_statement(node.children.last().unwrap())),
+                       span: span,
+                   })
+            }
+            // return Expression? ;
+            "ReturnStatement" => {
+                let value = match node.children.len() {
+                    3 => Some(try!(Expression::new(&node.children[1]))),
+                    _ => None,
+                };
+                Ok(Statement::Return {
+                       value: value,
+                       span: span,
+                   })
+            }
+            _ => Expression::new(node).map(Statement::Expression),
+        }
+    }
+
+    /// The region of source code covered by this statement. An empty `return`
+    /// has none.
+    pub fn span(&self) -> Option<Span> {
+        match *self {
+            Statement::Block { span, .. } |
+            Statement::LocalVariableDeclaration { span, .. } |
+            Statement::If { span, .. } |
+            Statement::While { span, .. } |
+            Statement::For { span, .. } |
+            Statement::Return { span, .. } => span,
+            Statement::Expression(ref expression) => expression.span(),
+            Statement::Empty(span) => span,
+        }
+    }
+}
+
+impl Parameter {
+    /// Builds a Parameter from `Type Identifier`.
+    pub fn new(node: &ASTNode) -> Result<Parameter, ASTError> {
+        Ok(Parameter {
+               kind: kind(try!(child(node, 0))),
+               name: try!(identifier(try!(child(node, 1)))),
+           })
+    }
+}
+
+// The parameters of a MethodDeclarator or ConstructorDeclarator, ie. of
+// `Identifier ( ParameterList? )`.
+fn parameters(declarator: &ASTNode) -> Result<Vec<Parameter>, ASTError> {
+    let mut parameters = Vec::new();
+    if declarator.children.len() == 4 {
+        for parameter in list(&declarator.children[2], "ParameterList") {
+            parameters.push(try!(Parameter::new(&parameter)));
+        }
+    }
+    Ok(parameters)
+}
+
+impl Member {
+    /// Builds a Member from a ClassBodyDeclaration or
+    /// InterfaceMemberDeclaration.
+    pub fn new(node: &ASTNode) -> Result<Member, ASTError> {
+        let span = node.span();
+        match lexeme(node) {
+            // Modifiers Type Identifier ;
+            // Modifiers Type Identifier = Expression ;
+            "FieldDeclaration" |
+            "ConstantDeclaration" => {
+                let initializer = match node.children.len() {
+                    6 => Some(try!(Expression::new(&node.children[4]))),
+                    _ => None,
+                };
+                Ok(Member::Field(Field {
+                                     modifiers: modifiers(try!(child(node, 0))),
+                                     kind: kind(try!(child(node, 1))),
+                                     name: try!(identifier(try!(child(node, 2)))),
+                                     initializer: initializer,
+                                     span: span,
+                                 }))
+            }
+            // MethodHeader Block, or a MethodHeader alone
+            "MethodDeclaration" |
+            "AbstractMethodDeclaration" => {
+                let header = try!(child(node, 0));
+                let declarator = try!(child(header, 2));
+                let body = match node.children.get(1) {
+                    Some(b) if lexeme(b) == "Block" => Some(try!(Statement::new(b))),
+                    _ => None,
+                };
+                Ok(Member::Method(Method {
+                                      modifiers: modifiers(try!(child(header, 0))),
+                                      return_type: kind(try!(child(header, 1))),
+                                      name: try!(identifier(try!(child(declarator, 0)))),
+                                      parameters: try!(parameters(declarator)),
+                                      body: body,
+                                      span: span,
+                                  }))
+            }
+            // Modifiers ConstructorDeclarator Block
+            "ConstructorDeclaration" => {
+                let declarator = try!(child(node, 1));
+                Ok(Member::Constructor(Constructor {
+                                           modifiers: modifiers(try!(child(node, 0))),
+                                           name: try!(identifier(try!(child(declarator, 0)))),
+                                           parameters: try!(parameters(declarator)),
+                                           body: try!(Statement::new(try!(child(node, 2)))),
+                                           span: span,
+                                       }))
+            }
+            _ => Err(unexpected(node)),
+        }
+    }
+}
+
+impl TypeDeclaration {
+    /// Builds a TypeDeclaration from a ClassDeclaration or
+    /// InterfaceDeclaration, ie. from the root of an AST.
+    pub fn new(node: &ASTNode) -> Result<TypeDeclaration, ASTError> {
+        let type_kind = match lexeme(node) {
+            "ClassDeclaration" => TypeKind::Class,
+            "InterfaceDeclaration" => TypeKind::Interface,
+            _ => return Err(unexpected(node)),
+        };
+
+        let mut declaration = TypeDeclaration {
+            kind: type_kind,
+            modifiers: modifiers(try!(child(node, 0))),
+            name: try!(identifier(try!(child(node, 2)))),
+            extends: Vec::new(),
+            implements: Vec::new(),
+            members: Vec::new(),
+            span: node.span(),
+        };
+
+        // Modifiers Class Identifier Extends? Implements? Body
+        for part in node.children.iter().skip(3) {
+            match lexeme(part) {
+                "ClassExtends" |
+                "InterfaceExtends" => {
+                    for parent in list(try!(child(part, 1)), "ClassOrInterfaceTypeList") {
+                        declaration.extends.push(name(&parent));
+                    }
+                }
+                "Implements" => {
+                    for parent in list(try!(child(part, 1)), "ClassOrInterfaceTypeList") {
+                        declaration.implements.push(name(&parent));
+                    }
+                }
+                "ClassBody" | "InterfaceBody" if part.children.len() == 3 => {
+                    let members = match type_kind {
+                        TypeKind::Class => "ClassBodyDeclarations",
+                        TypeKind::Interface => "InterfaceMemberDeclarations",
+                    };
+                    for member in list(&part.children[1], members) {
+                        declaration.members.push(try!(Member::new(&member)));
+                    }
+                }
+                "ClassBody" | "InterfaceBody" => (),
+                _ => return Err(unexpected(part)),
+            }
+        }
+
+        Ok(declaration)
+    }
+}
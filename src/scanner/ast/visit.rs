@@ -0,0 +1,658 @@
+//! Traversals of the typed AST.
+//!
+//! A `Visitor` walks a tree by reference, a `VisitorMut` walks it by mutable
+//! reference and a `Folder` rebuilds it from its parts. Each method defaults to
+//! walking every child of its node, through the matching `walk_*`,
+//! `walk_*_mut` or `fold_*` function, so a pass only overrides the methods for
+//! the nodes it cares about, calling the walk function itself to continue into
+//! their children.
+//!
+//! ```
+//! use juicyj::scanner::typed::Expression;
+//! use juicyj::scanner::visit;
+//! use juicyj::scanner::visit::Visitor;
+//!
+//! // Counts the method invocations in a file.
+//! struct Calls(usize);
+//!
+//! impl Visitor for Calls {
+//!     fn visit_expression(&mut self, node: &Expression) {
+//!         if let Expression::MethodInvocation { .. } = *node {
+//!             self.0 += 1;
+//!         }
+//!         visit::walk_expression(self, node);
+//!     }
+//! }
+//!
+//! let source = "public class Foo { public Foo() { f(g()); } \
+//!               public int f(int x) { return x; } public int g() { return 1; } }";
+//! let mut sources = juicyj::scanner::SourceMap::new();
+//! let file = sources.add("Foo.java", source);
+//! let ast = juicyj::scanner::scan(&sources, file).unwrap();
+//! let mut calls = Calls(0);
+//! calls.visit_type_declaration(ast.declaration.as_ref().unwrap());
+//! assert_eq!(calls.0, 2);
+//! ```
+use scanner::ast::ASTNode;
+use scanner::ast::typed::Constructor;
+use scanner::ast::typed::Expression;
+use scanner::ast::typed::Field;
+use scanner::ast::typed::Member;
+use scanner::ast::typed::Method;
+use scanner::ast::typed::Parameter;
+use scanner::ast::typed::Statement;
+use scanner::ast::typed::TypeDeclaration;
+
+/// Walks a typed AST by reference.
+pub trait Visitor {
+    fn visit_type_declaration(&mut self, node: &TypeDeclaration) {
+        walk_type_declaration(self, node)
+    }
+
+    fn visit_member(&mut self, node: &Member) {
+        walk_member(self, node)
+    }
+
+    fn visit_field(&mut self, node: &Field) {
+        walk_field(self, node)
+    }
+
+    fn visit_method(&mut self, node: &Method) {
+        walk_method(self, node)
+    }
+
+    fn visit_constructor(&mut self, node: &Constructor) {
+        walk_constructor(self, node)
+    }
+
+    fn visit_parameter(&mut self, node: &Parameter) {
+        walk_parameter(self, node)
+    }
+
+    fn visit_statement(&mut self, node: &Statement) {
+        walk_statement(self, node)
+    }
+
+    fn visit_expression(&mut self, node: &Expression) {
+        walk_expression(self, node)
+    }
+
+    /// Visits a type as written, eg. the `int[]` in `int[] x;`.
+    fn visit_type(&mut self, _node: &ASTNode) {}
+
+    /// Visits a Name used as an expression, eg. the `a.b` in `a.b + 1`.
+    fn visit_name(&mut self, _node: &ASTNode) {}
+}
+
+pub fn walk_type_declaration<V: Visitor + ?Sized>(visitor: &mut V, node: &TypeDeclaration) {
+    for kind in node.extends.iter().chain(node.implements.iter()) {
+        visitor.visit_type(kind);
+    }
+    for member in &node.members {
+        visitor.visit_member(member);
+    }
+}
+
+pub fn walk_member<V: Visitor + ?Sized>(visitor: &mut V, node: &Member) {
+    match *node {
+        Member::Field(ref field) => visitor.visit_field(field),
+        Member::Method(ref method) => visitor.visit_method(method),
+        Member::Constructor(ref constructor) => visitor.visit_constructor(constructor),
+    }
+}
+
+pub fn walk_field<V: Visitor + ?Sized>(visitor: &mut V, node: &Field) {
+    visitor.visit_type(&node.kind);
+    if let Some(ref initializer) = node.initializer {
+        visitor.visit_expression(initializer);
+    }
+}
+
+pub fn walk_method<V: Visitor + ?Sized>(visitor: &mut V, node: &Method) {
+    visitor.visit_type(&node.return_type);
+    for parameter in &node.parameters {
+        visitor.visit_parameter(parameter);
+    }
+    if let Some(ref body) = node.body {
+        visitor.visit_statement(body);
+    }
+}
+
+pub fn walk_constructor<V: Visitor + ?Sized>(visitor: &mut V, node: &Constructor) {
+    for parameter in &node.parameters {
+        visitor.visit_parameter(parameter);
+    }
+    visitor.visit_statement(&node.body);
+}
+
+pub fn walk_parameter<V: Visitor + ?Sized>(visitor: &mut V, node: &Parameter) {
+    visitor.visit_type(&node.kind);
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, node: &Statement) {
+    match *node {
+        Statement::Block { ref statements, .. } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+        Statement::LocalVariableDeclaration { ref kind, ref initializer, .. } => {
+            visitor.visit_type(kind);
+            if let Some(ref initializer) = *initializer {
+                visitor.visit_expression(initializer);
+            }
+        }
+        Statement::If { ref condition, ref then, ref otherwise, .. } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(then);
+            if let Some(ref otherwise) = *otherwise {
+                visitor.visit_statement(otherwise);
+            }
+        }
+        Statement::While { ref condition, ref body, .. } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(body);
+        }
+        Statement::For { ref init, ref condition, ref update, ref body, .. } => {
+            if let Some(ref init) = *init {
+                visitor.visit_statement(init);
+            }
+            if let Some(ref condition) = *condition {
+                visitor.visit_expression(condition);
+            }
+            if let Some(ref update) = *update {
+                visitor.visit_expression(update);
+            }
+            visitor.visit_statement(body);
+        }
+        Statement::Return { ref value, .. } => {
+            if let Some(ref value) = *value {
+                visitor.visit_expression(value);
+            }
+        }
+        Statement::Expression(ref expression) => visitor.visit_expression(expression),
+        Statement::Empty(_) => (),
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, node: &Expression) {
+    match *node {
+        Expression::Literal(_) |
+        Expression::This(_) => (),
+        Expression::Name(ref name) => visitor.visit_name(name),
+        Expression::FieldAccess { ref object, .. } => visitor.visit_expression(object),
+        Expression::MethodInvocation { ref object, ref method, ref arguments, .. } => {
+            match *object {
+                Some(ref object) => visitor.visit_expression(object),
+                None => visitor.visit_name(method),
+            }
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        Expression::ArrayAccess { ref array, ref index, .. } => {
+            visitor.visit_expression(array);
+            visitor.visit_expression(index);
+        }
+        Expression::ArrayCreation { ref element, ref size, .. } => {
+            visitor.visit_type(element);
+            if let Some(ref size) = *size {
+                visitor.visit_expression(size);
+            }
+        }
+        Expression::ClassInstanceCreation { ref kind, ref arguments, .. } => {
+            visitor.visit_type(kind);
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        Expression::Cast { ref kind, ref operand, .. } => {
+            visitor.visit_type(kind);
+            visitor.visit_expression(operand);
+        }
+        Expression::Unary { ref operand, .. } => visitor.visit_expression(operand),
+        Expression::Binary { ref lhs, ref rhs, .. } |
+        Expression::Assignment { ref lhs, ref rhs, .. } => {
+            visitor.visit_expression(lhs);
+            visitor.visit_expression(rhs);
+        }
+        Expression::InstanceOf { ref operand, ref kind, .. } => {
+            visitor.visit_expression(operand);
+            visitor.visit_type(kind);
+        }
+    }
+}
+
+/// Walks a typed AST by mutable reference, eg. to rewrite it in place.
+pub trait VisitorMut {
+    fn visit_type_declaration_mut(&mut self, node: &mut TypeDeclaration) {
+        walk_type_declaration_mut(self, node)
+    }
+
+    fn visit_member_mut(&mut self, node: &mut Member) {
+        walk_member_mut(self, node)
+    }
+
+    fn visit_field_mut(&mut self, node: &mut Field) {
+        walk_field_mut(self, node)
+    }
+
+    fn visit_method_mut(&mut self, node: &mut Method) {
+        walk_method_mut(self, node)
+    }
+
+    fn visit_constructor_mut(&mut self, node: &mut Constructor) {
+        walk_constructor_mut(self, node)
+    }
+
+    fn visit_parameter_mut(&mut self, node: &mut Parameter) {
+        walk_parameter_mut(self, node)
+    }
+
+    fn visit_statement_mut(&mut self, node: &mut Statement) {
+        walk_statement_mut(self, node)
+    }
+
+    fn visit_expression_mut(&mut self, node: &mut Expression) {
+        walk_expression_mut(self, node)
+    }
+
+    /// Visits a type as written, eg. the `int[]` in `int[] x;`.
+    fn visit_type_mut(&mut self, _node: &mut ASTNode) {}
+
+    /// Visits a Name used as an expression, eg. the `a.b` in `a.b + 1`.
+    fn visit_name_mut(&mut self, _node: &mut ASTNode) {}
+}
+
+pub fn walk_type_declaration_mut<V: VisitorMut + ?Sized>(visitor: &mut V,
+                                                         node: &mut TypeDeclaration) {
+    for kind in node.extends.iter_mut().chain(node.implements.iter_mut()) {
+        visitor.visit_type_mut(kind);
+    }
+    for member in &mut node.members {
+        visitor.visit_member_mut(member);
+    }
+}
+
+pub fn walk_member_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Member) {
+    match *node {
+        Member::Field(ref mut field) => visitor.visit_field_mut(field),
+        Member::Method(ref mut method) => visitor.visit_method_mut(method),
+        Member::Constructor(ref mut constructor) => visitor.visit_constructor_mut(constructor),
+    }
+}
+
+pub fn walk_field_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Field) {
+    visitor.visit_type_mut(&mut node.kind);
+    if let Some(ref mut initializer) = node.initializer {
+        visitor.visit_expression_mut(initializer);
+    }
+}
+
+pub fn walk_method_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Method) {
+    visitor.visit_type_mut(&mut node.return_type);
+    for parameter in &mut node.parameters {
+        visitor.visit_parameter_mut(parameter);
+    }
+    if let Some(ref mut body) = node.body {
+        visitor.visit_statement_mut(body);
+    }
+}
+
+pub fn walk_constructor_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Constructor) {
+    for parameter in &mut node.parameters {
+        visitor.visit_parameter_mut(parameter);
+    }
+    visitor.visit_statement_mut(&mut node.body);
+}
+
+pub fn walk_parameter_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Parameter) {
+    visitor.visit_type_mut(&mut node.kind);
+}
+
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Statement) {
+    match *node {
+        Statement::Block { ref mut statements, .. } => {
+            for statement in statements {
+                visitor.visit_statement_mut(statement);
+            }
+        }
+        Statement::LocalVariableDeclaration { ref mut kind, ref mut initializer, .. } => {
+            visitor.visit_type_mut(kind);
+            if let Some(ref mut initializer) = *initializer {
+                visitor.visit_expression_mut(initializer);
+            }
+        }
+        Statement::If { ref mut condition, ref mut then, ref mut otherwise, .. } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_statement_mut(then);
+            if let Some(ref mut otherwise) = *otherwise {
+                visitor.visit_statement_mut(otherwise);
+            }
+        }
+        Statement::While { ref mut condition, ref mut body, .. } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_statement_mut(body);
+        }
+        Statement::For { ref mut init, ref mut condition, ref mut update, ref mut body, .. } => {
+            if let Some(ref mut init) = *init {
+                visitor.visit_statement_mut(init);
+            }
+            if let Some(ref mut condition) = *condition {
+                visitor.visit_expression_mut(condition);
+            }
+            if let Some(ref mut update) = *update {
+                visitor.visit_expression_mut(update);
+            }
+            visitor.visit_statement_mut(body);
+        }
+        Statement::Return { ref mut value, .. } => {
+            if let Some(ref mut value) = *value {
+                visitor.visit_expression_mut(value);
+            }
+        }
+        Statement::Expression(ref mut expression) => visitor.visit_expression_mut(expression),
+        Statement::Empty(_) => (),
+    }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Expression) {
+    match *node {
+        Expression::Literal(_) |
+        Expression::This(_) => (),
+        Expression::Name(ref mut name) => visitor.visit_name_mut(name),
+        Expression::FieldAccess { ref mut object, .. } => visitor.visit_expression_mut(object),
+        Expression::MethodInvocation { ref mut object,
+                                       ref mut method,
+                                       ref mut arguments,
+                                       .. } => {
+            match *object {
+                Some(ref mut object) => visitor.visit_expression_mut(object),
+                None => visitor.visit_name_mut(method),
+            }
+            for argument in arguments {
+                visitor.visit_expression_mut(argument);
+            }
+        }
+        Expression::ArrayAccess { ref mut array, ref mut index, .. } => {
+            visitor.visit_expression_mut(array);
+            visitor.visit_expression_mut(index);
+        }
+        Expression::ArrayCreation { ref mut element, ref mut size, .. } => {
+            visitor.visit_type_mut(element);
+            if let Some(ref mut size) = *size {
+                visitor.visit_expression_mut(size);
+            }
+        }
+        Expression::ClassInstanceCreation { ref mut kind, ref mut arguments, .. } => {
+            visitor.visit_type_mut(kind);
+            for argument in arguments {
+                visitor.visit_expression_mut(argument);
+            }
+        }
+        Expression::Cast { ref mut kind, ref mut operand, .. } => {
+            visitor.visit_type_mut(kind);
+            visitor.visit_expression_mut(operand);
+        }
+        Expression::Unary { ref mut operand, .. } => visitor.visit_expression_mut(operand),
+        Expression::Binary { ref mut lhs, ref mut rhs, .. } |
+        Expression::Assignment { ref mut lhs, ref mut rhs, .. } => {
+            visitor.visit_expression_mut(lhs);
+            visitor.visit_expression_mut(rhs);
+        }
+        Expression::InstanceOf { ref mut operand, ref mut kind, .. } => {
+            visitor.visit_expression_mut(operand);
+            visitor.visit_type_mut(kind);
+        }
+    }
+}
+
+/// Rebuilds a typed AST, eg. to replace expressions with ones of a different
+/// kind.
+pub trait Folder {
+    fn fold_type_declaration(&mut self, node: TypeDeclaration) -> TypeDeclaration {
+        fold_type_declaration(self, node)
+    }
+
+    fn fold_member(&mut self, node: Member) -> Member {
+        fold_member(self, node)
+    }
+
+    fn fold_field(&mut self, node: Field) -> Field {
+        fold_field(self, node)
+    }
+
+    fn fold_method(&mut self, node: Method) -> Method {
+        fold_method(self, node)
+    }
+
+    fn fold_constructor(&mut self, node: Constructor) -> Constructor {
+        fold_constructor(self, node)
+    }
+
+    fn fold_parameter(&mut self, node: Parameter) -> Parameter {
+        fold_parameter(self, node)
+    }
+
+    fn fold_statement(&mut self, node: Statement) -> Statement {
+        fold_statement(self, node)
+    }
+
+    fn fold_expression(&mut self, node: Expression) -> Expression {
+        fold_expression(self, node)
+    }
+
+    /// Folds a type as written, eg. the `int[]` in `int[] x;`.
+    fn fold_type(&mut self, node: ASTNode) -> ASTNode {
+        node
+    }
+
+    /// Folds a Name used as an expression, eg. the `a.b` in `a.b + 1`.
+    fn fold_name(&mut self, node: ASTNode) -> ASTNode {
+        node
+    }
+}
+
+fn fold_boxed<F, T, G>(folder: &mut F, node: Box<T>, fold: G) -> Box<T>
+    where F: Folder + ?Sized,
+          G: FnOnce(&mut F, T) -> T
+{
+    Box::new(fold(folder, *node))
+}
+
+fn fold_expressions<F: Folder + ?Sized>(folder: &mut F,
+                                        nodes: Vec<Expression>)
+                                        -> Vec<Expression> {
+    nodes.into_iter().map(|e| folder.fold_expression(e)).collect()
+}
+
+pub fn fold_type_declaration<F: Folder + ?Sized>(folder: &mut F,
+                                                 node: TypeDeclaration)
+                                                 -> TypeDeclaration {
+    TypeDeclaration {
+        extends: node.extends.into_iter().map(|k| folder.fold_type(k)).collect(),
+        implements: node.implements.into_iter().map(|k| folder.fold_type(k)).collect(),
+        members: node.members.into_iter().map(|m| folder.fold_member(m)).collect(),
+        ..node
+    }
+}
+
+pub fn fold_member<F: Folder + ?Sized>(folder: &mut F, node: Member) -> Member {
+    match node {
+        Member::Field(field) => Member::Field(folder.fold_field(field)),
+        Member::Method(method) => Member::Method(folder.fold_method(method)),
+        Member::Constructor(constructor) => {
+            Member::Constructor(folder.fold_constructor(constructor))
+        }
+    }
+}
+
+pub fn fold_field<F: Folder + ?Sized>(folder: &mut F, node: Field) -> Field {
+    Field {
+        kind: folder.fold_type(node.kind),
+        initializer: node.initializer.map(|e| folder.fold_expression(e)),
+        ..node
+    }
+}
+
+pub fn fold_method<F: Folder + ?Sized>(folder: &mut F, node: Method) -> Method {
+    Method {
+        return_type: folder.fold_type(node.return_type),
+        parameters: node.parameters.into_iter().map(|p| folder.fold_parameter(p)).collect(),
+        body: node.body.map(|s| folder.fold_statement(s)),
+        ..node
+    }
+}
+
+pub fn fold_constructor<F: Folder + ?Sized>(folder: &mut F, node: Constructor) -> Constructor {
+    Constructor {
+        parameters: node.parameters.into_iter().map(|p| folder.fold_parameter(p)).collect(),
+        body: folder.fold_statement(node.body),
+        ..node
+    }
+}
+
+pub fn fold_parameter<F: Folder + ?Sized>(folder: &mut F, node: Parameter) -> Parameter {
+    Parameter {
+        kind: folder.fold_type(node.kind),
+        name: node.name,
+    }
+}
+
+pub fn fold_statement<F: Folder + ?Sized>(folder: &mut F, node: Statement) -> Statement {
+    match node {
+        Statement::Block { statements, span, end } => {
+            Statement::Block {
+                statements: statements.into_iter().map(|s| folder.fold_statement(s)).collect(),
+                span: span,
+                end: end,
+            }
+        }
+        Statement::LocalVariableDeclaration { kind, name, initializer, span } => {
+            Statement::LocalVariableDeclaration {
+                kind: folder.fold_type(kind),
+                name: name,
+                initializer: initializer.map(|e| folder.fold_expression(e)),
+                span: span,
+            }
+        }
+        Statement::If { condition, then, otherwise, span } => {
+            Statement::If {
+                condition: folder.fold_expression(condition),
+                then: fold_boxed(folder, then, F::fold_statement),
+                otherwise: otherwise.map(|s| fold_boxed(folder, s, F::fold_statement)),
+                span: span,
+            }
+        }
+        Statement::While { condition, body, span } => {
+            Statement::While {
+                condition: folder.fold_expression(condition),
+                body: fold_boxed(folder, body, F::fold_statement),
+                span: span,
+            }
+        }
+        Statement::For { init, condition, update, body, span } => {
+            Statement::For {
+                init: init.map(|s| fold_boxed(folder, s, F::fold_statement)),
+                condition: condition.map(|e| folder.fold_expression(e)),
+                update: update.map(|e| folder.fold_expression(e)),
+                body: fold_boxed(folder, body, F::fold_statement),
+                span: span,
+            }
+        }
+        Statement::Return { value, span } => {
+            Statement::Return {
+                value: value.map(|e| folder.fold_expression(e)),
+                span: span,
+            }
+        }
+        Statement::Expression(expression) => {
+            Statement::Expression(folder.fold_expression(expression))
+        }
+        Statement::Empty(span) => Statement::Empty(span),
+    }
+}
+
+pub fn fold_expression<F: Folder + ?Sized>(folder: &mut F, node: Expression) -> Expression {
+    match node {
+        Expression::Literal(_) |
+        Expression::This(_) => node,
+        Expression::Name(name) => Expression::Name(folder.fold_name(name)),
+        Expression::FieldAccess { object, field, span } => {
+            Expression::FieldAccess {
+                object: fold_boxed(folder, object, F::fold_expression),
+                field: field,
+                span: span,
+            }
+        }
+        Expression::MethodInvocation { object, method, arguments, span } => {
+            let (object, method) = match object {
+                Some(object) => (Some(fold_boxed(folder, object, F::fold_expression)), method),
+                None => (None, folder.fold_name(method)),
+            };
+            Expression::MethodInvocation {
+                object: object,
+                method: method,
+                arguments: fold_expressions(folder, arguments),
+                span: span,
+            }
+        }
+        Expression::ArrayAccess { array, index, span } => {
+            Expression::ArrayAccess {
+                array: fold_boxed(folder, array, F::fold_expression),
+                index: fold_boxed(folder, index, F::fold_expression),
+                span: span,
+            }
+        }
+        Expression::ArrayCreation { element, size, span } => {
+            Expression::ArrayCreation {
+                element: folder.fold_type(element),
+                size: size.map(|e| fold_boxed(folder, e, F::fold_expression)),
+                span: span,
+            }
+        }
+        Expression::ClassInstanceCreation { kind, arguments, span } => {
+            Expression::ClassInstanceCreation {
+                kind: folder.fold_type(kind),
+                arguments: fold_expressions(folder, arguments),
+                span: span,
+            }
+        }
+        Expression::Cast { kind, operand, span } => {
+            Expression::Cast {
+                kind: folder.fold_type(kind),
+                operand: fold_boxed(folder, operand, F::fold_expression),
+                span: span,
+            }
+        }
+        Expression::Unary { operator, operand, span } => {
+            Expression::Unary {
+                operator: operator,
+                operand: fold_boxed(folder, operand, F::fold_expression),
+                span: span,
+            }
+        }
+        Expression::Binary { operator, lhs, rhs, span } => {
+            Expression::Binary {
+                operator: operator,
+                lhs: fold_boxed(folder, lhs, F::fold_expression),
+                rhs: fold_boxed(folder, rhs, F::fold_expression),
+                span: span,
+            }
+        }
+        Expression::InstanceOf { operand, kind, span } => {
+            Expression::InstanceOf {
+                operand: fold_boxed(folder, operand, F::fold_expression),
+                kind: folder.fold_type(kind),
+                span: span,
+            }
+        }
+        Expression::Assignment { lhs, rhs, span } => {
+            Expression::Assignment {
+                lhs: fold_boxed(folder, lhs, F::fold_expression),
+                rhs: fold_boxed(folder, rhs, F::fold_expression),
+                span: span,
+            }
+        }
+    }
+}
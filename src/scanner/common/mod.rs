@@ -1,4 +1,7 @@
+pub mod span;
 mod tokens;
 
+pub use self::span::SourceMap;
+pub use self::span::Span;
 pub use self::tokens::Token;
 pub use self::tokens::TokenKind;
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
+/// A region of source code. Recorded by the lexer for every token and carried
+/// through the parse tree, AST and analysis environments so that later stages
+/// can point at the code which caused them to fail.
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Span {
+    /// id of the source file, as returned by `SourceMap::add`
+    pub file: usize,
+    /// line number (1-indexed) of the first character
+    pub line: u32,
+    /// column (1-indexed) of the first character
+    pub column: u32,
+    /// byte offset of the first character
+    pub start: usize,
+    /// byte offset one past the last character
+    pub end: usize,
+}
+
+impl Span {
+    /// Builds the smallest span covering both `self` and `other`. Spans from
+    /// different files can not be merged; `self` is returned unchanged.
+    pub fn to(&self, other: &Span) -> Span {
+        if self.file != other.file {
+            return *self;
+        }
+
+        let first = if other.start < self.start { other } else { self };
+        Span {
+            file: self.file,
+            line: first.line,
+            column: first.column,
+            start: first.start,
+            end: if other.end > self.end { other.end } else { self.end },
+        }
+    }
+
+    /// The code covered by this span, given the contents of its file.
+    pub fn text<'src>(&self, src: &'src str) -> &'src str {
+        src.get(self.start..self.end).unwrap_or("")
+    }
+
+    /// The full line containing the start of this span, given the contents of
+    /// its file.
+    pub fn source_line<'src>(&self, src: &'src str) -> &'src str {
+        src.split('\n').nth((self.line - 1) as usize).unwrap_or("")
+    }
+}
+
+/// Merges two optional spans, preferring whichever one exists.
+pub fn merge(lhs: Option<Span>, rhs: Option<Span>) -> Option<Span> {
+    match (lhs, rhs) {
+        (Some(l), Some(r)) => Some(l.to(&r)),
+        (Some(l), None) => Some(l),
+        (None, r) => r,
+    }
+}
+
+/// The source files of a single compilation. Spans only record the id of
+/// their file, so their text is resolved through the SourceMap which holds it.
+///
+/// # Examples
+///
+/// ```rust
+/// use juicyj::scanner::SourceMap;
+///
+/// let mut sources = SourceMap::new();
+/// let file = sources.add("Foo.java", "public class Foo { public Foo() {} }");
+/// assert_eq!(sources.name(file), Some("Foo.java"));
+/// ```
+#[derive(Clone,Debug)]
+pub struct SourceMap {
+    files: Vec<(String, String)>,
+}
+
+impl SourceMap {
+    /// Constructs an empty SourceMap.
+    pub fn new() -> SourceMap {
+        SourceMap { files: Vec::new() }
+    }
+
+    /// Records a source file and returns the id to be stored in its spans.
+    pub fn add(&mut self, name: &str, src: &str) -> usize {
+        self.files.push((name.to_owned(), src.to_owned()));
+        self.files.len() - 1
+    }
+
+    /// The number of files recorded so far. Their ids are `0..len()`.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// The name of a recorded source file.
+    pub fn name(&self, file: usize) -> Option<&str> {
+        self.files.get(file).map(|f| &f.0[..])
+    }
+
+    /// The contents of a recorded source file.
+    pub fn contents(&self, file: usize) -> Option<&str> {
+        self.files.get(file).map(|f| &f.1[..])
+    }
+
+    /// The source code covered by a span, or an empty string if its file is
+    /// unknown.
+    pub fn text(&self, span: &Span) -> &str {
+        self.contents(span.file).map(|src| span.text(src)).unwrap_or("")
+    }
+
+    /// The full source line containing the start of a span, or an empty
+    /// string if its file is unknown.
+    pub fn source_line(&self, span: &Span) -> &str {
+        self.contents(span.file).map(|src| span.source_line(src)).unwrap_or("")
+    }
+
+    /// The location of a span as `file:line:column`.
+    pub fn location(&self, span: &Span) -> String {
+        format!("{}:{}:{}",
+                self.name(span.file).unwrap_or("<unknown>"),
+                span.line,
+                span.column)
+    }
+}
+
+// Written as the list of file names, so that the `file` id of a serialized Span
+// can be traced back to its file.
+impl ToJson for SourceMap {
+    fn to_json(&self) -> Json {
+        self.files.iter().map(|f| f.0.clone()).collect::<Vec<String>>().to_json()
+    }
+}
+
+impl ToJson for Span {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("file".to_owned(), self.file.to_json());
+        object.insert("line".to_owned(), self.line.to_json());
+        object.insert("column".to_owned(), self.column.to_json());
+        object.insert("start".to_owned(), self.start.to_json());
+        object.insert("end".to_owned(), self.end.to_json());
+        Json::Object(object)
+    }
+}
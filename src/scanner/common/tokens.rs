@@ -1,12 +1,22 @@
 // TODO: global tokens module
+use std::collections::BTreeMap;
 use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::str::FromStr;
 
-#[derive(Clone,Debug,Eq,Hash,PartialEq)]
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
+use scanner::common::Span;
+
+#[derive(Clone,Debug)]
 #[allow(missing_docs)]
 pub struct Token {
     pub kind: TokenKind,
     pub lexeme: Option<String>,
+    /// where this token came from, if it was read from a source file
+    pub span: Option<Span>,
 }
 
 #[allow(missing_docs)]
@@ -18,10 +28,28 @@ impl Token {
                 Some(l) => Some(l.to_owned()),
                 _ => None,
             },
+            span: None,
         }
     }
 }
 
+// Spans are deliberately ignored: two tokens are the same token no matter
+// where in the source they were read from.
+impl PartialEq for Token {
+    fn eq(&self, other: &Token) -> bool {
+        self.kind == other.kind && self.lexeme == other.lexeme
+    }
+}
+
+impl Eq for Token {}
+
+impl Hash for Token {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.lexeme.hash(state);
+    }
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.lexeme {
@@ -36,6 +64,16 @@ impl fmt::Display for Token {
     }
 }
 
+impl ToJson for Token {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("kind".to_owned(), format!("{:?}", self.kind).to_json());
+        object.insert("lexeme".to_owned(), self.lexeme.to_json());
+        object.insert("span".to_owned(), self.span.to_json());
+        Json::Object(object)
+    }
+}
+
 #[derive(Clone,Debug,Eq,Hash,PartialEq)]
 #[allow(missing_docs)]
 pub enum TokenKind {
@@ -26,3 +26,38 @@ fn test_valid_start() {
     assert!(!super::identifier::valid_start(' '));
     assert!(!super::identifier::valid_start('\n'));
 }
+
+#[test]
+fn test_spans() {
+    let src = "class Foo {\n\n\tint bar;\n}";
+    let tokens = super::Lexer::new("Foo.java", 3, src).map(|t| t.ok().unwrap()).collect::<Vec<_>>();
+
+    let span = tokens[1].span.unwrap();
+    assert_eq!(span.line, 1);
+    assert_eq!(span.column, 7);
+    assert_eq!(&src[span.start..span.end], "Foo");
+    assert_eq!(span.file, 3);
+
+    let span = tokens[4].span.unwrap();
+    assert_eq!(span.line, 3);
+    assert_eq!(span.column, 6);
+    assert_eq!(&src[span.start..span.end], "bar");
+    assert_eq!(span.source_line(src), "\tint bar;");
+
+    let span = tokens[6].span.unwrap();
+    assert_eq!(span.line, 4);
+    assert_eq!(&src[span.start..span.end], "}");
+}
+
+#[test]
+fn test_span_text_out_of_range() {
+    let span = ::scanner::common::Span {
+        file: 0,
+        line: 1,
+        column: 1,
+        start: 2,
+        end: 40,
+    };
+    assert_eq!(span.text("é"), "");
+    assert_eq!(span.text("public"), "");
+}
@@ -7,6 +7,7 @@ use std::str::Split;
 
 use error::ErrorMessage;
 use error::LexerError;
+use scanner::common::Span;
 use scanner::common::Token;
 use scanner::common::TokenKind;
 use scanner::lexer::identifier;
@@ -15,7 +16,10 @@ use scanner::lexer::identifier;
 pub struct Lexer<'file, 'src> {
     current: Option<char>,
     file: &'file str,
+    file_id: usize,
+    index_byte: usize,
     index_character: u32,
+    index_column: u32,
     index_line: u32,
     line: Option<&'src str>,
     line_iter: Peekable<Chain<Chars<'src>, IntoIter<char>>>,
@@ -23,7 +27,7 @@ pub struct Lexer<'file, 'src> {
 }
 
 impl<'file, 'src> Lexer<'file, 'src> {
-    pub fn new(file: &'file str, src: &'src str) -> Lexer<'file, 'src> {
+    pub fn new(file: &'file str, file_id: usize, src: &'src str) -> Lexer<'file, 'src> {
         let mut lines = src.split('\n').peekable();
         let line = lines.next();
         let mut line_iter = match line {
@@ -34,7 +38,10 @@ impl<'file, 'src> Lexer<'file, 'src> {
         Lexer {
             current: line_iter.next(),
             file: file,
+            file_id: file_id,
+            index_byte: 0,
             index_character: 0,
+            index_column: 0,
             index_line: 1,
             line: line,
             line_iter: line_iter,
@@ -43,13 +50,19 @@ impl<'file, 'src> Lexer<'file, 'src> {
     }
 
     fn consume(&mut self) {
+        if let Some(ch) = self.current {
+            self.index_byte += ch.len_utf8();
+        }
+
         self.current = match self.line_iter.next() {
             Some(ch) if ch == '\t' => {
                 self.index_character += 4;
+                self.index_column += 1;
                 Some(ch)
             }
             Some(ch) => {
                 self.index_character += 1;
+                self.index_column += 1;
                 Some(ch)
             }
             None => {
@@ -59,13 +72,17 @@ impl<'file, 'src> Lexer<'file, 'src> {
 
                     self.line = self.lines.next();
                     self.line_iter = match self.line {
-                        Some("") => continue,
+                        Some("") => {
+                            self.index_byte += 1;
+                            continue;
+                        }
                         Some(line) => line.chars().chain(Some('\n')).peekable(),
                         None => "".chars().chain(None).peekable(),
                     };
                     break;
                 }
                 self.index_character = 0;
+                self.index_column = 0;
 
                 match self.line_iter.next() {
                     Some(ch) if ch == '\t' => {
@@ -89,6 +106,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
             line: self.line.unwrap_or("").to_owned(),
             line_number: self.index_line,
             message: message,
+            span: None,
         }
     }
 
@@ -151,6 +169,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
                                Ok(Token {
                                       kind: kind,
                                       lexeme: None,
+                                      span: None,
                                   })
                            }
                            None => Err(self.error(ErrorMessage::InvalidToken)),
@@ -195,6 +214,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
                            Ok(Token {
                                   kind: kind,
                                   lexeme: None,
+                                  span: None,
                               })
                        }
                        None => Err(self.error(ErrorMessage::InvalidToken)),
@@ -204,6 +224,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
         return Ok(Token {
                       kind: current_kind,
                       lexeme: None,
+                      span: None,
                   });
     }
 
@@ -297,6 +318,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
         Ok(Token {
                kind: TokenKind::CharValue,
                lexeme: Some(identifier),
+               span: None,
            })
     }
 
@@ -375,6 +397,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
                 return Ok(Token {
                               kind: TokenKind::Identifier,
                               lexeme: Some(identifier),
+                              span: None,
                           })
             }
         };
@@ -382,6 +405,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
         Ok(Token {
                kind: kind,
                lexeme: None,
+               span: None,
            })
     }
 
@@ -399,6 +423,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
         Ok(Token {
                kind: TokenKind::NumValue,
                lexeme: Some(identifier),
+               span: None,
            })
     }
 
@@ -441,6 +466,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
         Ok(Token {
                kind: TokenKind::StrValue,
                lexeme: Some(identifier),
+               span: None,
            })
     }
 
@@ -483,6 +509,7 @@ impl<'file, 'src> Lexer<'file, 'src> {
                 return Some(Ok(Token {
                                    kind: kind,
                                    lexeme: None,
+                                   span: None,
                                }));
             }
             _ => {}
@@ -621,6 +648,29 @@ impl<'file, 'src> Iterator for Lexer<'file, 'src> {
     type Item = Result<Token, LexerError>;
 
     fn next(&mut self) -> Option<Result<Token, LexerError>> {
-        self.next_token()
+        self.skip_comments();
+
+        let line = self.index_line;
+        let column = self.index_column + 1;
+        let start = self.index_byte;
+        let result = self.next_token();
+        let span = Some(Span {
+                            file: self.file_id,
+                            line: line,
+                            column: column,
+                            start: start,
+                            end: self.index_byte,
+                        });
+        match result {
+            Some(Ok(mut token)) => {
+                token.span = span;
+                Some(Ok(token))
+            }
+            Some(Err(mut error)) => {
+                error.span = span;
+                Some(Err(error))
+            }
+            None => None,
+        }
     }
 }
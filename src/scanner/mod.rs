@@ -8,16 +8,25 @@ mod lexer;
 mod parser;
 mod weeder;
 
-use std;
 use std::fs::File;
 use std::io::Read;
 
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+
 pub use self::ast::AST;
 pub use self::ast::ASTNode;
 pub use self::ast::ASTNodeImport;
+pub use self::ast::typed;
+pub use self::ast::visit;
+pub use self::common::SourceMap;
+pub use self::common::Span;
 pub use self::common::Token;
 pub use self::common::TokenKind;
 use self::lexer::Lexer;
+pub use self::parser::ParseNode;
+pub use self::parser::ParseTree;
 use self::parser::Parser;
 use self::weeder::Weeder;
 
@@ -27,66 +36,96 @@ use self::weeder::Weeder;
 ///
 /// ```rust,no_run
 /// let filename = "Sample.java";
-/// let contents = juicyj::scanner::read_src_file(&filename.to_owned());
+/// let contents = juicyj::scanner::read_src_file(&filename).unwrap();
 /// ```
-pub fn read_src_file(filename: &String) -> String {
+pub fn read_src_file(filename: &str) -> Result<String, Diagnostic> {
     let mut file = match File::open(&filename) {
         Ok(f) => f,
         Err(_) => {
-            error!("could not open file: {}", filename);
-            std::process::exit(1);
+            let message = ErrorMessage::CouldNotReadFile(filename.to_owned());
+            return Err(Diagnostic::new(Phase::Input, message, None));
         }
     };
 
     let mut src = String::new();
     match file.read_to_string(&mut src) {
-        Ok(_) => {}
+        Ok(_) => Ok(src),
         Err(_) => {
-            error!("could not read file {} to string", filename);
-            std::process::exit(1);
+            let message = ErrorMessage::CouldNotReadFile(filename.to_owned());
+            Err(Diagnostic::new(Phase::Input, message, None))
+        }
+    }
+}
+
+/// Runs a file through the lexer, reporting every lexer error in it.
+pub fn lex(sources: &SourceMap, file: usize) -> Result<Vec<Token>, Vec<Diagnostic>> {
+    let filename = sources.name(file).unwrap_or("");
+    let contents = sources.contents(file).unwrap_or("");
+
+    let mut errors = Vec::new();
+    let mut tokens = Vec::new();
+    for token in Lexer::new(&filename, file, &contents) {
+        match token {
+            Ok(t) => tokens.push(t),
+            Err(e) => errors.push(Diagnostic::from(e)),
         }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(tokens)
+}
+
+/// Runs a file through the lexer and parser. The parser recovers from syntax
+/// errors at the next `;` or `}` so that it can report several of them.
+pub fn parse(sources: &SourceMap, file: usize) -> Result<ParseTree, Vec<Diagnostic>> {
+    let tokens = match lex(sources, file) {
+        Ok(t) => t,
+        Err(e) => return Err(e),
     };
 
-    src
+    let mut parser = Parser::new(tokens.into_iter(), sources.contents(file).unwrap_or(""));
+    match parser.get_tree() {
+        Ok(pt) => Ok(pt),
+        Err(e) => Err(e.into_iter().map(Diagnostic::from).collect()),
+    }
 }
 
-/// Runs a file through the scanning stack (lexer, parser, weeder, AST) and
-/// exits with code 42 on a failure.
+/// Runs a file through the scanning stack (lexer, parser, weeder, AST). Every
+/// lexer error in the file is reported, as is every weeder error; the parser
+/// recovers from syntax errors at the next `;` or `}` so that it can report
+/// several of them.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// let filename = "Sample.java";
-/// let contents = juicyj::scanner::read_src_file(&filename.to_owned());
-/// let ast = juicyj::scanner::scan_or_exit(&filename, &contents);
+/// let contents = juicyj::scanner::read_src_file(&filename).unwrap();
+///
+/// let mut sources = juicyj::scanner::SourceMap::new();
+/// let file = sources.add(&filename, &contents);
+/// match juicyj::scanner::scan(&sources, file) {
+///     Ok(ast) => println!("{}", ast),
+///     Err(errors) => println!("found {} errors", errors.len()),
+/// }
 /// ```
-pub fn scan_or_exit(filename: &str, contents: &str) -> AST {
-    let lexer = Lexer::new(&filename, &contents);
-
-    let mut parser = Parser::new(lexer);
-    let parse_tree = match parser.get_tree() {
+pub fn scan(sources: &SourceMap, file: usize) -> Result<AST, Vec<Diagnostic>> {
+    let parse_tree = match parse(sources, file) {
         Ok(pt) => pt,
-        Err(e) => {
-            println!("{}", e);
-            std::process::exit(42);
-        }
+        Err(e) => return Err(e),
     };
 
+    let filename = sources.name(file).unwrap_or("");
     let mut weeder = Weeder::new(&filename, &parse_tree);
     match weeder.verify(None) {
         Ok(_) => (),
-        Err(e) => {
-            println!("{}", e);
-            std::process::exit(42);
-        }
+        Err(e) => return Err(e.into_iter().map(Diagnostic::from).collect()),
     }
 
     match AST::new(&filename, &parse_tree) {
-        Ok(ast) => ast,
-        Err(e) => {
-            println!("{}", e);
-            std::process::exit(42);
-        }
+        Ok(ast) => Ok(ast),
+        Err(e) => Err(vec![Diagnostic::from(e)]),
     }
 }
 
@@ -94,99 +133,31 @@ pub fn scan_or_exit(filename: &str, contents: &str) -> AST {
 // find this module in that case.
 #[allow(missing_docs)]
 pub mod tests {
-    use std;
-
-    use super::ast::AST;
-    use super::lexer::Lexer;
-    use super::parser::Parser;
-    use super::weeder::Weeder;
-
-    pub fn scan_and_assert(filename: &str, src: &str) -> Option<AST> {
-        let lexer = Lexer::new(&filename, &src);
-        for token in lexer.clone().collect::<Vec<Result<_, _>>>() {
-            match token {
-                Ok(_) => (),
-                Err(_) => {
-                    println!("Lexer Error");
-                    assert!(true);
-                    return None;
-                }
-            }
-        }
-
-        let mut parser = Parser::new(lexer);
-        let parse_tree = match parser.get_tree() {
-            Ok(pt) => pt,
-            Err(_) => {
-                println!("Parser Error");
-                assert!(true);
-                return None;
-            }
-        };
+    use compiler::Compiler;
 
-        let mut weeder = Weeder::new(&filename, &parse_tree);
-        match weeder.verify(None) {
-            Ok(_) => (),
-            Err(_) => {
-                println!("Weeder Verification Error");
-                assert!(true);
-                return None;
-            }
-        }
-
-        match AST::new(&filename, &parse_tree) {
-            Ok(ast) => Some(ast),
-            Err(_) => {
-                println!("AST Construction Error");
+    pub fn scan_and_assert(compiler: &mut Compiler) -> bool {
+        match compiler.parse() {
+            Ok(_) => false,
+            Err(e) => {
+                println!("Scanner Error");
+                for error in e {
+                    println!("{}", error.render(compiler.sources()));
+                }
                 assert!(true);
-                None
+                true
             }
         }
     }
 
-    pub fn scan_or_assert(filename: &str, src: &str) -> AST {
-        let lexer = Lexer::new(&filename, &src);
-        for token in lexer.clone().collect::<Vec<Result<_, _>>>() {
-            match token {
-                Ok(_) => (),
-                Err(e) => {
-                    println!("Lexer Error");
-                    println!("{}", e);
-                    assert!(false);
-                    std::process::exit(1);
-                }
-            }
-        }
-
-        let mut parser = Parser::new(lexer);
-        let parse_tree = match parser.get_tree() {
-            Ok(pt) => pt,
-            Err(e) => {
-                println!("Parser Error");
-                println!("{}", e);
-                assert!(false);
-                std::process::exit(1);
-            }
-        };
-
-        let mut weeder = Weeder::new(&filename, &parse_tree);
-        match weeder.verify(None) {
+    pub fn scan_or_assert(compiler: &mut Compiler) {
+        match compiler.parse() {
             Ok(_) => (),
             Err(e) => {
-                println!("Weeder Verification Error");
-                println!("{}", e);
-                assert!(false);
-                std::process::exit(1);
-            }
-        }
-
-        match AST::new(&filename, &parse_tree) {
-            Ok(ast) => ast,
-            Err(e) => {
-                println!("AST Construction Error");
-                println!("{}", e);
+                println!("Scanner Error");
+                for error in e {
+                    println!("{}", error.render(compiler.sources()));
+                }
                 assert!(false);
-                std::process::exit(1);
             }
         }
     }
@@ -61,6 +61,7 @@ impl Symbol {
                        token: Token {
                            kind: kind,
                            lexeme: Some(value),
+                           span: None,
                        },
                    })
             }
@@ -79,6 +80,25 @@ impl Symbol {
 
         Symbol::new(kind, value)
     }
+
+    /// Describes a terminal as it would be written in source, eg. `;` rather
+    /// than SEMICOLON.
+    pub fn describe(&self) -> String {
+        let lexeme = self.token.lexeme.clone().unwrap_or("".to_owned());
+        match lexeme.as_ref() {
+            "BOF" => "start of file".to_owned(),
+            "EOF" => "end of file".to_owned(),
+            "CharacterLit" => "character literal".to_owned(),
+            "IDENTIFIER" => "identifier".to_owned(),
+            "IntegerLit" => "integer literal".to_owned(),
+            "StringLit" => "string literal".to_owned(),
+            "COMMA" => "`,`".to_owned(),
+            "DOT" => "`.`".to_owned(),
+            "NullLit" => "`null`".to_owned(),
+            "SEMICOLON" => "`;`".to_owned(),
+            l => format!("`{}`", l.to_lowercase()),
+        }
+    }
 }
 
 impl FromStr for Function {
@@ -1,9 +1,3 @@
-use std;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::io::Read;
-use std::fs::File;
-
 use error::ErrorMessage;
 use error::ParserError;
 use scanner::common::Token;
@@ -15,6 +9,16 @@ use scanner::parser::common::Symbol;
 use scanner::parser::common::Terminality;
 use scanner::parser::common::Transition;
 
+// The LR(1) parse table, generated from grammar/joos.grammar with `juicyj
+// grammar` (see `make grammar`). Embedded so that the compiler does not depend
+// on being run from the repository root.
+const TABLE: &'static str = include_str!("../../../grammar/joos.lr1");
+
+lazy_static! {
+    /// The Joos DFA, decoded from the parse table on first use.
+    pub static ref JOOS: DFA = DFA::new();
+}
+
 pub struct DFA {
     pub non_terminals: Vec<Symbol>,
     pub terminals: Vec<Symbol>,
@@ -25,52 +29,23 @@ pub struct DFA {
 
 impl DFA {
     pub fn new() -> DFA {
-        let filename = "grammar/joos.lr1";
-        let mut file = match File::open(filename) {
-            Ok(file) => BufReader::new(file),
-            Err(_) => {
-                let error = ParserError::new(ErrorMessage::CouldNotReadFile(filename.to_string()),
-                                             None);
-                println!("{}", error);
-                std::process::exit(1);
-            }
-        };
+        let mut lines = TABLE.lines();
 
         let mut terminals = Vec::new();
-        for _ in 0..
-                 file.by_ref()
-                     .lines()
-                     .next()
-                     .unwrap()
-                     .unwrap()
-                     .parse()
-                     .unwrap() {
-            let symbol = file.by_ref().lines().next().unwrap().unwrap();
+        for _ in 0..lines.next().unwrap().parse().unwrap() {
+            let symbol = lines.next().unwrap().to_owned();
             match Symbol::new(Terminality::Terminal, symbol) {
                 Ok(s) => terminals.push(s),
-                Err(e) => {
-                    println!("{}", e);
-                    std::process::exit(1);
-                }
+                Err(e) => panic!("invalid parse table: {}", e),
             }
         }
 
         let mut non_terminals = Vec::new();
-        for _ in 0..
-                 file.by_ref()
-                     .lines()
-                     .next()
-                     .unwrap()
-                     .unwrap()
-                     .parse()
-                     .unwrap() {
-            let symbol = file.by_ref().lines().next().unwrap().unwrap();
+        for _ in 0..lines.next().unwrap().parse().unwrap() {
+            let symbol = lines.next().unwrap().to_owned();
             match Symbol::new(Terminality::NonTerminal, symbol) {
                 Ok(s) => non_terminals.push(s),
-                Err(e) => {
-                    println!("{}", e);
-                    std::process::exit(1);
-                }
+                Err(e) => panic!("invalid parse table: {}", e),
             }
         }
 
@@ -80,35 +55,21 @@ impl DFA {
             .map(|t| t.token.kind)
             .collect();
 
-        let start =
-            match Symbol::new_from_terminals(&kinds_terminal,
-                                             file.by_ref().lines().next().unwrap().unwrap()) {
-                Ok(s) => s,
-                Err(e) => {
-                    println!("{}", e);
-                    std::process::exit(1);
-                }
-            };
+        let start = match Symbol::new_from_terminals(&kinds_terminal,
+                                                     lines.next().unwrap().to_owned()) {
+            Ok(s) => s,
+            Err(e) => panic!("invalid parse table: {}", e),
+        };
 
         let mut rules = Vec::new();
-        for _ in 0..
-                 file.by_ref()
-                     .lines()
-                     .next()
-                     .unwrap()
-                     .unwrap()
-                     .parse()
-                     .unwrap() {
-            let rule = file.by_ref().lines().next().unwrap().unwrap();
+        for _ in 0..lines.next().unwrap().parse().unwrap() {
+            let rule = lines.next().unwrap();
             let mut sides = rule.splitn(2, " ");
 
             let lhs = match Symbol::new(Terminality::NonTerminal,
                                         sides.next().unwrap().to_owned()) {
                 Ok(s) => s,
-                Err(e) => {
-                    println!("{}", e);
-                    std::process::exit(1);
-                }
+                Err(e) => panic!("invalid parse table: {}", e),
             };
 
             let rhs = match sides.next() {
@@ -116,10 +77,7 @@ impl DFA {
                     side.split_whitespace()
                         .map(|s| match Symbol::new_from_terminals(&kinds_terminal, s.to_owned()) {
                                  Ok(s) => s,
-                                 Err(e) => {
-                            println!("{}", e);
-                            std::process::exit(42);
-                        }
+                                 Err(e) => panic!("invalid parse table: {}", e),
                              })
                         .collect()
                 }
@@ -129,26 +87,12 @@ impl DFA {
         }
 
         let mut states = Vec::new();
-        for i in 0..
-                 file.by_ref()
-                     .lines()
-                     .next()
-                     .unwrap()
-                     .unwrap()
-                     .parse()
-                     .unwrap() {
+        for i in 0..lines.next().unwrap().parse().unwrap() {
             states.push(State::new(i as usize));
         }
 
-        for _ in 0..
-                 file.by_ref()
-                     .lines()
-                     .next()
-                     .unwrap()
-                     .unwrap()
-                     .parse()
-                     .unwrap() {
-            let transition = file.by_ref().lines().next().unwrap().unwrap();
+        for _ in 0..lines.next().unwrap().parse().unwrap() {
+            let transition = lines.next().unwrap();
             let mut tx = transition.split(" ");
 
             let start_state: usize = tx.next().unwrap().parse().unwrap();
@@ -165,10 +109,7 @@ impl DFA {
                           symbol: match Symbol::new_from_terminals(&kinds_terminal,
                                                                    symbol.to_owned()) {
                               Ok(s) => s,
-                              Err(e) => {
-                    println!("{}", e);
-                    std::process::exit(1);
-                }
+                              Err(e) => panic!("invalid parse table: {}", e),
                           },
                       });
         }
@@ -182,6 +123,25 @@ impl DFA {
         }
     }
 
+    /// Lists every terminal which has an entry in the given state, ie. every
+    /// token which could have been consumed there. Reductions are keyed on
+    /// their lookahead, so these count as well as shifts.
+    pub fn expected(&self, state: &usize) -> Vec<String> {
+        let mut expected = Vec::new();
+        for transition in &self.states[*state].transitions {
+            if transition.symbol.terminality != Terminality::Terminal {
+                continue;
+            }
+
+            let description = transition.symbol.describe();
+            if !expected.contains(&description) {
+                expected.push(description);
+            }
+        }
+        expected.sort();
+        expected
+    }
+
     pub fn consume(&self, state: &usize, ref token: &Token) -> Result<Transition, ParserError> {
         let ref state = self.states[*state];
         let ref transitions = state.transitions;
@@ -201,6 +161,7 @@ impl DFA {
         }
 
         Err(ParserError::new(ErrorMessage::UnparseableToken(format!("{}", token)),
-                             Some(format!("last known state: {}", state))))
+                             Some(format!("last known state: {}", state)))
+                    .with_span(token.span))
     }
 }
@@ -1,33 +1,35 @@
-use std;
 use std::iter::Peekable;
 
 use error::ErrorMessage;
-use error::LexerError;
 use error::ParserError;
 use scanner::common::Token;
 use scanner::common::TokenKind;
+use scanner::common::span;
 use scanner::parser::common::Function;
 use scanner::parser::common::Terminality;
 use scanner::parser::common::Transition;
 use scanner::parser::dfa::DFA;
+use scanner::parser::dfa::JOOS;
 use scanner::parser::tree::ParseNode;
 use scanner::parser::tree::ParseTree;
 
-pub struct Parser<T: Iterator<Item = Result<Token, LexerError>>> {
-    dfa: DFA,
+const RECOVERY_DISTANCE: usize = 3;
+
+pub struct Parser<'src, T: Iterator<Item = Token>> {
+    dfa: &'static DFA,
     nodes: Vec<ParseNode>,
+    src: &'src str,
     states: Vec<usize>,
     token_state: u8,
     tokens: Peekable<T>,
 }
 
-impl<T: Iterator<Item = Result<Token, LexerError>>> Parser<T> {
-    pub fn new(it: T) -> Parser<T> {
-        let dfa = DFA::new();
-
+impl<'src, T: Iterator<Item = Token>> Parser<'src, T> {
+    pub fn new(it: T, src: &'src str) -> Parser<'src, T> {
         Parser {
-            dfa: dfa,
+            dfa: &JOOS,
             nodes: Vec::new(),
+            src: src,
             states: vec![0],
             token_state: 0,
             tokens: it.peekable(),
@@ -59,21 +61,18 @@ impl<T: Iterator<Item = Result<Token, LexerError>>> Parser<T> {
             Some(Token {
                      kind: TokenKind::BOF,
                      lexeme: None,
+                     span: None,
                  })
         } else {
             match self.tokens.peek() {
-                Some(&Ok(ref t)) => Some(t.clone()),
-                Some(&Err(ref e)) => {
-                    // TODO: get this out of here
-                    println!("{}", e);
-                    std::process::exit(42);
-                }
+                Some(t) => Some(t.clone()),
                 _ => {
                     if self.token_state == 1 {
                         self.token_state += 1;
                         Some(Token {
                                  kind: TokenKind::EOF,
                                  lexeme: None,
+                                 span: None,
                              })
                     } else {
                         None
@@ -97,10 +96,12 @@ impl<T: Iterator<Item = Result<Token, LexerError>>> Parser<T> {
             }
         }
 
+        let mut lhs = rule.lhs.token.clone();
+        lhs.span = children.iter().fold(None, |acc, child| span::merge(acc, child.token.span));
         self.nodes
             .push(ParseNode {
                       children: children,
-                      token: rule.lhs.token.clone(),
+                      token: lhs,
                   });
 
         match self.consume(rule.lhs.token.clone()) {
@@ -128,33 +129,113 @@ impl<T: Iterator<Item = Result<Token, LexerError>>> Parser<T> {
         Ok(())
     }
 
-    pub fn get_tree(&mut self) -> Result<ParseTree, ParserError> {
+    // Simulates consuming a token from the given stack of states, without
+    // building any nodes.
+    fn accepts(&self, states: &[usize], token: &Token) -> bool {
+        let mut states = states.to_vec();
+        loop {
+            let transition = match self.dfa.consume(states.last().unwrap_or(&0), token) {
+                Ok(t) => t,
+                Err(_) => return false,
+            };
+            let rule = match transition.function {
+                Function::Shift => return true,
+                Function::Reduce => &self.dfa.rules[transition.value],
+            };
+
+            if rule.rhs.len() >= states.len() {
+                return false;
+            }
+            let remaining = states.len() - rule.rhs.len();
+            states.truncate(remaining);
+
+            match self.dfa.consume(states.last().unwrap_or(&0), &rule.lhs.token) {
+                Ok(Transition { function: Function::Shift, value, .. }) => states.push(value),
+                _ => return false,
+            }
+        }
+    }
+
+    // Panic-mode recovery: discards tokens up to the next `;` or `}`, then
+    // unwinds the stack to the deepest state which can consume that token.
+    // Returns false once there is no more input to recover with.
+    fn recover(&mut self) -> bool {
         while let Some(token) = self.peek() {
-            match self.dfa
-                      .consume(self.states.last().unwrap_or(&0), &token) {
-                Ok(transition) => {
-                    let result = match transition.function {
-                        Function::Reduce => self.reduce(transition, token.clone()),
-                        Function::Shift => self.shift(transition, token.clone()),
-                    };
-                    match result {
-                        Ok(_) => (),
-                        Err(e) => return Err(e.with_nodes(self.nodes.clone())),
+            match token.kind {
+                TokenKind::EOF => return false,
+                TokenKind::RBrace | TokenKind::Semicolon => {
+                    for depth in (1..self.states.len() + 1).rev() {
+                        if self.accepts(&self.states[..depth], &token) {
+                            let popped = self.states.len() - depth;
+                            let remaining = self.nodes.len().saturating_sub(popped);
+                            self.states.truncate(depth);
+                            self.nodes.truncate(remaining);
+                            return true;
+                        }
                     }
+                }
+                _ => (),
+            }
+            self.tokens.next();
+        }
+
+        false
+    }
+
+    pub fn get_tree(&mut self) -> Result<ParseTree, Vec<ParserError>> {
+        let mut errors = Vec::new();
+        // errors within a few tokens of the last recovery are most likely
+        // caused by it, so are not reported
+        let mut consumed = RECOVERY_DISTANCE;
+
+        while let Some(token) = self.peek() {
+            match self.consume(token.clone()) {
+                Ok(_) => {
                     if token.kind != TokenKind::BOF {
                         self.tokens.next();
                     }
+                    consumed += 1;
+                }
+                Err(ParserError { message: ErrorMessage::UnparseableToken(_), .. }) => {
+                    debug!("could not consume {} in state {}",
+                           token,
+                           self.dfa.states[*self.states.last().unwrap_or(&0)]);
+                    if consumed >= RECOVERY_DISTANCE {
+                        let state = self.states.last().unwrap_or(&0);
+                        let found = match (token.span, &token.kind) {
+                            (Some(span), _) => format!("`{}`", span.text(self.src)),
+                            (None, &TokenKind::EOF) => "end of file".to_owned(),
+                            (None, _) => format!("{}", token),
+                        };
+                        let message = ErrorMessage::UnexpectedToken(found,
+                                                                    self.dfa.expected(state));
+                        errors.push(ParserError::new(message, None)
+                                        .with_span(token.span)
+                                        .with_nodes(self.nodes.clone()));
+                    }
+
+                    consumed = 0;
+                    if !self.recover() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    errors.push(e.with_nodes(self.nodes.clone()));
+                    return Err(errors);
                 }
-                Err(e) => return Err(e.with_nodes(self.nodes.clone())),
             }
         }
 
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
         // TODO: one more manual reduce step?
         match self.nodes.len() {
             3 => Ok(ParseTree { root: self.nodes[1].clone() }),
             _ => {
-                Err(ParserError::new(ErrorMessage::InvalidParseTree, None)
-                        .with_nodes(self.nodes.clone()))
+                Err(vec![ParserError::new(ErrorMessage::InvalidParseTree, None)
+                             .with_nodes(self.nodes.clone())])
             }
         }
     }
@@ -19,11 +19,21 @@ impl<'filename, 'tree> Weeder<'filename, 'tree> {
         }
     }
 
-    fn error(&self, message: ErrorMessage, node: &ParseNode) -> Result<(), WeederError> {
-        Err(WeederError::new(self.filename.to_owned(), message, &node))
+    // Sources without a `.java` name, such as one read from standard input,
+    // have no name for their type to match.
+    fn named_file(&self) -> bool {
+        self.filename.ends_with(".java")
     }
 
-    pub fn verify(&mut self, node: Option<ParseNode>) -> Result<(), WeederError> {
+    fn error(&self, message: ErrorMessage, node: &ParseNode) -> Result<(), Vec<WeederError>> {
+        Err(vec![WeederError::new(self.filename.to_owned(), message, &node)
+                     .with_span(node.token.span)])
+    }
+
+    /// Verifies a node and all of its children. A node which fails to verify
+    /// is not descended into, but its siblings are, so that every error in
+    /// the tree is reported.
+    pub fn verify(&mut self, node: Option<ParseNode>) -> Result<(), Vec<WeederError>> {
         let node = match node {
             Some(n) => n,
             _ => self.tree.root.clone(),
@@ -110,7 +120,8 @@ impl<'filename, 'tree> Weeder<'filename, 'tree> {
                         for (idx, child) in node.children.iter().enumerate() {
                             if child.token.kind == TokenKind::Class {
                                 match node.children[idx + 1].clone().token.lexeme {
-                                    Some(ref lexeme) if format!("{}.java", lexeme) !=
+                                    Some(ref lexeme) if self.named_file() &&
+                                                        format!("{}.java", lexeme) !=
                                                         self.filename => {
                                         return self.error(ErrorMessage::ClassBadName, &node);
                                     }
@@ -123,7 +134,8 @@ impl<'filename, 'tree> Weeder<'filename, 'tree> {
                         for (idx, child) in node.children.iter().enumerate() {
                             if child.token.kind == TokenKind::Interface {
                                 match node.children[idx + 1].clone().token.lexeme {
-                                    Some(ref lexeme) if format!("{}.java", lexeme) !=
+                                    Some(ref lexeme) if self.named_file() &&
+                                                        format!("{}.java", lexeme) !=
                                                         self.filename => {
                                         return self.error(ErrorMessage::InterfaceBadName, &node);
                                     }
@@ -138,13 +150,18 @@ impl<'filename, 'tree> Weeder<'filename, 'tree> {
             _ => (),
         }
 
+        let mut errors = Vec::new();
         for child in node.children {
             match self.verify(Some(child)) {
-                Err(e) => return Err(e),
+                Err(e) => errors.extend(e),
                 _ => (),
             }
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 }
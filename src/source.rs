@@ -0,0 +1,114 @@
+//! Sources for juicyj. A SourceProvider supplies a set of (path, contents)
+//! pairs to a compilation, whether they are read from disk or held in memory.
+use std::io::Read;
+
+use walkdir::WalkDir;
+
+use error::Diagnostic;
+use error::ErrorMessage;
+use error::Phase;
+use scanner;
+
+/// The name given to a source read from standard input.
+pub const STDIN: &'static str = "<stdin>";
+
+/// Anything which can supply source files to a compilation.
+pub trait SourceProvider {
+    /// Returns a list of (path, contents) pairs, one per source file.
+    fn sources(&self) -> Result<Vec<(String, String)>, Diagnostic>;
+}
+
+/// Every `.java` file below a directory on disk, in order of their paths.
+pub struct Directory {
+    root: String,
+}
+
+impl Directory {
+    /// Constructs a provider for the `.java` files below `root`.
+    pub fn new(root: &str) -> Directory {
+        Directory { root: root.to_owned() }
+    }
+}
+
+impl SourceProvider for Directory {
+    fn sources(&self) -> Result<Vec<(String, String)>, Diagnostic> {
+        let mut paths = Vec::new();
+        for entry in WalkDir::new(&self.root) {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(_) => {
+                    let message = ErrorMessage::CouldNotReadFile(self.root.clone());
+                    return Err(Diagnostic::new(Phase::Input, message, None));
+                }
+            };
+            match entry.path().to_str() {
+                Some(path) if path.ends_with(".java") => paths.push(path.to_owned()),
+                _ => (),
+            }
+        }
+        paths.sort();
+
+        let mut sources = Vec::new();
+        for path in paths {
+            match scanner::read_src_file(&path) {
+                Ok(text) => sources.push((path, text)),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(sources)
+    }
+}
+
+/// A virtual file system: sources which exist only in memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use juicyj::source::Memory;
+///
+/// let files = Memory::new().with("Foo.java", "public class Foo { public Foo() {} }");
+/// let mut compiler = juicyj::Compiler::new();
+/// compiler.add_sources(&files).unwrap();
+/// assert!(compiler.parse().is_ok());
+/// ```
+pub struct Memory {
+    files: Vec<(String, String)>,
+}
+
+impl Memory {
+    /// Constructs an empty file system.
+    pub fn new() -> Memory {
+        Memory { files: Vec::new() }
+    }
+
+    /// Adds a file, replacing any earlier file with the same path.
+    pub fn with(mut self, path: &str, contents: &str) -> Memory {
+        self.files.retain(|&(ref p, _)| p != path);
+        self.files.push((path.to_owned(), contents.to_owned()));
+        self
+    }
+}
+
+impl SourceProvider for Memory {
+    fn sources(&self) -> Result<Vec<(String, String)>, Diagnostic> {
+        Ok(self.files.clone())
+    }
+}
+
+/// The Joos standard library, as shipped in `stdlib/java`. It is found
+/// relative to where juicyj was built, rather than the current directory.
+pub fn stdlib() -> Directory {
+    Directory::new(concat!(env!("CARGO_MANIFEST_DIR"), "/stdlib/java"))
+}
+
+/// Reads a single source from standard input.
+pub fn read_stdin() -> Result<String, Diagnostic> {
+    let mut text = String::new();
+    match ::std::io::stdin().read_to_string(&mut text) {
+        Ok(_) => Ok(text),
+        Err(_) => {
+            let message = ErrorMessage::CouldNotReadFile(STDIN.to_owned());
+            Err(Diagnostic::new(Phase::Input, message, None))
+        }
+    }
+}
@@ -0,0 +1,90 @@
+extern crate juicyj;
+
+use juicyj::analysis::Declaration;
+
+mod common;
+
+// The type and declaration recorded for the expression returned by the method
+// `test` of a class Foo with the given members.
+fn annotated(members: &str) -> (String, Option<Declaration>) {
+    let mut compiler = common::compiler(&[("Foo.java", &common::foo(members))]);
+    compiler.analyze().unwrap();
+
+    let env = compiler.environment().unwrap();
+    let method = common::test_method(env);
+    let value = common::returned(method.body.as_ref().unwrap()).unwrap();
+    let annotation = env.annotations.get(&value).unwrap();
+    (annotation.kind.source_text(), annotation.declaration.clone())
+}
+
+fn declaration(declaration: Option<Declaration>) -> String {
+    match declaration {
+        Some(Declaration::Local(v)) => format!("local {}", v.name.source_text()),
+        Some(Declaration::Field(cls, f)) => {
+            format!("field {}.{}", cls.source_text(), f.name.source_text())
+        }
+        Some(Declaration::Method(cls, m)) => {
+            format!("method {}.{}", cls.source_text(), m.name.source_text())
+        }
+        Some(Declaration::Type(cls)) => format!("type {}", cls.source_text()),
+        None => "none".to_owned(),
+    }
+}
+
+macro_rules! annotation_tests {
+    ($($name:ident: $members:tt => ($kind:tt, $declaration:tt),)*) => {
+    $(
+        #[test]
+        fn $name() {
+            let (kind, decl) = annotated($members);
+            assert_eq!((kind, declaration(decl)), ($kind.to_owned(), $declaration.to_owned()));
+        }
+    )*
+    }
+}
+
+annotation_tests! {
+    literal: "public int test() { return 1; }" => ("int", "none"),
+    math: "public int test(int x) { return x * 2; }" => ("int", "none"),
+    comparison: "public boolean test(int x) { return x < 2; }" => ("boolean", "none"),
+    concatenation: "public String test(int x) { return \"a\" + x; }" =>
+        ("java.lang.String", "none"),
+    local: "public int test() { int x = 1; return x; }" => ("int", "local x"),
+    parameter: "public String test(String s) { return s; }" => ("java.lang.String", "local s"),
+    field: "public int n; public int test() { return n; }" =>
+        ("int", "field juicyj_unnamed.Foo.n"),
+    qualified_field: "public Foo f; public Foo test() { return this.f; }" =>
+        ("juicyj_unnamed.Foo", "field juicyj_unnamed.Foo.f"),
+    static_field: "public static int n; public int test() { return Foo.n; }" =>
+        ("int", "field juicyj_unnamed.Foo.n"),
+    array: "public int[] test() { int[] a = new int[3]; return a; }" => ("int[]", "local a"),
+    array_access: "public int test(int[] a) { return a[0]; }" => ("int", "none"),
+    array_length: "public int test(int[] a) { return a.length; }" => ("int", "none"),
+    method: "public int n() { return 1; } public int test() { return n(); }" =>
+        ("int", "method juicyj_unnamed.Foo.n"),
+    stdlib_method: "public int test(String s) { return s.length(); }" =>
+        ("int", "method java.lang.String.length"),
+    constructor: "public Foo test() { return new Foo(); }" =>
+        ("juicyj_unnamed.Foo", "method juicyj_unnamed.Foo.Foo"),
+    cast: "public Object test(String s) { return (Object) s; }" => ("java.lang.Object", "none"),
+}
+
+#[test]
+fn declarations() {
+    let members = "public String s; public int test(int p) { int x = p; return x; }";
+    let mut compiler = common::compiler(&[("Foo.java", &common::foo(members))]);
+    compiler.analyze().unwrap();
+
+    let env = compiler.environment().unwrap();
+    assert_eq!(env.annotations.label(&common::class(env).fields[0].name),
+               Some("java.lang.String".to_owned()));
+    assert_eq!(env.annotations.label(&common::test_method(env).parameters[0].name),
+               Some("int".to_owned()));
+}
+
+#[test]
+fn generated() {
+    let members = "public static int test() { int[] a = new int[3]; return a.length; }";
+    let files = common::generate(&[("Foo.java", &common::foo(members))]);
+    assert!(files.iter().any(|&(ref name, _)| name == "juicyj_unnamedfoo.s"));
+}
@@ -0,0 +1,68 @@
+extern crate juicyj;
+
+// Compiles a method body, returning the definite assignment errors in it.
+fn errors(body: &str) -> Vec<String> {
+    let source = format!("public class Foo {{ public Foo() {{}} \
+                          public int test(boolean c) {{ {} }} }}",
+                         body);
+    let mut compiler = juicyj::Compiler::new();
+    compiler.add_source("Foo.java", &source);
+    compiler.add_sources(&juicyj::source::stdlib()).unwrap();
+    match compiler.analyze() {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| format!("{}", e)).collect(),
+    }
+}
+
+macro_rules! assigned_tests {
+    ($($name:ident: $body:tt,)*) => {
+    $(
+        #[test]
+        fn $name() {
+            assert_eq!(errors($body), Vec::<String>::new());
+        }
+    )*
+    }
+}
+
+macro_rules! unassigned_tests {
+    ($($name:ident: $body:tt => $path:tt,)*) => {
+    $(
+        #[test]
+        fn $name() {
+            let errors = errors($body);
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].contains("variable x might not have been initialized"),
+                    "{}",
+                    errors[0]);
+            assert!(errors[0].contains($path), "{}", errors[0]);
+        }
+    )*
+    }
+}
+
+assigned_tests! {
+    assigned_later: "int x; x = 1; int y = x; return y;",
+    both_branches: "int x; if (c) { x = 1; } else { x = 2; } return x;",
+    and_when_true: "int x; if (c && (x = 1) > 0) { return x; } return 0;",
+    or_when_false: "int x; if (c || (x = 1) > 0) { return 0; } return x;",
+    not: "int x; if (!(c && (x = 1) > 0)) { return 0; } return x;",
+    constant_true: "int x; if (true) { x = 1; } return x;",
+    constant_false: "int x; if (false) { } else { x = 1; } return x;",
+    return_in_branch: "int x; if (c) { return 0; } else { x = 1; } return x;",
+    for_init: "int x; for (x = 0; x < 3; x = x + 1) { } return x;",
+    scopes: "{ int x; x = 1; } int x; x = 2; return x;",
+}
+
+unassigned_tests! {
+    no_initializer: "int x; return x;" => "declared without an initializer",
+    if_without_else: "int x; if (c) { x = 1; } return x;" => "if this condition is false",
+    if_else: "int x; if (c) { } else { x = 1; } return x;" => "if this condition is true",
+    and_when_false: "int x; if (c && (x = 1) > 0) { return 0; } return x;" => "if this is false",
+    or_when_true: "int x; if (c || (x = 1) > 0) { return x; } return 0;" => "if this is true",
+    while_skipped: "int x; while (c) { x = 1; } return x;" => "before the loop runs",
+    for_skipped: "int x; for (int i = 0; i < 3; i = i + 1) { x = i; } return x;" =>
+        "before the loop runs",
+    in_initializer: "int x; int y = x + 1; return y;" => "declared without an initializer",
+    own_initializer: "int x = x + 1; return x;" => "not assigned until its initializer completes",
+}
@@ -0,0 +1,56 @@
+extern crate juicyj;
+
+mod common;
+
+// The assembly of a class Foo, extending Bar, with the given members.
+fn generate(members: &str) -> String {
+    let files = common::generate(&[("Bar.java", common::BAR),
+                                   ("Foo.java", &common::foo_extends_bar(members))]);
+    common::code(&files, "juicyj_unnamedfoo.s").to_owned()
+}
+
+#[test]
+fn narrowing() {
+    let foo = generate("public static int test() { int i = 300; byte b = (byte) i; \
+                        short s = (short) i; char c = (char) i; return (int) c; }");
+
+    assert!(foo.contains("; cast to byte\n  movsx eax, al\n"));
+    assert!(foo.contains("; cast to short\n  movsx eax, ax\n"));
+    assert!(foo.contains("; cast to char\n  movzx eax, ax\n"));
+    assert!(foo.contains("; cast to int\n\n"));
+}
+
+#[test]
+fn downcasts() {
+    let foo = generate("public static int test() { Object o = new Foo(); Bar b = (Bar) o; \
+                        Object a = new int[1]; int[] i = (int[]) a; return 0; }");
+
+    // null, whose type descriptor is 0, skips the check
+    let null = "mov ecx, [esi-4]\n  cmp ecx, 0\n  je .cast";
+    assert!(foo.contains(&format!("; cast to juicyj_unnamed.Bar\n  {}", null)));
+    assert!(foo.contains(&format!("; cast to int[]\n  {}", null)));
+    assert_eq!(foo.matches("  je __exception").count(), 2);
+    assert!(foo.contains("extern __exception"));
+}
+
+#[test]
+fn null() {
+    let foo = generate("public static int test() { Object o = null; Foo f = (Foo) o; \
+                        Bar b = (Bar) null; return 0; }");
+
+    // casting null always succeeds, whatever the type
+    let null = "mov ecx, [esi-4]\n  cmp ecx, 0\n  je .cast";
+    assert!(foo.contains(&format!("; cast to juicyj_unnamed.Foo\n  {}", null)));
+    assert!(foo.contains(&format!("; cast to juicyj_unnamed.Bar\n  {}", null)));
+    assert!(!foo.contains("mov ecx, [esi-4]\n  cmp ecx, 0\n  je __exception"));
+}
+
+#[test]
+fn upcasts() {
+    let foo = generate("public static int test() { Foo f = new Foo(); Bar b = (Bar) f; \
+                        Object o = (Object) new Foo[1]; return 0; }");
+
+    assert!(foo.contains("; cast to juicyj_unnamed.Bar\n\n"));
+    assert!(foo.contains("; cast to java.lang.Object\n\n"));
+    assert!(!foo.contains("je __exception"));
+}
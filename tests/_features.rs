@@ -6,9 +6,10 @@ macro_rules! feature_tests {
         #[test]
         fn $name() {
             let filename: String = format!("tests/cases/features/{}.java", $case);
-            let src: String = juicyj::scanner::read_src_file(&filename);
+            let mut compiler = juicyj::Compiler::new();
+            compiler.add_file(&filename).unwrap();
 
-            juicyj::scanner::tests::scan_or_assert(&filename, &src);
+            juicyj::scanner::tests::scan_or_assert(&mut compiler);
         }
     )*
     }
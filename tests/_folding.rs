@@ -0,0 +1,125 @@
+extern crate juicyj;
+
+mod common;
+
+// The folded expression returned by the method `test` of a class Foo with the
+// given members, compiled along with any other classes, as its token kind and
+// source text.
+fn folded_with(members: &str, others: &[(&str, &str)]) -> (String, String) {
+    let source = common::foo(members);
+    let mut sources = vec![("Foo.java", source.as_ref())];
+    sources.extend_from_slice(others);
+    let mut compiler = common::compiler(&sources);
+    compiler.analyze().unwrap();
+
+    let method = common::test_method(compiler.environment().unwrap());
+    let value = common::returned(method.body.as_ref().unwrap()).unwrap();
+    (format!("{:?}", value.token.kind), value.source_text())
+}
+
+fn folded(members: &str) -> (String, String) {
+    folded_with(members, &[])
+}
+
+// The errors found in a class Foo with the given members.
+fn errors(members: &str) -> Vec<String> {
+    let mut compiler = common::compiler(&[("Foo.java", &common::foo(members))]);
+    match compiler.analyze() {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| format!("{}", e)).collect(),
+    }
+}
+
+macro_rules! folding_tests {
+    ($($name:ident: $members:tt => ($kind:tt, $text:tt),)*) => {
+    $(
+        #[test]
+        fn $name() {
+            assert_eq!(folded($members), ($kind.to_owned(), $text.to_owned()));
+        }
+    )*
+    }
+}
+
+folding_tests! {
+    arithmetic: "public int test() { return 1 + 2 * 3 - 8 / 4 % 3; }" => ("NumValue", "5"),
+    negative: "public int test() { return -2147483648; }" => ("NumValue", "-2147483648"),
+    wraparound_add: "public int test() { return 2147483647 + 1; }" => ("NumValue", "-2147483648"),
+    wraparound_mul: "public int test() { return 65536 * 65536; }" => ("NumValue", "0"),
+    wraparound_div: "public int test() { return (-2147483648) / -1; }" =>
+        ("NumValue", "-2147483648"),
+    division_by_zero: "public int test() { return 1 / 0; }" => ("FSlash", "10"),
+    comparison: "public boolean test() { return 1 + 1 == 2 && 3 < 2 == false; }" =>
+        ("True", "true"),
+    not: "public boolean test() { return !(1 >= 2); }" => ("True", "true"),
+    chars: "public int test() { return 'a' + 1; }" => ("NumValue", "98"),
+    concatenation: "public String test() { return \"a\" + 1 + 'b' + true; }" =>
+        ("StrValue", "a1btrue"),
+    concatenation_escapes: "public String test() { return \"a\\n\" + '\\t'; }" =>
+        ("StrValue", "a\\n\\t"),
+    cast_char: "public char test() { return (char) 98; }" => ("CharValue", "b"),
+    cast_byte: "public int test() { return (byte) 200; }" => ("NumValue", "-56"),
+    field: "public static final int N = 4; public int test() { return N * 2; }" =>
+        ("NumValue", "8"),
+    qualified_field: "public static final int N = 4; public int test() { return Foo.N; }" =>
+        ("NumValue", "4"),
+    field_conversion: "public static final int C = 'a'; public int test() { return C; }" =>
+        ("NumValue", "97"),
+    string_field: "public static final String S = \"x\" + 1; \
+                   public String test() { return S + S; }" => ("StrValue", "x1x1"),
+    not_final: "public static int N = 4; public int test() { return N; }" => ("NonTerminal", "N"),
+    not_static: "public final int N = 4; public int test() { return N; }" => ("NonTerminal", "N"),
+    hidden_by_local: "public static final int N = 4; public int test() { int N = 1; return N; }" =>
+        ("NonTerminal", "N"),
+    hidden_by_parameter: "public static final int N = 4; public int test(int N) { return N; }" =>
+        ("NonTerminal", "N"),
+}
+
+#[test]
+fn other_class() {
+    // Bar.M can only be found once Foo.N has been.
+    let bar = "public class Bar { public Bar() {} public static final int M = Foo.N + 1; }";
+    let members = "public static final int N = 4; public int test() { return Bar.M; }";
+    assert_eq!(folded_with(members, &[("Bar.java", bar)]),
+               ("NumValue".to_owned(), "5".to_owned()));
+}
+
+#[test]
+fn field_initializer() {
+    let mut compiler = common::compiler(&[("Foo.java",
+                                           &common::foo("public static final int N = 2 + 2;"))]);
+    compiler.analyze().unwrap();
+
+    let kind = common::class(compiler.environment().unwrap());
+    let value = kind.fields[0].value.as_ref().unwrap();
+    assert_eq!(value.source_text(), "4");
+}
+
+#[test]
+fn reachability() {
+    let members = "public static final boolean DEBUG = 1 > 0; \
+                   public int test() { while (DEBUG) { } }";
+    assert_eq!(errors(members), Vec::<String>::new());
+
+    let members = "public static final boolean DEBUG = 1 > 0; \
+                   public int test() { while (!DEBUG) { return 1; } return 2; }";
+    let errors = errors(members);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("unreachable statement"), "{}", errors[0]);
+}
+
+#[test]
+fn definite_assignment() {
+    let members = "public static final boolean DEBUG = true; \
+                   public int test() { int x; if (DEBUG || x == 0) { return 1; } return 2; }";
+    assert_eq!(errors(members), Vec::<String>::new());
+}
+
+#[test]
+fn generated() {
+    let files = common::generate(&[("Foo.java",
+                                    &common::foo("public static int test() { return 1 + 2; }"))]);
+    let asm = common::code(&files, "juicyj_unnamedfoo.s");
+    assert!(asm.contains("; num 3"), "{}", asm);
+    assert!(!asm.contains("; +"), "{}", asm);
+}
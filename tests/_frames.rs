@@ -0,0 +1,97 @@
+extern crate juicyj;
+
+mod common;
+
+// The assembly of the method with the given label, in a class Foo with the
+// given members.
+fn method(members: &str, label: &str) -> String {
+    let files = common::generate(&[("Foo.java", &common::foo(members))]);
+    let code = common::code(&files, "juicyj_unnamedfoo.s");
+    let start = code.find(&format!("{}:", label)).unwrap();
+    let end = code[start..].find("\n\n__").map(|e| start + e).unwrap_or(code.len());
+    code[start..end].to_owned()
+}
+
+fn lines(code: &str) -> Vec<String> {
+    code.lines().map(|l| l.trim().to_owned()).filter(|l| !l.is_empty()).collect()
+}
+
+#[test]
+fn prologue() {
+    let code = method("public int test(int a) { int x = 1; int y = 2; return a; }",
+                      "__juicyj_unnamed.Foo.test_INT_");
+    assert_eq!(lines(&code)[1..5].to_vec(),
+               vec!["push ebp", "mov ebp, esp", "sub esp, 8", "; get this"]);
+    assert!(code.contains("mov ebx, [ebp+12]"));
+}
+
+#[test]
+fn parameters() {
+    let code = method("public int test(int a, int b) { return a + b; }",
+                      "__juicyj_unnamed.Foo.test_INTINT_");
+    assert!(code.contains("lea esi, [ebp+8]"));
+    assert!(code.contains("lea esi, [ebp+12]"));
+    assert!(code.contains("mov ebx, [ebp+16]"));
+    assert!(!code.contains("__malloc"));
+}
+
+#[test]
+fn locals() {
+    let code = method("public int test(int a) { int x = a; String s = null; \
+                       for (int i = 0; i < a; i = i + 1) { x = x + i; } return x; }",
+                      "__juicyj_unnamed.Foo.test_INT_");
+    assert!(code.contains("sub esp, 12"));
+    // primitives are stored in their slot, references are pointed to
+    assert!(code.contains("mov [ebp-4], eax"));
+    assert!(code.contains("mov [ebp-8], esi"));
+    assert!(code.contains("lea esi, [ebp-12]"));
+}
+
+#[test]
+fn recursion() {
+    let code = method("public static int test(int n) { \
+                       if (n < 2) { return 1; } return n * Foo.test(n - 1); }",
+                      "__juicyj_unnamed.Foo.test_INT_");
+    let lines = lines(&code);
+    let call = lines.iter().position(|l| l == "call __juicyj_unnamed.Foo.test_INT_").unwrap();
+    assert_eq!(lines[call - 1], "push eax");
+    assert_eq!(lines[call + 1], "add esp, 4");
+    assert_eq!(lines.iter().filter(|l| *l == "ret").count(),
+               lines.iter().filter(|l| *l == "pop ebp").count());
+}
+
+#[test]
+fn no_bss() {
+    let members = "public int test(int a) { int x = a; return x; }";
+    for (_, code) in common::generate(&[("Foo.java", &common::foo(members))]) {
+        assert!(!code.contains("section .bss"));
+    }
+}
+
+#[test]
+fn sibling_scopes() {
+    let code = method("public int test(int a) { \
+                       if (a > 0) { int x = a; a = x; } else { String x = \"s\"; a = 2; } \
+                       return a; }",
+                      "__juicyj_unnamed.Foo.test_INT_");
+    // each x has its own slot, and keeps its own type
+    assert!(code.contains("sub esp, 8"));
+    assert!(code.contains("mov [ebp-4], eax"));
+    assert!(code.contains("mov [ebp-8], esi"));
+    assert!(!code.contains("mov [ebp-4], esi"));
+    assert!(!code.contains("mov [ebp-8], eax"));
+}
+
+#[test]
+fn reassignment() {
+    let code = method("public int test() { Object o = new Object(); o = new Foo(); \
+                       int x = 1; x = 2; return x; }",
+                      "__juicyj_unnamed.Foo.test__");
+    // a reference is reassigned by pointing its slot at the new object
+    assert!(code.contains("; o =\n  lea esi, [ebp-4]\n  push esi\n"));
+    assert!(code.contains("pop ecx\n  mov [ecx], esi\n"));
+    assert!(!code.contains("mov esi, [ebp-4]"));
+    // a primitive is stored in its slot
+    assert!(code.contains("; x =\n  lea esi, [ebp-8]\n  push esi\n"));
+    assert!(code.contains("pop esi\n  mov [esi], eax\n"));
+}
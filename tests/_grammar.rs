@@ -0,0 +1,65 @@
+extern crate juicyj;
+
+#[test]
+fn table_is_up_to_date() {
+    let grammar = juicyj::scanner::read_src_file("grammar/joos.grammar").unwrap();
+    let table = juicyj::scanner::read_src_file("grammar/joos.lr1").unwrap();
+
+    match juicyj::grammar::generate(&grammar) {
+        Ok(generated) => assert!(generated == table, "run `make grammar` to regenerate"),
+        Err(errors) => {
+            for e in errors {
+                println!("{}", e);
+            }
+            assert!(false);
+        }
+    }
+}
+
+#[test]
+fn terminals_must_be_tokens() {
+    let grammar = "Start:\n\tBOF S EOF\n\nS:\n\tIDENTIFER\n";
+
+    match juicyj::grammar::generate(grammar) {
+        Ok(_) => assert!(false, "IDENTIFER is not a token"),
+        Err(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].message.code(), "J0023");
+            assert!(format!("{}", errors[0].message).contains("IDENTIFER on line 5"));
+        }
+    }
+}
+
+#[test]
+fn shift_reduce_conflict() {
+    // the dangling else
+    let grammar = "Start:\n\tBOF S EOF\n\nS:\n\tIF S\n\tIF S ELSE S\n\tIDENTIFIER\n";
+
+    match juicyj::grammar::generate(grammar) {
+        Ok(_) => assert!(false, "dangling else is ambiguous"),
+        Err(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].message.code(), "J0029");
+            assert!(format!("{}", errors[0].message).ends_with("on ELSE"));
+            assert!(errors[0].items.contains(&"S -> IF S ., ELSE".to_owned()));
+            assert!(errors[0].items.contains(&"S -> IF S . ELSE S, ELSE".to_owned()));
+        }
+    }
+}
+
+#[test]
+fn reduce_reduce_conflict() {
+    let grammar = "Start:\n\tBOF S EOF\n\nS:\n\tA\n\tB\n\nA:\n\tIDENTIFIER\n\nB:\n\tIDENTIFIER\n";
+
+    match juicyj::grammar::generate(grammar) {
+        Ok(_) => assert!(false, "A and B both reduce IDENTIFIER"),
+        Err(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].message.code(), "J0028");
+            assert!(format!("{}", errors[0].message).ends_with("on EOF"));
+            assert_eq!(errors[0].items,
+                       vec!["A -> IDENTIFIER ., EOF".to_owned(),
+                            "B -> IDENTIFIER ., EOF".to_owned()]);
+        }
+    }
+}
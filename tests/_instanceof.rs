@@ -0,0 +1,110 @@
+extern crate juicyj;
+
+mod common;
+
+use common::code;
+
+// Generates a class Foo extending Bar with the given members, returning the
+// assembly of each class by file name.
+fn generate(members: &str) -> Vec<(String, String)> {
+    common::generate(&[("Bar.java", common::BAR),
+                       ("Foo.java", &common::foo_extends_bar(members))])
+}
+
+// The entries of the type descriptor with the given label.
+fn descriptor(code: &str, label: &str) -> Vec<String> {
+    let label = format!("{}: dd ", label);
+    let start = code.find(&label).unwrap() + label.len();
+    let end = code[start..].find('\n').map(|e| start + e).unwrap_or(code.len());
+    code[start..end].split(", ").map(|m| m.to_owned()).collect()
+}
+
+// The index of each instanceof test, in order.
+fn indices(code: &str) -> Vec<usize> {
+    code.lines()
+        .filter(|l| l.starts_with("  mov eax, [ecx+4*"))
+        .map(|l| l["  mov eax, [ecx+4*".len()..l.len() - 1].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn descriptors() {
+    let files = generate("public static int test() { return 0; }");
+    let foo = code(&files, "juicyj_unnamedfoo.s");
+    let object = code(&files, "javalangobject.s");
+
+    let foo_type = descriptor(foo, "__TYPEjuicyj_unnamed.Foo");
+    let foo_array = descriptor(foo, "__ARRAYjuicyj_unnamed.Foo");
+    let object_type = descriptor(object, "__TYPEjava.lang.Object");
+    let int_array = descriptor(object, "__ARRAYint");
+    assert_eq!(foo_type.len(), object_type.len());
+    assert_eq!(foo_array.len(), int_array.len());
+
+    // Foo, Bar and Object
+    assert_eq!(foo_type.iter().filter(|e| *e == "1").count(), 3);
+    assert_eq!(object_type.iter().filter(|e| *e == "1").count(), 1);
+    // Foo[], Bar[], Object[], Object, Cloneable and Serializable
+    assert_eq!(foo_array.iter().filter(|e| *e == "1").count(), 6);
+    // int[], Object, Cloneable and Serializable
+    assert_eq!(int_array.iter().filter(|e| *e == "1").count(), 4);
+
+    assert!(foo.contains("global __TYPEjuicyj_unnamed.Foo"));
+    assert!(object.contains("global __ARRAYint"));
+}
+
+#[test]
+fn headers() {
+    let files = generate("public static int test() { int[] a = new int[2]; Object o = null; \
+                          return a.length; }");
+    let foo = code(&files, "juicyj_unnamedfoo.s");
+
+    // objects, arrays and null all point to their descriptor from the word
+    // before them
+    assert!(foo.contains("mov dword [eax], __TYPEjuicyj_unnamed.Foo\n  lea ebx, [eax+4]"));
+    assert!(foo.contains("mov dword [eax], __ARRAYint\n  ; array.length = x\n  \
+                          lea esi, [eax+4]"));
+    assert!(foo.contains("extern __ARRAYint"));
+    assert!(foo.contains("mov dword [eax], 0\n  lea esi, [eax+4]"));
+}
+
+#[test]
+fn instanceof() {
+    let files = generate("public static int test() { Object o = new Foo(); \
+                          Object a = new Foo[1]; int r = 0; \
+                          if (o instanceof Bar) r = r + 1; \
+                          if (a instanceof Object[]) r = r + 1; \
+                          if (null instanceof Foo) r = r + 1; return r; }");
+    let foo = code(&files, "juicyj_unnamedfoo.s");
+    assert!(foo.contains("; instanceof juicyj_unnamed.Bar"));
+    assert!(foo.contains("; instanceof java.lang.Object[]"));
+    assert!(foo.contains("; instanceof juicyj_unnamed.Foo"));
+
+    // each test reads the entry for its type from the operand's descriptor
+    let foo_type = descriptor(foo, "__TYPEjuicyj_unnamed.Foo");
+    let foo_array = descriptor(foo, "__ARRAYjuicyj_unnamed.Foo");
+    let indices = indices(foo);
+    assert_eq!(indices.len(), 3);
+    assert_eq!(foo_type[indices[0]], "1");
+    assert_eq!(foo_array[indices[1]], "1");
+    assert_eq!(foo_type[indices[2]], "1");
+}
+
+#[test]
+fn null() {
+    // null has a type descriptor of 0, so the test gives 0 without reading it
+    let files = generate("public static int test() { Foo f = null; \
+                          if (f instanceof Foo) return 1; return 0; }");
+    let foo = code(&files, "juicyj_unnamedfoo.s");
+    assert!(foo.contains("; null\n"));
+    assert!(foo.contains("; instanceof juicyj_unnamed.Foo\n  mov eax, 0\n  \
+                          mov ecx, [esi-4]\n  cmp ecx, 0\n  je .instanceof"));
+    assert!(!foo.contains("__exception"));
+}
+
+#[test]
+fn not_constant() {
+    // instanceof depends on the runtime type, even for a subtype
+    let files = generate("public static int test() { Bar b = null; \
+                          if (b instanceof Bar) return 1; return 0; }");
+    assert!(code(&files, "juicyj_unnamedfoo.s").contains("; instanceof juicyj_unnamed.Bar"));
+}
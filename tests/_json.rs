@@ -0,0 +1,66 @@
+extern crate juicyj;
+extern crate rustc_serialize;
+
+use rustc_serialize::json::Json;
+use rustc_serialize::json::ToJson;
+
+const SOURCE: &'static str = "package foo; import java.util.*; public class Bar { public int x; \
+                              public Bar() {} public static int test(int[] a) { return 123; } }";
+
+#[test]
+fn ast() {
+    let mut compiler = juicyj::Compiler::new();
+    compiler.add_source("Bar.java", SOURCE);
+    compiler.parse().unwrap();
+
+    let json = Json::from_str(&compiler.asts().to_json().to_string()).unwrap();
+    let ast = &json[0];
+    assert_eq!(ast["canonical"], Json::String("foo.Bar".to_owned()));
+    assert_eq!(ast["package"], Json::String("foo".to_owned()));
+    assert_eq!(ast["imports"],
+               Json::Array(vec![Json::String("java.lang.*".to_owned()),
+                                Json::String("java.util.*".to_owned())]));
+    assert_eq!(ast["root"]["lexeme"], Json::String("ClassDeclaration".to_owned()));
+    assert_eq!(ast["root"]["span"]["line"], Json::U64(1));
+
+    let file = ast["root"]["span"]["file"].as_u64().unwrap() as usize;
+    assert_eq!(compiler.sources().to_json()[file], Json::String("Bar.java".to_owned()));
+}
+
+#[test]
+fn environment() {
+    let mut compiler = juicyj::Compiler::new();
+    compiler.add_sources(&juicyj::source::stdlib()).unwrap();
+    compiler.add_source("Bar.java", SOURCE);
+    compiler.analyze().unwrap();
+
+    let json = compiler.environment().unwrap().to_json();
+    let kinds = json["kinds"].as_array().unwrap();
+    let bar = kinds.iter().find(|k| k["name"] == Json::String("foo.Bar".to_owned())).unwrap();
+    assert_eq!(bar["kind"], Json::String("class".to_owned()));
+    assert_eq!(bar["fields"][0]["name"], Json::String("x".to_owned()));
+    assert_eq!(bar["fields"][0]["type"], Json::String("int".to_owned()));
+
+    let methods = bar["methods"].as_array().unwrap();
+    let test = methods.iter().find(|m| m["name"] == Json::String("test".to_owned())).unwrap();
+    assert_eq!(test["modifiers"],
+               Json::Array(vec![Json::String("public".to_owned()),
+                                Json::String("static".to_owned())]));
+    assert_eq!(test["parameters"][0]["type"], Json::String("int[]".to_owned()));
+}
+
+#[test]
+fn diagnostics() {
+    let mut compiler = juicyj::Compiler::new();
+    compiler.add_source("Bar.java",
+                        "public class Bar {\n    public final int x;\n    public Bar() {}\n}\n");
+    let errors = compiler.parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    let json = Json::from_str(&errors[0].to_json(compiler.sources()).to_string()).unwrap();
+    assert_eq!(json["phase"], Json::String("weeder".to_owned()));
+    assert_eq!(json["code"], Json::String("FinalNoInit".to_owned()));
+    assert_eq!(json["file"], Json::String("Bar.java".to_owned()));
+    assert_eq!(json["line"], Json::U64(2));
+    assert_eq!(json["message"], Json::String("final field has no initializer".to_owned()));
+}
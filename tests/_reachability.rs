@@ -0,0 +1,101 @@
+extern crate juicyj;
+
+use juicyj::error::Diagnostic;
+
+// Wraps a method body in a class. The method ends with a closing brace on line
+// 5, column 5.
+fn source(body: &str) -> String {
+    format!("public class Foo {{\n    public Foo() {{}}\n    \
+             public int test(boolean c) {{\n        {}\n    }}\n}}\n",
+            body)
+}
+
+// Compiles a method body, returning the errors in it.
+fn errors(body: &str) -> Vec<Diagnostic> {
+    let source = source(body);
+    let mut compiler = juicyj::Compiler::new();
+    compiler.add_source("Foo.java", &source);
+    compiler.add_sources(&juicyj::source::stdlib()).unwrap();
+    match compiler.analyze() {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors,
+    }
+}
+
+macro_rules! reachable_tests {
+    ($($name:ident: $body:tt,)*) => {
+    $(
+        #[test]
+        fn $name() {
+            let errors = errors($body);
+            assert!(errors.is_empty(), "{:?}", errors);
+        }
+    )*
+    }
+}
+
+macro_rules! unreachable_tests {
+    ($($name:ident: $body:tt => $statement:tt,)*) => {
+    $(
+        #[test]
+        fn $name() {
+            let errors = errors($body);
+            assert_eq!(errors.len(), 1);
+            let message = format!("{}", errors[0]);
+            assert!(message.contains("unreachable statement"), "{}", message);
+            assert_eq!(errors[0].span.unwrap().text(&source($body)), $statement);
+        }
+    )*
+    }
+}
+
+macro_rules! missing_return_tests {
+    ($($name:ident: $body:tt,)*) => {
+    $(
+        #[test]
+        fn $name() {
+            let errors = errors($body);
+            assert_eq!(errors.len(), 1);
+            let message = format!("{}", errors[0]);
+            assert!(message.contains("missing return statement"), "{}", message);
+            let span = errors[0].span.unwrap();
+            assert_eq!((span.line, span.column), (5, 5));
+        }
+    )*
+    }
+}
+
+reachable_tests! {
+    return_last: "int x = 1; return x;",
+    both_branches_return: "if (c) { return 1; } else { return 2; }",
+    while_true: "while (true) { }",
+    while_true_nested: "int x = 0; while (true) { x = x + 1; if (x > 10) return x; }",
+    while_constant_expression: "while (1 < 2 && !false) { }",
+    for_without_condition: "for (;;) { }",
+    for_true: "for (int i = 0; true; i = i + 1) { }",
+    if_false: "if (false) { return 1; } return 2;",
+    if_true: "if (true) { return 1; } return 2;",
+    while_variable_then_return: "while (c) { return 1; } return 2;",
+}
+
+unreachable_tests! {
+    after_return: "return 1; return 2;" => "return 2;",
+    after_nested_return: "{ return 1; } int x = 2;" => "int x = 2",
+    after_if_else: "if (c) return 1; else return 2; return 3;" => "return 3;",
+    after_while_true: "while (true) { } return 1;" => "return 1;",
+    after_for_without_condition: "for (;;) { } return 1;" => "return 1;",
+    while_false: "while (false) { return 1; } return 2;" => "{ return 1; }",
+    while_constant_false: "while (1 > 2) { return 1; } return 2;" => "{ return 1; }",
+    for_false: "for (; false; ) { return 1; } return 2;" => "{ return 1; }",
+    in_loop_body: "while (c) { return 1; return 2; } return 3;" => "return 2;",
+}
+
+missing_return_tests! {
+    empty: "",
+    no_return: "int x = 1;",
+    if_without_else: "if (c) { return 1; }",
+    one_branch_returns: "if (c) { return 1; } else { }",
+    while_variable: "while (c) { return 1; }",
+    for_with_condition: "for (int i = 0; i < 3; i = i + 1) { return i; }",
+}
+
@@ -1,14 +1,19 @@
 extern crate juicyj;
 
+use std::path::Path;
+
+use juicyj::source::SourceProvider;
+
 macro_rules! feature_tests {
     ($($name:ident: $case:tt,)*) => {
     $(
         #[test]
         fn $name() {
             let filename: String = format!("stdlib/java/{}.java", $case);
-            let src: String = juicyj::scanner::read_src_file(&filename);
+            let mut compiler = juicyj::Compiler::new();
+            compiler.add_file(&filename).unwrap();
 
-            juicyj::scanner::tests::scan_or_assert(&filename, &src);
+            juicyj::scanner::tests::scan_or_assert(&mut compiler);
         }
     )*
     }
@@ -31,3 +36,11 @@ feature_tests! {
     lang_system: "lang/System",
     util_arrays: "util/Arrays",
 }
+
+#[test]
+fn provider() {
+    // the stdlib does not depend on the current directory
+    let sources = juicyj::source::stdlib().sources().unwrap();
+    assert_eq!(sources.len(), 15);
+    assert!(sources.iter().all(|&(ref path, _)| Path::new(path).is_absolute()));
+}
@@ -0,0 +1,248 @@
+extern crate juicyj;
+
+use juicyj::scanner::typed::Expression;
+use juicyj::scanner::typed::Member;
+use juicyj::scanner::typed::Statement;
+use juicyj::scanner::typed::TypeDeclaration;
+use juicyj::scanner::typed::TypeKind;
+
+fn declaration(source: &str) -> TypeDeclaration {
+    let mut sources = juicyj::scanner::SourceMap::new();
+    let file = sources.add("Foo.java", source);
+    juicyj::scanner::scan(&sources, file).unwrap().declaration.unwrap()
+}
+
+// Scans a class with the given members, returning the body of its method
+// `test`.
+fn body(members: &str) -> Statement {
+    let source = format!("public class Foo {{ public Foo() {{}} {} }}", members);
+    for member in declaration(&source).members {
+        if let Member::Method(method) = member {
+            if method.name.lexeme == Some("test".to_owned()) {
+                return method.body.unwrap();
+            }
+        }
+    }
+    panic!("no method test");
+}
+
+// The value returned by the method `test` with the given body.
+fn returned(statements: &str) -> Expression {
+    match body(&format!("public int test() {{ {} }}", statements)) {
+        Statement::Block { mut statements, .. } => {
+            match statements.pop() {
+                Some(Statement::Return { value: Some(value), .. }) => value,
+                s => panic!("expected a return, got {:?}", s),
+            }
+        }
+        s => panic!("expected a block, got {:?}", s),
+    }
+}
+
+fn token(lexeme: &Option<String>) -> String {
+    lexeme.clone().unwrap_or("".to_owned())
+}
+
+fn list(expressions: &[Expression]) -> String {
+    expressions.iter().map(expression).collect::<Vec<_>>().join(" ")
+}
+
+// The shape of an expression, written as an s-expression.
+fn expression(node: &Expression) -> String {
+    match *node {
+        Expression::Literal(ref t) => format!("{:?}", t.kind),
+        Expression::This(_) => "this".to_owned(),
+        Expression::Name(ref n) => n.source_text(),
+        Expression::FieldAccess { ref object, ref field, .. } => {
+            format!("(field {} {})", expression(object), token(&field.lexeme))
+        }
+        Expression::MethodInvocation { object: Some(ref object),
+                                       ref method,
+                                       ref arguments,
+                                       .. } => {
+            format!("(call {} {} [{}])",
+                    expression(object),
+                    method.source_text(),
+                    list(arguments))
+        }
+        Expression::MethodInvocation { object: None, ref method, ref arguments, .. } => {
+            format!("(call {} [{}])", method.source_text(), list(arguments))
+        }
+        Expression::ArrayAccess { ref array, ref index, .. } => {
+            format!("(index {} {})", expression(array), expression(index))
+        }
+        Expression::ArrayCreation { ref element, size: Some(ref size), .. } => {
+            format!("(array {} {})", element.source_text(), expression(size))
+        }
+        Expression::ArrayCreation { ref element, size: None, .. } => {
+            format!("(array {})", element.source_text())
+        }
+        Expression::ClassInstanceCreation { ref kind, ref arguments, .. } => {
+            format!("(new {} [{}])", kind.source_text(), list(arguments))
+        }
+        Expression::Cast { ref kind, ref operand, .. } => {
+            format!("(cast {} {})", kind.source_text(), expression(operand))
+        }
+        Expression::Unary { ref operator, ref operand, .. } => {
+            format!("({:?} {})", operator.kind, expression(operand))
+        }
+        Expression::Binary { ref operator, ref lhs, ref rhs, .. } => {
+            format!("({:?} {} {})", operator.kind, expression(lhs), expression(rhs))
+        }
+        Expression::InstanceOf { ref operand, ref kind, .. } => {
+            format!("(instanceof {} {})", expression(operand), kind.source_text())
+        }
+        Expression::Assignment { ref lhs, ref rhs, .. } => {
+            format!("(= {} {})", expression(lhs), expression(rhs))
+        }
+    }
+}
+
+macro_rules! expression_tests {
+    ($($name:ident: $statements:tt => $shape:tt,)*) => {
+    $(
+        #[test]
+        fn $name() {
+            assert_eq!(expression(&returned($statements)), $shape);
+        }
+    )*
+    }
+}
+
+expression_tests! {
+    literal: "return 1;" => "NumValue",
+    name: "return a.b.c;" => "a.b.c",
+    field_access: "return this.f;" => "(field this f)",
+    method_by_name: "return f(1, 2);" => "(call f [NumValue NumValue])",
+    method_qualified: "return a.f(1);" => "(call a.f [NumValue])",
+    method_on_object: "return this.f();" => "(call this f [])",
+    array_access: "return a[0];" => "(index a NumValue)",
+    array_creation: "return new int[3];" => "(array int NumValue)",
+    class_creation: "return new Foo(1);" => "(new Foo [NumValue])",
+    cast: "return (Object) x;" => "(cast Object x)",
+    cast_array: "return (int[]) x;" => "(cast int[] x)",
+    negation: "return -x;" => "(Minus NumValue x)",
+    not: "return !x;" => "(Not x)",
+    binary: "return 1 + x * 2;" => "(Plus NumValue (Star x NumValue))",
+    instanceof: "return x instanceof Foo;" => "(instanceof x Foo)",
+    assignment: "return x = y = 1;" => "(= x (= y NumValue))",
+    parenthesized: "return (1 + 2) * 3;" => "(Star (Plus NumValue NumValue) NumValue)",
+}
+
+#[test]
+fn statements() {
+    let statements = match body("public void test() { int x; int y = 1; if (b) x = 1; \
+                                 else { x = 2; } while (b) ; for (int i = 0; ; ) {} \
+                                 return; }") {
+        Statement::Block { statements, .. } => statements,
+        s => panic!("expected a block, got {:?}", s),
+    };
+    assert_eq!(statements.len(), 6);
+
+    match statements[0] {
+        Statement::LocalVariableDeclaration { ref name, initializer: None, .. } => {
+            assert_eq!(name.lexeme, Some("x".to_owned()))
+        }
+        ref s => panic!("expected a declaration, got {:?}", s),
+    }
+    match statements[1] {
+        Statement::LocalVariableDeclaration { ref kind, initializer: Some(_), .. } => {
+            assert_eq!(kind.source_text(), "int")
+        }
+        ref s => panic!("expected a declaration, got {:?}", s),
+    }
+    match statements[2] {
+        Statement::If { ref then, otherwise: Some(ref otherwise), .. } => {
+            match **then {
+                Statement::Expression(Expression::Assignment { .. }) => (),
+                ref s => panic!("expected an assignment, got {:?}", s),
+            }
+            match **otherwise {
+                Statement::Block { ref statements, .. } => assert_eq!(statements.len(), 1),
+                ref s => panic!("expected a block, got {:?}", s),
+            }
+        }
+        ref s => panic!("expected an if, got {:?}", s),
+    }
+    match statements[3] {
+        Statement::While { ref body, .. } => {
+            match **body {
+                Statement::Empty(_) => (),
+                ref s => panic!("expected an empty statement, got {:?}", s),
+            }
+        }
+        ref s => panic!("expected a while, got {:?}", s),
+    }
+    match statements[4] {
+        Statement::For { init: Some(_), condition: None, update: None, .. } => (),
+        ref s => panic!("expected a for, got {:?}", s),
+    }
+    match statements[5] {
+        Statement::Return { value: None, .. } => (),
+        ref s => panic!("expected a return, got {:?}", s),
+    }
+}
+
+#[test]
+fn class() {
+    let source = "public abstract class Foo extends Bar implements Baz, Qux { \
+                  public Foo(int a) {} \
+                  protected static int n = 1; \
+                  public Foo f; \
+                  public abstract String[] g(int[] a, Foo b); \
+                  public void h() {} }";
+    let class = declaration(source);
+    assert_eq!(class.kind, TypeKind::Class);
+    assert_eq!(class.name.lexeme, Some("Foo".to_owned()));
+    assert_eq!(class.modifiers.len(), 2);
+    assert_eq!(class.extends.iter().map(|e| e.source_text()).collect::<Vec<_>>(), vec!["Bar"]);
+    assert_eq!(class.implements.iter().map(|e| e.source_text()).collect::<Vec<_>>(),
+               vec!["Baz", "Qux"]);
+    assert_eq!(class.members.len(), 5);
+
+    match class.members[0] {
+        Member::Constructor(ref c) => assert_eq!(c.parameters.len(), 1),
+        ref m => panic!("expected a constructor, got {:?}", m),
+    }
+    match class.members[1] {
+        Member::Field(ref f) => {
+            assert_eq!(f.modifiers.len(), 2);
+            assert_eq!(f.kind.source_text(), "int");
+            assert!(f.initializer.is_some());
+        }
+        ref m => panic!("expected a field, got {:?}", m),
+    }
+    match class.members[2] {
+        Member::Field(ref f) => {
+            assert_eq!(f.kind.source_text(), "Foo");
+            assert!(f.initializer.is_none());
+        }
+        ref m => panic!("expected a field, got {:?}", m),
+    }
+    match class.members[3] {
+        Member::Method(ref m) => {
+            assert_eq!(m.return_type.source_text(), "String[]");
+            assert_eq!(m.parameters.iter().map(|p| p.kind.source_text()).collect::<Vec<_>>(),
+                       vec!["int[]", "Foo"]);
+            assert!(m.body.is_none());
+        }
+        ref m => panic!("expected a method, got {:?}", m),
+    }
+    match class.members[4] {
+        Member::Method(ref m) => {
+            assert_eq!(m.return_type.source_text(), "void");
+            assert!(m.body.is_some());
+        }
+        ref m => panic!("expected a method, got {:?}", m),
+    }
+}
+
+#[test]
+fn interface() {
+    let source = "public interface Foo extends Bar, Baz { public int f(); }";
+    let interface = declaration(source);
+    assert_eq!(interface.kind, TypeKind::Interface);
+    assert_eq!(interface.extends.len(), 2);
+    assert!(interface.implements.is_empty());
+    assert_eq!(interface.members.len(), 1);
+}
@@ -0,0 +1,172 @@
+extern crate juicyj;
+
+use juicyj::scanner::ASTNode;
+use juicyj::scanner::TokenKind;
+use juicyj::scanner::typed::Expression;
+use juicyj::scanner::typed::Statement;
+use juicyj::scanner::typed::TypeDeclaration;
+use juicyj::scanner::visit;
+use juicyj::scanner::visit::Folder;
+use juicyj::scanner::visit::Visitor;
+use juicyj::scanner::visit::VisitorMut;
+
+fn declaration(members: &str) -> TypeDeclaration {
+    let source = format!("public class Foo extends Bar {{ public Foo() {{}} {} }}", members);
+    let mut sources = juicyj::scanner::SourceMap::new();
+    let file = sources.add("Foo.java", &source);
+    juicyj::scanner::scan(&sources, file).unwrap().declaration.unwrap()
+}
+
+// Records every name, type and local variable it visits, in order.
+struct Names(Vec<String>);
+
+impl Visitor for Names {
+    fn visit_statement(&mut self, node: &Statement) {
+        if let Statement::LocalVariableDeclaration { ref name, .. } = *node {
+            self.0.push(format!("local {}", name.lexeme.clone().unwrap()));
+        }
+        visit::walk_statement(self, node);
+    }
+
+    fn visit_type(&mut self, node: &ASTNode) {
+        self.0.push(format!("type {}", node.source_text()));
+    }
+
+    fn visit_name(&mut self, node: &ASTNode) {
+        self.0.push(format!("name {}", node.source_text()));
+    }
+}
+
+#[test]
+fn visitor() {
+    let class = declaration("public int[] f; public void test(String s) { \
+                             int x = s.length(); for (Object o = s; o != null; ) { x = x + 1; } \
+                             this.g((Foo) o, new int[a.b]); }");
+    let mut names = Names(Vec::new());
+    names.visit_type_declaration(&class);
+    assert_eq!(names.0,
+               vec!["type Bar",
+                    "type int[]",
+                    "type void",
+                    "type String",
+                    "local x",
+                    "type int",
+                    "name s.length",
+                    "local o",
+                    "type Object",
+                    "name s",
+                    "name o",
+                    "name x",
+                    "name x",
+                    "type Foo",
+                    "name o",
+                    "type int",
+                    "name a.b"]);
+}
+
+// Counts the statements within loops, without descending into expressions.
+struct Looped {
+    depth: usize,
+    count: usize,
+}
+
+impl Visitor for Looped {
+    fn visit_statement(&mut self, node: &Statement) {
+        let looping = match *node {
+            Statement::While { .. } |
+            Statement::For { .. } => true,
+            _ => false,
+        };
+        if self.depth > 0 {
+            self.count += 1;
+        }
+        if looping {
+            self.depth += 1;
+        }
+        visit::walk_statement(self, node);
+        if looping {
+            self.depth -= 1;
+        }
+    }
+
+    fn visit_expression(&mut self, _node: &Expression) {}
+}
+
+#[test]
+fn visitor_overrides() {
+    let class = declaration("public void test() { int x = 0; while (x < 1) { x = 1; ; } \
+                             for (;;) return; }");
+    let mut looped = Looped { depth: 0, count: 0 };
+    looped.visit_type_declaration(&class);
+    assert_eq!(looped.count, 4);
+}
+
+// Renames every local variable declaration.
+struct Rename;
+
+impl VisitorMut for Rename {
+    fn visit_statement_mut(&mut self, node: &mut Statement) {
+        if let Statement::LocalVariableDeclaration { ref mut name, .. } = *node {
+            name.lexeme = name.lexeme.as_ref().map(|l| format!("{}_", l));
+        }
+        visit::walk_statement_mut(self, node);
+    }
+}
+
+#[test]
+fn visitor_mut() {
+    let mut class = declaration("public void test() { int x = 1; if (true) { int y; } }");
+    Rename.visit_type_declaration_mut(&mut class);
+
+    let mut names = Names(Vec::new());
+    names.visit_type_declaration(&class);
+    assert!(names.0.contains(&"local x_".to_owned()));
+    assert!(names.0.contains(&"local y_".to_owned()));
+}
+
+// Replaces every `true` with `false`.
+struct Negate;
+
+impl Folder for Negate {
+    fn fold_expression(&mut self, node: Expression) -> Expression {
+        match node {
+            Expression::Literal(mut token) => {
+                if token.kind == TokenKind::True {
+                    token.kind = TokenKind::False;
+                }
+                Expression::Literal(token)
+            }
+            _ => visit::fold_expression(self, node),
+        }
+    }
+}
+
+// Counts the `false` literals.
+struct Falses(usize);
+
+impl Visitor for Falses {
+    fn visit_expression(&mut self, node: &Expression) {
+        if let Expression::Literal(ref token) = *node {
+            if token.kind == TokenKind::False {
+                self.0 += 1;
+            }
+        }
+        visit::walk_expression(self, node);
+    }
+}
+
+#[test]
+fn folder() {
+    let class = declaration("public static boolean b = true; \
+                             public boolean test() { while (true && b) { b = !true; } \
+                             return false; }");
+    let mut falses = Falses(0);
+    falses.visit_type_declaration(&class);
+    assert_eq!(falses.0, 1);
+
+    let class = Negate.fold_type_declaration(class);
+    let mut falses = Falses(0);
+    falses.visit_type_declaration(&class);
+    assert_eq!(falses.0, 4);
+    assert_eq!(class.members.len(), 3);
+}
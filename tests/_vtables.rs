@@ -0,0 +1,131 @@
+extern crate juicyj;
+
+mod common;
+
+use common::code;
+use common::generate;
+
+// The methods in the vtable of the given class, without their class prefix.
+fn vtable(code: &str, class: &str) -> Vec<String> {
+    let label = format!("__VTABLEjuicyj_unnamed.{}: dd ", class);
+    let start = code.find(&label).unwrap() + label.len();
+    let end = code[start..].find('\n').map(|e| start + e).unwrap_or(code.len());
+    let prefix = format!("__juicyj_unnamed.{}.", class);
+    code[start..end].split(", ").map(|m| m.trim_left_matches(&prefix).to_owned()).collect()
+}
+
+const BAR: &'static str = "public class Bar { public Bar() {} \
+                           public int f(int a) { return a; } \
+                           public int g() { return this.f(2); } \
+                           public static int s() { return 0; } }";
+const FOO: &'static str = "public class Foo extends Bar { public Foo() {} \
+                           public int h() { return 3; } \
+                           public int f(int a) { return a + 1; } \
+                           public static int test() { Bar b = new Foo(); \
+                           return b.f(1) + b.g() + Bar.s(); } }";
+
+#[test]
+fn layout() {
+    let files = generate(&[("Bar.java", BAR), ("Foo.java", FOO)]);
+    let bar = vtable(code(&files, "juicyj_unnamedbar.s"), "Bar");
+    let foo = vtable(code(&files, "juicyj_unnamedfoo.s"), "Foo");
+
+    // a subclass extends its parent's vtable, overriding methods in place
+    assert_eq!(foo[..bar.len()].to_vec(), bar);
+    assert_eq!(foo[bar.len()..].to_vec(), vec!["h__"]);
+    assert!(bar.contains(&"f_INT_".to_owned()));
+    assert!(bar.contains(&"toString__".to_owned()));
+    assert!(!bar.contains(&"s__".to_owned()));
+    assert!(!bar.contains(&"Bar__".to_owned()));
+}
+
+#[test]
+fn constructor() {
+    let files = generate(&[("Bar.java", BAR), ("Foo.java", FOO)]);
+    let foo = code(&files, "juicyj_unnamedfoo.s");
+    assert!(foo.contains("mov dword [ebx], __VTABLEjuicyj_unnamed.Foo"));
+    assert!(foo.contains("global __VTABLEjuicyj_unnamed.Foo"));
+    assert!(!foo.contains("0xBAADCAFE"));
+}
+
+#[test]
+fn dispatch() {
+    let files = generate(&[("Bar.java", BAR), ("Foo.java", FOO)]);
+    let foo = code(&files, "juicyj_unnamedfoo.s");
+    let bar = vtable(code(&files, "juicyj_unnamedbar.s"), "Bar");
+    let f = bar.iter().position(|m| m == "f_INT_").unwrap();
+    let g = bar.iter().position(|m| m == "g__").unwrap();
+
+    // calls on a Bar go through the receiver's vtable
+    assert!(foo.contains(&format!("mov eax, [esp+4*1]\n  mov eax, [eax]\n  call [eax+4*{}]", f)));
+    assert!(foo.contains(&format!("mov eax, [esp+4*0]\n  mov eax, [eax]\n  call [eax+4*{}]", g)));
+    assert!(!foo.contains("call __juicyj_unnamed.Bar.f_INT_"));
+    assert!(!foo.contains("extern __juicyj_unnamed.Bar.f_INT_"));
+
+    // static methods and constructors are still called directly
+    assert!(foo.contains("call __juicyj_unnamed.Bar.s__"));
+    assert!(foo.contains("call __juicyj_unnamed.Foo.Foo__"));
+}
+
+#[test]
+fn interfaces() {
+    let files = generate(&[("Baz.java", "public interface Baz { public int f(int a); }")]);
+    assert!(!code(&files, "juicyj_unnamedbaz.s").contains("__VTABLE"));
+}
+
+const BAZ: &'static str = "public interface Baz { public int f(int a); public int k(); }";
+const QUX: &'static str = "public class Qux implements Baz { public Qux() {} \
+                           public int f(int a) { return a; } \
+                           public int k() { return 1; } \
+                           public static int test() { Baz b = new Qux(); \
+                           return b.f(1) + b.k(); } }";
+
+// The entries of the interface table of the given class.
+fn itable(code: &str, class: &str) -> Vec<String> {
+    let label = format!("__ITABLEjuicyj_unnamed.{}: dd ", class);
+    let start = code.find(&label).unwrap() + label.len();
+    let end = code[start..].find('\n').map(|e| start + e).unwrap_or(code.len());
+    code[start..end].split(", ").map(|m| m.to_owned()).collect()
+}
+
+#[test]
+fn itables() {
+    let files = generate(&[("Bar.java", BAR), ("Baz.java", BAZ), ("Qux.java", QUX)]);
+    let bar = code(&files, "juicyj_unnamedbar.s");
+    let qux = code(&files, "juicyj_unnamedqux.s");
+
+    // every class has a table of the same size, indexed by selector
+    let bar = itable(bar, "Bar");
+    let qux = itable(qux, "Qux");
+    assert_eq!(bar.len(), qux.len());
+    let f = qux.iter().position(|m| m == "__juicyj_unnamed.Qux.f_INT_").unwrap();
+    let k = qux.iter().position(|m| m == "__juicyj_unnamed.Qux.k__").unwrap();
+    assert_eq!(bar[f], "__juicyj_unnamed.Bar.f_INT_");
+    assert_eq!(bar[k], "0");
+}
+
+#[test]
+fn itable_pointer() {
+    let files = generate(&[("Baz.java", BAZ), ("Qux.java", QUX)]);
+    let qux = code(&files, "juicyj_unnamedqux.s");
+    assert!(qux.contains("  dd __ITABLEjuicyj_unnamed.Qux\n__VTABLEjuicyj_unnamed.Qux: dd "));
+}
+
+#[test]
+fn interface_dispatch() {
+    let files = generate(&[("Baz.java", BAZ), ("Qux.java", QUX)]);
+    let qux = code(&files, "juicyj_unnamedqux.s");
+    let table = itable(qux, "Qux");
+    let f = table.iter().position(|m| m == "__juicyj_unnamed.Qux.f_INT_").unwrap();
+    let k = table.iter().position(|m| m == "__juicyj_unnamed.Qux.k__").unwrap();
+
+    // calls on a Baz go through the receiver's interface table
+    assert!(qux.contains(&format!("mov eax, [esp+4*1]\n  mov eax, [eax]\n  \
+                                   mov eax, [eax-4]\n  call [eax+4*{}]",
+                                  f)));
+    assert!(qux.contains(&format!("mov eax, [esp+4*0]\n  mov eax, [eax]\n  \
+                                   mov eax, [eax-4]\n  call [eax+4*{}]",
+                                  k)));
+    assert!(!qux.contains("call __juicyj_unnamed.Baz."));
+    assert!(!qux.contains("extern __juicyj_unnamed.Baz."));
+}
@@ -0,0 +1,48 @@
+extern crate juicyj;
+
+use juicyj::error::Lint;
+
+macro_rules! warning_tests {
+    ($($name:ident: $case:tt => [$($lint:expr),*],)*) => {
+    $(
+        #[test]
+        fn $name() {
+            let filename: String = format!("tests/cases/warnings/{}.java", $case);
+            let mut compiler = juicyj::Compiler::new();
+            compiler.add_file(&filename).unwrap();
+            compiler.add_sources(&juicyj::source::stdlib()).unwrap();
+            compiler.analyze().unwrap();
+
+            let lints = compiler.warnings().iter().map(|w| w.lint).collect::<Vec<Lint>>();
+            assert_eq!(lints, vec![$($lint),*]);
+        }
+    )*
+    }
+}
+
+warning_tests! {
+    clean: "Clean" => [],
+    empty_body: "EmptyBody" => [Lint::EmptyBody],
+    nested_string_comparison: "NestedStringComparison" => [Lint::StringComparison,
+                                                           Lint::StringComparison],
+    shadowed_field: "ShadowedField" => [Lint::ShadowedField],
+    string_comparison: "StringComparison" => [Lint::StringComparison],
+    unused_import: "UnusedImport" => [Lint::UnusedImport],
+    unused_parameter: "UnusedParameter" => [Lint::UnusedParameter],
+    unused_variable: "UnusedVariable" => [Lint::UnusedVariable],
+    write_only: "WriteOnly" => [Lint::UnusedParameter, Lint::UnusedVariable],
+}
+
+#[test]
+fn allow() {
+    let mut compiler = juicyj::Compiler::new();
+    compiler.add_file("tests/cases/warnings/UnusedVariable.java").unwrap();
+    compiler.add_sources(&juicyj::source::stdlib()).unwrap();
+    compiler.analyze().unwrap();
+
+    compiler.allow(Lint::UnusedVariable);
+    assert!(compiler.warnings().is_empty());
+
+    compiler.warn(Lint::UnusedVariable);
+    assert_eq!(compiler.warnings().len(), 1);
+}
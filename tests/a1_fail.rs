@@ -5,30 +5,16 @@ macro_rules! a1_fail_tests {
     $(
         #[test]
         fn $name() {
-            let stdlib_io = std::fs::read_dir("stdlib/java/io").unwrap();
-            let stdlib_lang = std::fs::read_dir("stdlib/java/lang").unwrap();
-            let stdlib_util = std::fs::read_dir("stdlib/java/util").unwrap();
-
-            let mut asts = Vec::new();
-
-            for path in stdlib_io.chain(stdlib_lang).chain(stdlib_util) {
-                match path.unwrap().path().to_str() {
-                    Some(filename) => {
-                        let src: String = juicyj::scanner::read_src_file(&filename.to_string());
-                        asts.push(juicyj::scanner::tests::scan_or_assert(&filename, &src));
-                    }
-                    _ => (),
-                }
-            }
+            let mut compiler = juicyj::Compiler::new();
+            compiler.add_sources(&juicyj::source::stdlib()).unwrap();
 
             let filename: String = format!("tests/cases/a1/fail/{}.java", $case);
-            let src: String = juicyj::scanner::read_src_file(&filename);
-            match juicyj::scanner::tests::scan_and_assert(&filename, &src) {
-                Some(ast) => asts.push(ast),
-                None => return,
+            compiler.add_file(&filename).unwrap();
+            if juicyj::scanner::tests::scan_and_assert(&mut compiler) {
+                return;
             }
 
-            juicyj::analysis::tests::analyze_and_assert(&asts);
+            juicyj::analysis::tests::analyze_and_assert(&mut compiler);
         }
     )*
     }
@@ -6,27 +6,13 @@ macro_rules! a3_fail_tests {
     $(
         #[test]
         fn $name() {
-            let stdlib_io = std::fs::read_dir("stdlib/java/io").unwrap();
-            let stdlib_lang = std::fs::read_dir("stdlib/java/lang").unwrap();
-            let stdlib_util = std::fs::read_dir("stdlib/java/util").unwrap();
-
-            let mut asts = Vec::new();
-
-            for path in stdlib_io.chain(stdlib_lang).chain(stdlib_util) {
-                match path.unwrap().path().to_str() {
-                    Some(filename) => {
-                        let src: String = juicyj::scanner::read_src_file(&filename.to_string());
-                        asts.push(juicyj::scanner::tests::scan_or_assert(&filename, &src));
-                    }
-                    _ => (),
-                }
-            }
+            let mut compiler = juicyj::Compiler::new();
+            compiler.add_sources(&juicyj::source::stdlib()).unwrap();
 
             let filename: String = format!("tests/cases/a3/fail/{}.java", $case);
-            let src: String = juicyj::scanner::read_src_file(&filename);
-            asts.push(juicyj::scanner::tests::scan_or_assert(&filename, &src));
+            compiler.add_file(&filename).unwrap();
 
-            juicyj::analysis::tests::analyze_and_assert(&asts);
+            juicyj::analysis::tests::analyze_and_assert(&mut compiler);
         }
     )*
     }
@@ -37,39 +23,23 @@ macro_rules! a3_fail_tests_folders {
     $(
         #[test]
         fn $name() {
-            let stdlib_io = std::fs::read_dir("stdlib/java/io").unwrap();
-            let stdlib_lang = std::fs::read_dir("stdlib/java/lang").unwrap();
-            let stdlib_util = std::fs::read_dir("stdlib/java/util").unwrap();
-
-            let mut asts = Vec::new();
-
-            for path in stdlib_io.chain(stdlib_lang).chain(stdlib_util) {
-                match path.unwrap().path().to_str() {
-                    Some(filename) => {
-                        let src: String = juicyj::scanner::read_src_file(&filename.to_string());
-                        asts.push(juicyj::scanner::tests::scan_or_assert(&filename, &src));
-                    }
-                    _ => (),
-                }
-            }
+            let mut compiler = juicyj::Compiler::new();
+            compiler.add_sources(&juicyj::source::stdlib()).unwrap();
 
             for path in walkdir::WalkDir::new(format!("tests/cases/a3/fail/{}", $case)) {
                 match path.unwrap().path().to_str() {
                     Some(filename) if filename.ends_with(".java") => {
-                        let src: String = juicyj::scanner::read_src_file(&filename.to_string());
-                        if format!("{}", $case).starts_with("Je_3_SingleTypeImport_ClashWithEach") {
-                            // these fail as AST building
-                            juicyj::scanner::tests::scan_and_assert(&filename, &src);
-                        } else {
-                            asts.push(juicyj::scanner::tests::scan_or_assert(&filename, &src));
-                        }
+                        compiler.add_file(filename).unwrap()
                     }
                     _ => (),
                 }
             }
 
-            if !format!("{}", $case).starts_with("Je_3_SingleTypeImport_ClashWithEach") {
-                juicyj::analysis::tests::analyze_and_assert(&asts);
+            if format!("{}", $case).starts_with("Je_3_SingleTypeImport_ClashWithEach") {
+                // these fail as AST building
+                juicyj::scanner::tests::scan_and_assert(&mut compiler);
+            } else {
+                juicyj::analysis::tests::analyze_and_assert(&mut compiler);
             }
         }
     )*
@@ -0,0 +1,14 @@
+public class Clean {
+    public int count = 0;
+
+    public Clean() {}
+
+    public int test(int p) {
+        String s = "a";
+        int total = p + count;
+        if (s.equals("a")) {
+            total = total + 1;
+        }
+        return total;
+    }
+}
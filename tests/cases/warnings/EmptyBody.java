@@ -0,0 +1,8 @@
+public class EmptyBody {
+    public EmptyBody() {}
+
+    public int test(int x) {
+        while (x < 3) {}
+        return x;
+    }
+}
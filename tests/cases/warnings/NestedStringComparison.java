@@ -0,0 +1,13 @@
+public class NestedStringComparison {
+    public NestedStringComparison() {}
+
+    public int test(String s) {
+        int n = 0;
+        for (String t = "a"; t == s; t = "b") {
+            if (s != t) {
+                n = n + 1;
+            }
+        }
+        return n;
+    }
+}
@@ -0,0 +1,10 @@
+public class ShadowedField {
+    public int x = 0;
+
+    public ShadowedField() {}
+
+    public int test() {
+        int x = 1;
+        return x;
+    }
+}
@@ -0,0 +1,7 @@
+public class StringComparison {
+    public StringComparison() {}
+
+    public boolean test(String s) {
+        return s == "a";
+    }
+}
@@ -0,0 +1,5 @@
+import java.util.Arrays;
+
+public class UnusedImport {
+    public UnusedImport() {}
+}
@@ -0,0 +1,7 @@
+public class UnusedParameter {
+    public UnusedParameter() {}
+
+    public int test(int x) {
+        return 0;
+    }
+}
@@ -0,0 +1,8 @@
+public class UnusedVariable {
+    public UnusedVariable() {}
+
+    public int test() {
+        int x = 1;
+        return 0;
+    }
+}
@@ -0,0 +1,10 @@
+public class WriteOnly {
+    public WriteOnly() {}
+
+    public int test(int a) {
+        int x = 1;
+        x = 2;
+        a = 3;
+        return 0;
+    }
+}
@@ -0,0 +1,66 @@
+//! Fixtures shared by the integration tests, which compile a few small
+//! classes along with the stdlib and pick apart the results. Each test crate
+//! only uses some of them.
+#![allow(dead_code)]
+
+use juicyj::Compiler;
+use juicyj::analysis::ClassOrInterfaceEnvironment;
+use juicyj::analysis::Environment;
+use juicyj::analysis::MethodEnvironment;
+use juicyj::scanner::ASTNode;
+
+/// The source of a class Foo, with a public constructor and the given
+/// members.
+pub fn foo(members: &str) -> String {
+    format!("public class Foo {{ public Foo() {{}} {} }}", members)
+}
+
+/// A class Bar, to be extended by the class Foo of `foo_extends_bar`.
+pub const BAR: &'static str = "public class Bar { public Bar() {} }";
+
+/// The source of a class Foo extending `BAR`, with a public constructor and
+/// the given members.
+pub fn foo_extends_bar(members: &str) -> String {
+    format!("public class Foo extends Bar {{ public Foo() {{}} {} }}", members)
+}
+
+/// A compiler holding the stdlib and the given files, each as its name and
+/// source.
+pub fn compiler(sources: &[(&str, &str)]) -> Compiler {
+    let mut compiler = Compiler::new();
+    for &(name, source) in sources {
+        compiler.add_source(name, source);
+    }
+    compiler.add_sources(&::juicyj::source::stdlib()).unwrap();
+    compiler
+}
+
+/// Generates the given files, returning the assembly of each class by file
+/// name.
+pub fn generate(sources: &[(&str, &str)]) -> Vec<(String, String)> {
+    compiler(sources).generate().unwrap()
+}
+
+/// The assembly generated into the file with the given name.
+pub fn code<'a>(files: &'a Vec<(String, String)>, name: &str) -> &'a str {
+    &files.iter().find(|&&(ref file, _)| file == name).unwrap().1
+}
+
+/// The class Foo, once analyzed.
+pub fn class(env: &Environment) -> &ClassOrInterfaceEnvironment {
+    env.kinds.iter().find(|k| k.name.source_text() == "juicyj_unnamed.Foo").unwrap()
+}
+
+/// The method `test` of the class Foo, once analyzed.
+pub fn test_method(env: &Environment) -> &MethodEnvironment {
+    class(env).methods.iter().find(|m| m.name.source_text() == "test").unwrap()
+}
+
+/// The expression returned by the first return statement within a node, eg.
+/// a method body.
+pub fn returned(node: &ASTNode) -> Option<ASTNode> {
+    if node.token.lexeme == Some("ReturnStatement".to_owned()) {
+        return Some(node.children[1].clone());
+    }
+    node.children.iter().filter_map(returned).next()
+}